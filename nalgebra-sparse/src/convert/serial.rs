@@ -51,6 +51,11 @@ where
 }
 
 /// Converts a [`CooMatrix`] to a [`CsrMatrix`].
+///
+/// The entries are sorted by row first and column second, as appropriate for the CSR format.
+/// The sort is stable, so duplicate entries are summed in the order in which they appear in
+/// the COO representation. This makes the result deterministic, which matters when
+/// non-associative floating-point summation order affects reproducibility.
 pub fn convert_coo_csr<T>(coo: &CooMatrix<T>) -> CsrMatrix<T>
 where
     T: Scalar + Zero,
@@ -68,6 +73,89 @@ where
         .expect("Internal error: Invalid CSR data during COO->CSR conversion")
 }
 
+/// Options controlling the behavior of [`convert_coo_csr_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CooToCsrOptions {
+    /// Whether entries whose final value is zero are dropped from the result.
+    ///
+    /// When `false`, such entries are retained as explicitly stored zeros, so the result
+    /// preserves the full structural pattern of the COO input.
+    pub drop_zeros: bool,
+    /// Whether duplicate entries are summed.
+    ///
+    /// When `false`, only the first occurrence (in insertion order) of each duplicated
+    /// index pair is kept.
+    pub sum_duplicates: bool,
+}
+
+impl Default for CooToCsrOptions {
+    /// Returns options matching the behavior of [`convert_coo_csr`]: duplicate entries are
+    /// summed and explicit zeros are kept.
+    fn default() -> Self {
+        Self {
+            drop_zeros: false,
+            sum_duplicates: true,
+        }
+    }
+}
+
+/// Converts a [`CooMatrix`] to a [`CsrMatrix`] with explicit control over zeros and duplicates.
+///
+/// With the default options the result is identical to that of [`convert_coo_csr`]. Setting
+/// `drop_zeros` removes entries whose final value is zero, while clearing `sum_duplicates`
+/// keeps only the first occurrence of each duplicated index pair instead of summing. Keeping
+/// explicit zeros (the default) makes it possible to build a fixed sparsity pattern from a
+/// zero-valued COO matrix once and refill the values later.
+pub fn convert_coo_csr_with_options<T>(coo: &CooMatrix<T>, options: CooToCsrOptions) -> CsrMatrix<T>
+where
+    T: Scalar + Zero,
+{
+    let row_indices = coo.row_indices();
+    let col_indices = coo.col_indices();
+    let coo_values = coo.values();
+
+    // A stable sort keeps duplicates in insertion order, so both the summation order and
+    // the choice of first occurrence are deterministic
+    let mut order: Vec<usize> = (0..coo_values.len()).collect();
+    order.sort_by_key(|&k| (row_indices[k], col_indices[k]));
+
+    let mut offsets = Vec::with_capacity(coo.nrows() + 1);
+    let mut indices = Vec::with_capacity(coo_values.len());
+    let mut values = Vec::with_capacity(coo_values.len());
+
+    offsets.push(0);
+    let mut current_row = 0;
+    let mut p = 0;
+    while p < order.len() {
+        let (i, j) = (row_indices[order[p]], col_indices[order[p]]);
+        while current_row < i {
+            offsets.push(indices.len());
+            current_row += 1;
+        }
+
+        let mut value = coo_values[order[p]].clone();
+        p += 1;
+        while p < order.len() && row_indices[order[p]] == i && col_indices[order[p]] == j {
+            if options.sum_duplicates {
+                value = value + coo_values[order[p]].clone();
+            }
+            p += 1;
+        }
+
+        if !options.drop_zeros || !value.is_zero() {
+            indices.push(j);
+            values.push(value);
+        }
+    }
+    while current_row < coo.nrows() {
+        offsets.push(indices.len());
+        current_row += 1;
+    }
+
+    CsrMatrix::try_from_csr_data(coo.nrows(), coo.ncols(), offsets, indices, values)
+        .expect("Internal error: Invalid CSR data during COO->CSR conversion")
+}
+
 /// Converts a [`CsrMatrix`] to a [`CooMatrix`].
 pub fn convert_csr_coo<T: Scalar>(csr: &CsrMatrix<T>) -> CooMatrix<T> {
     let mut result = CooMatrix::new(csr.nrows(), csr.ncols());
@@ -125,6 +213,11 @@ where
 }
 
 /// Converts a [`CooMatrix`] to a [`CscMatrix`].
+///
+/// The entries are sorted by column first and row second, as appropriate for the CSC format.
+/// The sort is stable, so duplicate entries are summed in the order in which they appear in
+/// the COO representation. This makes the result deterministic, which matters when
+/// non-associative floating-point summation order affects reproducibility.
 pub fn convert_coo_csc<T>(coo: &CooMatrix<T>) -> CscMatrix<T>
 where
     T: Scalar + Zero,
@@ -247,6 +340,13 @@ where
     assert_eq!(minor_indices.len(), values.len());
     let nnz = major_indices.len();
 
+    // Triplets generated by iterating a structured problem are typically already in
+    // lexicographic order, in which case the sort below is pure overhead. Detecting this
+    // costs one cheap linear scan and lets us assemble directly, only coalescing duplicates.
+    if is_sorted_lexicographically(major_indices, minor_indices) {
+        return convert_sorted_coo_cs(major_dim, major_indices, minor_indices, values);
+    }
+
     let (unsorted_major_offsets, unsorted_minor_idx, unsorted_vals) = {
         let mut offsets = vec![0usize; major_dim + 1];
         let mut minor_idx = vec![0usize; nnz];
@@ -263,9 +363,6 @@ where
         (offsets, minor_idx, vals)
     };
 
-    // TODO: If input is sorted and/or without duplicates, we can avoid additional allocations
-    // and work. Might want to take advantage of this.
-
     // At this point, assembly is essentially complete. However, we must ensure
     // that minor indices are sorted within each lane and without duplicates.
     let mut sorted_major_offsets = Vec::new();
@@ -316,6 +413,61 @@ where
     (sorted_major_offsets, sorted_minor_idx, sorted_vals)
 }
 
+/// Returns `true` if the triplets described by the index arrays are sorted lexicographically
+/// by major index first and minor index second.
+fn is_sorted_lexicographically(major_indices: &[usize], minor_indices: &[usize]) -> bool {
+    (1..major_indices.len()).all(|k| {
+        (major_indices[k - 1], minor_indices[k - 1]) <= (major_indices[k], minor_indices[k])
+    })
+}
+
+/// Converts *lexicographically sorted* triplet data to CSR/CSC in a single pass, summing
+/// duplicate entries.
+///
+/// Here `major/minor` is `row/col` for CSR and `col/row` for CSC.
+fn convert_sorted_coo_cs<T>(
+    major_dim: usize,
+    major_indices: &[usize],
+    minor_indices: &[usize],
+    values: &[T],
+) -> (Vec<usize>, Vec<usize>, Vec<T>)
+where
+    T: Scalar + Zero,
+{
+    let nnz = major_indices.len();
+    let mut major_offsets = Vec::with_capacity(major_dim + 1);
+    let mut cs_minor_idx = Vec::with_capacity(nnz);
+    let mut cs_values = Vec::with_capacity(nnz);
+
+    major_offsets.push(0);
+    let mut current_major = 0;
+    let mut k = 0;
+    while k < nnz {
+        let major = major_indices[k];
+        while current_major < major {
+            major_offsets.push(cs_minor_idx.len());
+            current_major += 1;
+        }
+
+        // Coalesce any duplicates of this entry; they are necessarily adjacent in sorted input
+        let minor = minor_indices[k];
+        let mut value = values[k].clone();
+        k += 1;
+        while k < nnz && major_indices[k] == major && minor_indices[k] == minor {
+            value = value + values[k].clone();
+            k += 1;
+        }
+        cs_minor_idx.push(minor);
+        cs_values.push(value);
+    }
+    while current_major < major_dim {
+        major_offsets.push(cs_minor_idx.len());
+        current_major += 1;
+    }
+
+    (major_offsets, cs_minor_idx, cs_values)
+}
+
 /// Converts matrix data given in triplet format to unsorted CSR/CSC, retaining any duplicated
 /// indices.
 ///
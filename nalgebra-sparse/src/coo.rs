@@ -46,6 +46,9 @@ pub struct CooMatrix<T> {
     row_indices: Vec<usize>,
     col_indices: Vec<usize>,
     values: Vec<T>,
+    /// Optional provenance tag per triplet, recorded for debugging assembly
+    #[cfg(feature = "provenance")]
+    tags: Vec<Option<usize>>,
 }
 
 impl<T: na::Scalar> CooMatrix<T> {
@@ -80,6 +83,8 @@ impl<T: na::Scalar> CooMatrix<T> {
                 self.row_indices.push(r + row_idx);
                 self.col_indices.push(c + col_idx);
                 self.values.push(v.clone());
+                #[cfg(feature = "provenance")]
+                self.tags.push(None);
             }
         }
     }
@@ -98,6 +103,8 @@ impl<T> CooMatrix<T> {
             row_indices: Vec::new(),
             col_indices: Vec::new(),
             values: Vec::new(),
+            #[cfg(feature = "provenance")]
+            tags: Vec::new(),
         }
     }
 
@@ -116,6 +123,10 @@ impl<T> CooMatrix<T> {
     /// Returns an error if either row or column indices contain indices out of bounds,
     /// or if the data arrays do not all have the same length. Note that the COO format
     /// inherently supports duplicate entries.
+    ///
+    /// The indices are validated in a single pass that stops at the first offending triplet,
+    /// whose position and indices are recorded in the error message. This pinpoints the exact
+    /// bad entry when importing large external triplet data.
     pub fn try_from_triplets(
         nrows: usize,
         ncols: usize,
@@ -136,28 +147,63 @@ impl<T> CooMatrix<T> {
             ));
         }
 
-        let row_indices_in_bounds = row_indices.iter().all(|i| *i < nrows);
-        let col_indices_in_bounds = col_indices.iter().all(|j| *j < ncols);
+        for (t, (&i, &j)) in row_indices.iter().zip(&col_indices).enumerate() {
+            if i >= nrows {
+                return Err(SparseFormatError::from_kind_and_error(
+                    IndexOutOfBounds,
+                    format!(
+                        "Triplet {} = ({}, {}, _) has row index out of bounds \
+                         (matrix has {} rows).",
+                        t, i, j, nrows
+                    )
+                    .into(),
+                ));
+            }
+            if j >= ncols {
+                return Err(SparseFormatError::from_kind_and_error(
+                    IndexOutOfBounds,
+                    format!(
+                        "Triplet {} = ({}, {}, _) has col index out of bounds \
+                         (matrix has {} cols).",
+                        t, i, j, ncols
+                    )
+                    .into(),
+                ));
+            }
+        }
+
+        Ok(Self {
+            nrows,
+            ncols,
+            row_indices,
+            col_indices,
+            #[cfg(feature = "provenance")]
+            tags: vec![None; values.len()],
+            values,
+        })
+    }
 
-        if !row_indices_in_bounds {
-            Err(SparseFormatError::from_kind_and_msg(
-                IndexOutOfBounds,
-                "Row index out of bounds.",
-            ))
-        } else if !col_indices_in_bounds {
-            Err(SparseFormatError::from_kind_and_msg(
-                IndexOutOfBounds,
-                "Col index out of bounds.",
-            ))
-        } else {
-            Ok(Self {
-                nrows,
-                ncols,
-                row_indices,
-                col_indices,
-                values,
-            })
+    /// Construct a COO matrix of the given dimensions by consuming an iterator of
+    /// (i, j, v) triplets.
+    ///
+    /// The indices of each triplet are validated as the iterator is consumed, so that invalid
+    /// input is rejected as soon as it is encountered. This makes it possible to assemble a
+    /// matrix directly from a generator - for example, when reading entries from a file line
+    /// by line - without materializing intermediate triplet arrays.
+    ///
+    /// Panics
+    /// ------
+    ///
+    /// Panics if any triplet has a row or column index out of bounds.
+    pub fn from_iter<I>(nrows: usize, ncols: usize, iter: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, T)>,
+    {
+        let mut coo = Self::new(nrows, ncols);
+        for (i, j, v) in iter {
+            coo.push(i, j, v);
         }
+        coo
     }
 
     /// An iterator over triplets (i, j, v).
@@ -209,6 +255,62 @@ impl<T> CooMatrix<T> {
         self.row_indices.push(i);
         self.col_indices.push(j);
         self.values.push(v);
+        #[cfg(feature = "provenance")]
+        self.tags.push(None);
+    }
+
+    /// Push a single triplet to the matrix together with a provenance tag.
+    ///
+    /// This behaves like [`CooMatrix::push`], but additionally records the user-supplied tag
+    /// for the triplet, so that [`CooMatrix::entry_provenance`] can later report which
+    /// contributions - e.g. which elements in a finite element assembly - produced each final
+    /// entry. Only available when the `provenance` feature is enabled.
+    ///
+    /// Panics
+    /// ------
+    ///
+    /// Panics if `i` or `j` is out of bounds.
+    #[cfg(feature = "provenance")]
+    #[inline]
+    pub fn push_tagged(&mut self, i: usize, j: usize, v: T, tag: usize) {
+        self.push(i, j, v);
+        *self.tags.last_mut().unwrap() = Some(tag);
+    }
+
+    /// Reports, for each distinct entry position, the provenance tags of the triplets that
+    /// contribute to it.
+    ///
+    /// The positions are reported in row-major order - the order in which duplicates are
+    /// summed when converting to CSR - with the tags of each position in insertion order.
+    /// Triplets inserted without a tag (e.g. through [`CooMatrix::push`]) contribute to the
+    /// entry but do not appear in its tag list. This makes it possible to track down which
+    /// contribution corrupted a given entry when debugging assembly. Only available when the
+    /// `provenance` feature is enabled.
+    #[cfg(feature = "provenance")]
+    #[must_use]
+    pub fn entry_provenance(&self) -> Vec<((usize, usize), Vec<usize>)> {
+        let mut order: Vec<usize> = (0..self.values.len()).collect();
+        order.sort_by_key(|&idx| (self.row_indices[idx], self.col_indices[idx], idx));
+
+        let mut result: Vec<((usize, usize), Vec<usize>)> = Vec::new();
+        for idx in order {
+            let position = (self.row_indices[idx], self.col_indices[idx]);
+            match result.last_mut() {
+                Some((last_position, tags)) if *last_position == position => {
+                    if let Some(tag) = self.tags[idx] {
+                        tags.push(tag);
+                    }
+                }
+                _ => {
+                    let mut tags = Vec::new();
+                    if let Some(tag) = self.tags[idx] {
+                        tags.push(tag);
+                    }
+                    result.push((position, tags));
+                }
+            }
+        }
+        result
     }
 
     /// Clear all triplets from the matrix.
@@ -216,6 +318,8 @@ impl<T> CooMatrix<T> {
         self.col_indices.clear();
         self.row_indices.clear();
         self.values.clear();
+        #[cfg(feature = "provenance")]
+        self.tags.clear();
     }
 
     /// The number of rows in the matrix.
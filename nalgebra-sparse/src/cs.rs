@@ -1,5 +1,6 @@
 use std::mem::replace;
 use std::ops::Range;
+use std::sync::Arc;
 
 use num_traits::One;
 
@@ -18,7 +19,11 @@ use crate::{SparseEntry, SparseEntryMut, SparseFormatError, SparseFormatErrorKin
 /// is obtained by associating columns with the major dimension.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CsMatrix<T> {
-    sparsity_pattern: SparsityPattern,
+    // The pattern is kept behind an `Arc` so that matrices can share one pattern
+    // allocation, e.g. through `PatternInterner`. The `Arc` is an implementation detail:
+    // the public API hands out plain (references to) `SparsityPattern`, and mutation goes
+    // through `Arc::make_mut`.
+    sparsity_pattern: Arc<SparsityPattern>,
     values: Vec<T>,
 }
 
@@ -27,7 +32,7 @@ impl<T> CsMatrix<T> {
     #[inline]
     pub fn new(major_dim: usize, minor_dim: usize) -> Self {
         Self {
-            sparsity_pattern: SparsityPattern::zeros(major_dim, minor_dim),
+            sparsity_pattern: Arc::new(SparsityPattern::zeros(major_dim, minor_dim)),
             values: vec![],
         }
     }
@@ -38,6 +43,25 @@ impl<T> CsMatrix<T> {
         &self.sparsity_pattern
     }
 
+    /// The shared handle to the sparsity pattern of the matrix.
+    #[inline]
+    #[must_use]
+    pub fn pattern_arc(&self) -> &Arc<SparsityPattern> {
+        &self.sparsity_pattern
+    }
+
+    /// Replaces the pattern handle of the matrix with another handle to an equal pattern.
+    ///
+    /// This does not change the matrix; it only substitutes the backing allocation, so that
+    /// matrices interned against the same canonical pattern share memory.
+    pub fn replace_pattern_arc(&mut self, pattern: Arc<SparsityPattern>) {
+        debug_assert!(
+            *pattern == *self.sparsity_pattern,
+            "Internal error: The replacement pattern must be equal to the current pattern."
+        );
+        self.sparsity_pattern = pattern;
+    }
+
     #[inline]
     #[must_use]
     pub fn values(&self) -> &[T] {
@@ -49,6 +73,19 @@ impl<T> CsMatrix<T> {
         &mut self.values
     }
 
+    /// The number of bytes of memory used by the pattern and value arrays, accounting for the
+    /// capacities of the backing arrays rather than their lengths.
+    #[must_use]
+    pub fn memory_bytes(&self) -> usize {
+        self.sparsity_pattern.memory_bytes() + self.values.capacity() * std::mem::size_of::<T>()
+    }
+
+    /// Shrinks the capacity of the backing arrays to their lengths.
+    pub fn shrink_to_fit(&mut self) {
+        Arc::make_mut(&mut self.sparsity_pattern).shrink_to_fit();
+        self.values.shrink_to_fit();
+    }
+
     /// Returns the raw data represented as a tuple `(major_offsets, minor_indices, values)`.
     #[inline]
     #[must_use]
@@ -64,7 +101,7 @@ impl<T> CsMatrix<T> {
     /// Returns the raw data represented as a tuple `(major_offsets, minor_indices, values)`.
     #[inline]
     pub fn cs_data_mut(&mut self) -> (&[usize], &[usize], &mut [T]) {
-        let pattern = &mut self.sparsity_pattern;
+        let pattern = &self.sparsity_pattern;
         (
             pattern.major_offsets(),
             pattern.minor_indices(),
@@ -79,6 +116,22 @@ impl<T> CsMatrix<T> {
 
     #[inline]
     pub fn from_pattern_and_values(pattern: SparsityPattern, values: Vec<T>) -> Self {
+        assert_eq!(
+            pattern.nnz(),
+            values.len(),
+            "Internal error: consumers should verify shape compatibility."
+        );
+        Self {
+            sparsity_pattern: Arc::new(pattern),
+            values,
+        }
+    }
+
+    /// Constructs a matrix from a shared pattern handle, which is stored directly so that
+    /// the resulting matrix shares the pattern allocation with the other holders of the
+    /// handle.
+    #[inline]
+    pub fn from_shared_pattern_and_values(pattern: Arc<SparsityPattern>, values: Vec<T>) -> Self {
         assert_eq!(
             pattern.nnz(),
             values.len(),
@@ -100,18 +153,21 @@ impl<T> CsMatrix<T> {
     }
 
     pub fn take_pattern_and_values(self) -> (SparsityPattern, Vec<T>) {
-        (self.sparsity_pattern, self.values)
+        let pattern = Arc::try_unwrap(self.sparsity_pattern).unwrap_or_else(|p| (*p).clone());
+        (pattern, self.values)
     }
 
     #[inline]
     pub fn disassemble(self) -> (Vec<usize>, Vec<usize>, Vec<T>) {
-        let (offsets, indices) = self.sparsity_pattern.disassemble();
+        let pattern = Arc::try_unwrap(self.sparsity_pattern).unwrap_or_else(|p| (*p).clone());
+        let (offsets, indices) = pattern.disassemble();
         (offsets, indices, self.values)
     }
 
     #[inline]
     pub fn into_pattern_and_values(self) -> (SparsityPattern, Vec<T>) {
-        (self.sparsity_pattern, self.values)
+        let pattern = Arc::try_unwrap(self.sparsity_pattern).unwrap_or_else(|p| (*p).clone());
+        (pattern, self.values)
     }
 
     /// Returns an entry for the given major/minor indices, or `None` if the indices are out
@@ -289,6 +345,8 @@ pub struct CsLaneMut<'a, T> {
 pub struct CsLaneIter<'a, T> {
     // The index of the lane that will be returned on the next iteration
     current_lane_idx: usize,
+    // One past the index of the lane that will be returned by the next iteration from the back
+    end_lane_idx: usize,
     pattern: &'a SparsityPattern,
     remaining_values: &'a [T],
 }
@@ -297,6 +355,7 @@ impl<'a, T> CsLaneIter<'a, T> {
     pub fn new(pattern: &'a SparsityPattern, values: &'a [T]) -> Self {
         Self {
             current_lane_idx: 0,
+            end_lane_idx: pattern.major_dim(),
             pattern,
             remaining_values: values,
         }
@@ -310,6 +369,9 @@ where
     type Item = CsLane<'a, T>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.current_lane_idx >= self.end_lane_idx {
+            return None;
+        }
         let lane = self.pattern.get_lane(self.current_lane_idx);
         let minor_dim = self.pattern.minor_dim();
 
@@ -328,11 +390,49 @@ where
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end_lane_idx - self.current_lane_idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for CsLaneIter<'a, T>
+where
+    T: 'a,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current_lane_idx >= self.end_lane_idx {
+            return None;
+        }
+        self.end_lane_idx -= 1;
+        let lane = self.pattern.get_lane(self.end_lane_idx);
+        let minor_dim = self.pattern.minor_dim();
+
+        if let Some(minor_indices) = lane {
+            let count = minor_indices.len();
+            let split = self.remaining_values.len() - count;
+            let values_in_lane = &self.remaining_values[split..];
+            self.remaining_values = &self.remaining_values[..split];
+
+            Some(CsLane {
+                minor_dim,
+                minor_indices,
+                values: values_in_lane,
+            })
+        } else {
+            None
+        }
+    }
 }
 
+impl<'a, T> ExactSizeIterator for CsLaneIter<'a, T> where T: 'a {}
+
 pub struct CsLaneIterMut<'a, T> {
     // The index of the lane that will be returned on the next iteration
     current_lane_idx: usize,
+    // One past the index of the lane that will be returned by the next iteration from the back
+    end_lane_idx: usize,
     pattern: &'a SparsityPattern,
     remaining_values: &'a mut [T],
 }
@@ -341,6 +441,7 @@ impl<'a, T> CsLaneIterMut<'a, T> {
     pub fn new(pattern: &'a SparsityPattern, values: &'a mut [T]) -> Self {
         Self {
             current_lane_idx: 0,
+            end_lane_idx: pattern.major_dim(),
             pattern,
             remaining_values: values,
         }
@@ -354,6 +455,9 @@ where
     type Item = CsLaneMut<'a, T>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.current_lane_idx >= self.end_lane_idx {
+            return None;
+        }
         let lane = self.pattern.get_lane(self.current_lane_idx);
         let minor_dim = self.pattern.minor_dim();
 
@@ -374,8 +478,46 @@ where
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end_lane_idx - self.current_lane_idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for CsLaneIterMut<'a, T>
+where
+    T: 'a,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current_lane_idx >= self.end_lane_idx {
+            return None;
+        }
+        self.end_lane_idx -= 1;
+        let lane = self.pattern.get_lane(self.end_lane_idx);
+        let minor_dim = self.pattern.minor_dim();
+
+        if let Some(minor_indices) = lane {
+            let count = minor_indices.len();
+
+            let remaining = replace(&mut self.remaining_values, &mut []);
+            let split = remaining.len() - count;
+            let (remaining, values_in_lane) = remaining.split_at_mut(split);
+            self.remaining_values = remaining;
+
+            Some(CsLaneMut {
+                minor_dim,
+                minor_indices,
+                values: values_in_lane,
+            })
+        } else {
+            None
+        }
+    }
 }
 
+impl<'a, T> ExactSizeIterator for CsLaneIterMut<'a, T> where T: 'a {}
+
 /// Implement the methods common to both CsLane and CsLaneMut. See the documentation for the
 /// methods delegated here by CsrMatrix and CscMatrix members for more information.
 macro_rules! impl_cs_lane_common_methods {
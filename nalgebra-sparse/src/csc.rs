@@ -7,13 +7,16 @@
 mod csc_serde;
 
 use crate::cs;
+use crate::convert::serial::convert_csc_coo;
+use crate::coo::CooMatrix;
 use crate::cs::{CsLane, CsLaneIter, CsLaneIterMut, CsLaneMut, CsMatrix};
 use crate::csr::CsrMatrix;
 use crate::pattern::{SparsityPattern, SparsityPatternFormatError, SparsityPatternIter};
 use crate::{SparseEntry, SparseEntryMut, SparseFormatError, SparseFormatErrorKind};
 
-use nalgebra::Scalar;
-use num_traits::One;
+use crate::ops::serial::{OperationError, OperationErrorKind};
+use nalgebra::{ComplexField, DVector, RealField, Scalar};
+use num_traits::{One, Zero};
 use std::slice::{Iter, IterMut};
 
 /// A CSC representation of a sparse matrix.
@@ -157,6 +160,11 @@ impl<T> CscMatrix<T> {
     ///
     /// An error is returned if the data given does not conform to the CSC storage format.
     /// See the documentation for [CscMatrix](struct.CscMatrix.html) for more information.
+    ///
+    /// The arrays correspond to the `indptr`, `indices` and `data` arrays of e.g. SciPy's
+    /// `csc_matrix`. Note however that unlike SciPy, the row indices within each column are
+    /// required to be sorted; use [`CscMatrix::try_from_unsorted_csc_data`] for data that
+    /// does not satisfy this requirement.
     pub fn try_from_csc_data(
         num_rows: usize,
         num_cols: usize,
@@ -164,6 +172,38 @@ impl<T> CscMatrix<T> {
         row_indices: Vec<usize>,
         values: Vec<T>,
     ) -> Result<Self, SparseFormatError> {
+        // When the per-column data is invalid, produce an error naming the offending column.
+        // Structural problems with the offset array itself are still diagnosed by the
+        // pattern constructor below.
+        if col_offsets.len() == num_cols + 1
+            && col_offsets.first() == Some(&0)
+            && col_offsets.last() == Some(&row_indices.len())
+            && col_offsets.windows(2).all(|window| window[0] <= window[1])
+        {
+            for j in 0..num_cols {
+                let col = &row_indices[col_offsets[j]..col_offsets[j + 1]];
+                if let Some(&i) = col.iter().find(|&&i| i >= num_rows) {
+                    return Err(SparseFormatError::from_kind_and_error(
+                        SparseFormatErrorKind::IndexOutOfBounds,
+                        format!("Row index {} in column {} is out of bounds.", i, j).into(),
+                    ));
+                }
+                if let Some(window) = col.windows(2).find(|window| window[0] >= window[1]) {
+                    return Err(if window[0] == window[1] {
+                        SparseFormatError::from_kind_and_error(
+                            SparseFormatErrorKind::DuplicateEntry,
+                            format!("Duplicate row index {} in column {}.", window[0], j).into(),
+                        )
+                    } else {
+                        SparseFormatError::from_kind_and_error(
+                            SparseFormatErrorKind::InvalidStructure,
+                            format!("Row indices in column {} are not sorted.", j).into(),
+                        )
+                    });
+                }
+            }
+        }
+
         let pattern = SparsityPattern::try_from_offsets_and_indices(
             num_cols,
             num_rows,
@@ -390,6 +430,11 @@ impl<T> CscMatrix<T> {
     }
 
     /// A mutable iterator over columns in the matrix.
+    ///
+    /// This is the CSC analogue of [`CsrMatrix::row_iter_mut`](crate::csr::CsrMatrix::row_iter_mut).
+    /// Each item is a [`CscColMut`] that gives mutable access to the values of a single column
+    /// through [`CscColMut::values_mut`] or [`CscColMut::rows_and_values_mut`], which is the
+    /// primary building block for writing column-oriented kernels.
     pub fn col_iter_mut(&mut self) -> CscColIterMut<'_, T> {
         let (pattern, values) = self.cs.pattern_and_values_mut();
         CscColIterMut {
@@ -570,6 +615,184 @@ impl<T> CscMatrix<T> {
     {
         CsrMatrix::from(self).transpose_as_csc()
     }
+
+    /// Converts the matrix to a [`CooMatrix`], with one triplet per explicitly stored entry.
+    ///
+    /// The triplets are produced in column-major order, i.e. the order in which the entries
+    /// are stored in the CSC format. See also [`CsrMatrix::to_coo`](crate::csr::CsrMatrix::to_coo).
+    #[must_use]
+    pub fn to_coo(&self) -> CooMatrix<T>
+    where
+        T: Scalar,
+    {
+        convert_csc_coo(self)
+    }
+
+    /// Returns the indices of all columns without any explicitly stored entries.
+    ///
+    /// Note that this detects *structural* emptiness: a column that stores explicit zeros is
+    /// not reported, even though it is numerically zero. See
+    /// [`CscMatrix::numerically_zero_cols`] for the numerical counterpart. Only the offset
+    /// array is read, so the cost is `O(ncols)`.
+    #[must_use]
+    pub fn empty_cols(&self) -> Vec<usize> {
+        self.col_offsets()
+            .windows(2)
+            .enumerate()
+            .filter(|(_, w)| w[0] == w[1])
+            .map(|(j, _)| j)
+            .collect()
+    }
+
+    /// Returns `true` if any column has no explicitly stored entries.
+    ///
+    /// See [`CscMatrix::empty_cols`] for the precise semantics.
+    #[must_use]
+    pub fn has_empty_col(&self) -> bool {
+        self.col_offsets().windows(2).any(|w| w[0] == w[1])
+    }
+
+    /// Returns the indices of all columns whose stored entries all have modulus at most `tol`.
+    ///
+    /// In contrast to [`CscMatrix::empty_cols`], this detects *numerical* emptiness:
+    /// structurally empty columns are reported, but so are columns that only store entries
+    /// indistinguishable from zero up to the given tolerance.
+    #[must_use]
+    pub fn numerically_zero_cols(&self, tol: T::RealField) -> Vec<usize>
+    where
+        T: ComplexField,
+    {
+        self.col_iter()
+            .enumerate()
+            .filter(|(_, col)| {
+                col.values()
+                    .iter()
+                    .all(|v| v.clone().modulus() <= tol.clone())
+            })
+            .map(|(j, _)| j)
+            .collect()
+    }
+
+    /// Computes the maximum modulus of the stored entries of each column.
+    ///
+    /// Entry `j` of the result is the largest modulus among the explicitly stored entries of
+    /// column `j`, or zero for columns without stored entries. This is the per-column half of
+    /// matrix equilibration; see
+    /// [`CsrMatrix::ruiz_equilibration`](crate::csr::CsrMatrix::ruiz_equilibration) for the
+    /// row counterpart and the full scaling loop. The cost is a single pass over the stored
+    /// entries.
+    #[must_use]
+    pub fn column_max_abs(&self) -> DVector<T::RealField>
+    where
+        T: ComplexField,
+    {
+        DVector::from_iterator(
+            self.ncols(),
+            self.col_iter().map(|col| {
+                col.values()
+                    .iter()
+                    .fold(T::RealField::zero(), |max, v| max.max(v.clone().modulus()))
+            }),
+        )
+    }
+
+    /// Computes the entrywise absolute value of the matrix.
+    ///
+    /// The result has the same sparsity pattern as this matrix, with each stored value replaced
+    /// by its magnitude: the absolute value for real input, and the modulus for complex input.
+    /// This is useful e.g. for entrywise spectral radius bounds.
+    #[must_use]
+    pub fn abs(&self) -> CscMatrix<T::RealField>
+    where
+        T: ComplexField,
+    {
+        let values = self.values().iter().map(|v| v.clone().modulus()).collect();
+        CscMatrix::try_from_pattern_and_values(self.pattern().clone(), values)
+            .expect("Internal error: Pattern and values must be compatible by construction")
+    }
+
+    /// Solves the lower triangular system `L x = b` in place by forward substitution.
+    ///
+    /// The matrix is interpreted as a lower triangular matrix `L`: entries above the diagonal
+    /// are ignored. On successful return, `b` has been overwritten with the solution `x`.
+    /// If `unit_diagonal` is `true`, the diagonal of `L` is taken to be all ones and any stored
+    /// diagonal entries are ignored; otherwise each diagonal entry must be explicitly stored
+    /// and non-zero.
+    ///
+    /// The substitution proceeds column by column, which matches the storage order of the
+    /// matrix and therefore accesses the stored entries sequentially.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind `Singular` if `unit_diagonal` is `false` and a diagonal entry
+    /// is missing from the sparsity pattern or is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square, or if the dimension of `b` is not compatible with
+    /// the matrix.
+    pub fn solve_lower_triangular_vector(
+        &self,
+        b: &mut DVector<T>,
+        unit_diagonal: bool,
+    ) -> Result<(), OperationError>
+    where
+        T: RealField,
+    {
+        assert_eq!(
+            self.nrows(),
+            self.ncols(),
+            "Matrix must be square for triangular solve."
+        );
+        assert_eq!(
+            self.nrows(),
+            b.nrows(),
+            "Dimension mismatch in sparse lower triangular solver."
+        );
+
+        for k in 0..self.ncols() {
+            let col_k = self.col(k);
+            let row_indices = col_k.row_indices();
+            let diag_pos = row_indices.iter().position(|&i| i == k);
+
+            // Index of the first stored entry strictly below the diagonal.
+            let below_diag = if unit_diagonal {
+                match diag_pos {
+                    Some(pos) => pos + 1,
+                    None => row_indices
+                        .iter()
+                        .position(|&i| i > k)
+                        .unwrap_or(row_indices.len()),
+                }
+            } else {
+                match diag_pos {
+                    Some(pos) if col_k.values()[pos] != T::zero() => {
+                        let l_kk = col_k.values()[pos].clone();
+                        b[k] /= l_kk;
+                        pos + 1
+                    }
+                    _ => {
+                        return Err(OperationError::from_kind_and_message(
+                            OperationErrorKind::Singular,
+                            String::from(
+                                "Matrix contains at least one diagonal entry that is zero.",
+                            ),
+                        ))
+                    }
+                }
+            };
+
+            let x_k = b[k].clone();
+            for (&i, l_ik) in row_indices[below_diag..]
+                .iter()
+                .zip(&col_k.values()[below_diag..])
+            {
+                b[i] -= l_ik.clone() * x_k.clone();
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Convert pattern format errors into more meaningful CSC-specific errors.
@@ -757,8 +980,20 @@ impl<'a, T> Iterator for CscColIter<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.lane_iter.next().map(|lane| CscCol { lane })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lane_iter.size_hint()
+    }
 }
 
+impl<'a, T> DoubleEndedIterator for CscColIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.lane_iter.next_back().map(|lane| CscCol { lane })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for CscColIter<'a, T> {}
+
 /// Mutable column iterator for [CscMatrix](struct.CscMatrix.html).
 pub struct CscColIterMut<'a, T> {
     lane_iter: CsLaneIterMut<'a, T>,
@@ -773,4 +1008,19 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         self.lane_iter.next().map(|lane| CscColMut { lane })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lane_iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for CscColIterMut<'a, T>
+where
+    T: 'a,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.lane_iter.next_back().map(|lane| CscColMut { lane })
+    }
 }
+
+impl<'a, T> ExactSizeIterator for CscColIterMut<'a, T> where T: 'a {}
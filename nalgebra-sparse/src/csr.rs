@@ -6,16 +6,29 @@
 #[cfg(feature = "serde-serialize")]
 mod csr_serde;
 
+use crate::convert::serial::convert_csr_coo;
+use crate::coo::CooMatrix;
 use crate::cs;
+use crate::ops::serial::{spadd_pattern, OperationError, OperationErrorKind};
 use crate::cs::{CsLane, CsLaneIter, CsLaneIterMut, CsLaneMut, CsMatrix};
 use crate::csc::CscMatrix;
+use crate::factorization::{CscCholesky, LinearSolver};
 use crate::pattern::{SparsityPattern, SparsityPatternFormatError, SparsityPatternIter};
+use crate::sparse_vector::SparseVector;
 use crate::{SparseEntry, SparseEntryMut, SparseFormatError, SparseFormatErrorKind};
 
-use nalgebra::Scalar;
-use num_traits::One;
+use nalgebra::{
+    ClosedAdd, ClosedDiv, ClosedMul, ClosedSub, ComplexField, DMatrix, DVector, Field, RealField,
+    Scalar,
+};
+use num_traits::{NumCast, One, PrimInt, ToPrimitive, Zero};
+#[cfg(feature = "rand")]
+use rand::Rng;
 
+use std::collections::HashMap;
+use std::ops::{Neg, Range};
 use std::slice::{Iter, IterMut};
+use std::sync::Arc;
 
 /// A CSR representation of a sparse matrix.
 ///
@@ -131,6 +144,26 @@ pub struct CsrMatrix<T> {
     pub(crate) cs: CsMatrix<T>,
 }
 
+/// The policy applied by [`CsrMatrix::normalize_rows`] to rows whose entries sum to zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ZeroRowPolicy {
+    /// Leave the entries of the row unchanged.
+    LeaveUnchanged,
+    /// Distribute the weight uniformly over the explicitly stored entries of the row, so that
+    /// each entry becomes the reciprocal of the number of stored entries. Rows without any
+    /// stored entries are left unchanged.
+    Uniform,
+}
+
+/// The traversal order of a relaxation sweep such as [`CsrMatrix::sor_sweep`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SweepDirection {
+    /// Traverse the rows from first to last.
+    Forward,
+    /// Traverse the rows from last to first.
+    Backward,
+}
+
 impl<T> CsrMatrix<T> {
     /// Constructs a CSR representation of the (square) `n x n` identity matrix.
     #[inline]
@@ -150,6 +183,91 @@ impl<T> CsrMatrix<T> {
         }
     }
 
+    /// Constructs an `n x n` banded matrix from a one-dimensional stencil.
+    ///
+    /// Row `i` of the result has the entry `coeffs[k]` at column `i + offsets[k]` for every
+    /// stencil point `k`. This directly produces the banded matrices arising from
+    /// finite-difference discretizations; for example, the classic 1D Laplacian stencil
+    /// `[-1, 2, -1]` with offsets `[-1, 0, 1]` yields the familiar tridiagonal matrix.
+    ///
+    /// The boundary policy is truncation: stencil points whose column `i + offset` falls
+    /// outside `0..n` are simply skipped for that row, so the first and last rows contain
+    /// fewer entries than interior rows. No boundary modification of the coefficients is
+    /// performed; schemes that require e.g. one-sided boundary stencils should adjust the
+    /// affected rows afterwards.
+    ///
+    /// The offsets may be given in any order, but must be distinct.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `offsets` and `coeffs` have different lengths, or if `offsets` contains
+    /// duplicate entries.
+    pub fn from_stencil_1d(n: usize, offsets: &[isize], coeffs: &[T]) -> Self
+    where
+        T: Scalar,
+    {
+        assert_eq!(
+            offsets.len(),
+            coeffs.len(),
+            "The number of stencil offsets and coefficients must be the same."
+        );
+
+        // Sort the stencil points by offset so that each row has sorted column indices
+        let mut stencil: Vec<(isize, &T)> = offsets.iter().copied().zip(coeffs).collect();
+        stencil.sort_unstable_by_key(|(offset, _)| *offset);
+        assert!(
+            stencil.windows(2).all(|w| w[0].0 != w[1].0),
+            "Stencil offsets must be distinct."
+        );
+
+        let mut row_offsets = Vec::with_capacity(n + 1);
+        let mut col_indices = Vec::new();
+        let mut values = Vec::new();
+        row_offsets.push(0);
+        for i in 0..n {
+            for &(offset, coeff) in &stencil {
+                let j = i as isize + offset;
+                if j >= 0 && (j as usize) < n {
+                    col_indices.push(j as usize);
+                    values.push(coeff.clone());
+                }
+            }
+            row_offsets.push(col_indices.len());
+        }
+
+        Self::try_from_csr_data(n, n, row_offsets, col_indices, values)
+            .expect("Internal error: Stencil construction must produce valid CSR data")
+    }
+
+    /// Constructs a CSR matrix from a dense boolean mask and a value generator.
+    ///
+    /// The result has a structural entry at every position where the mask is `true`, with
+    /// the value `f(i, j)`. This separates the specification of the sparsity structure from
+    /// the computation of the values, which is convenient for building matrices with a
+    /// specific pattern in tests. The pattern is built directly from the mask by row-major
+    /// traversal, so the column indices of each row are sorted by construction.
+    pub fn from_mask(mask: &DMatrix<bool>, f: impl Fn(usize, usize) -> T) -> Self
+    where
+        T: Scalar,
+    {
+        let mut row_offsets = Vec::with_capacity(mask.nrows() + 1);
+        let mut col_indices = Vec::new();
+        let mut values = Vec::new();
+        row_offsets.push(0);
+        for i in 0..mask.nrows() {
+            for j in 0..mask.ncols() {
+                if mask[(i, j)] {
+                    col_indices.push(j);
+                    values.push(f(i, j));
+                }
+            }
+            row_offsets.push(col_indices.len());
+        }
+
+        Self::try_from_csr_data(mask.nrows(), mask.ncols(), row_offsets, col_indices, values)
+            .expect("Internal error: Mask construction must produce valid CSR data")
+    }
+
     /// Try to construct a CSR matrix from raw CSR data.
     ///
     /// It is assumed that each row contains unique and sorted column indices that are in
@@ -175,6 +293,97 @@ impl<T> CsrMatrix<T> {
         Self::try_from_pattern_and_values(pattern, values)
     }
 
+    /// Try to construct a CSR matrix from a shared sparsity pattern and an array of values.
+    ///
+    /// This is useful when the structure of the matrix is fixed but the values change over
+    /// time, such as in time-dependent simulations: the pattern can be cached in an
+    /// [`Arc`] - possibly deserialized once through the `serde-serialize` feature - and new
+    /// matrices can be constructed by pairing it with fresh value arrays. The handle is
+    /// stored directly, so all matrices constructed from the same `Arc` share a single
+    /// pattern allocation. See also [`PatternInterner`](crate::pattern::PatternInterner) for
+    /// canonicalizing the patterns of independently constructed matrices.
+    ///
+    /// Returns an error if the number of values does not match the number of minor indices
+    /// in the pattern.
+    pub fn try_from_shared_pattern_and_values(
+        pattern: Arc<SparsityPattern>,
+        values: Vec<T>,
+    ) -> Result<Self, SparseFormatError> {
+        if pattern.nnz() == values.len() {
+            Ok(Self {
+                cs: CsMatrix::from_shared_pattern_and_values(pattern, values),
+            })
+        } else {
+            Err(SparseFormatError::from_kind_and_msg(
+                SparseFormatErrorKind::InvalidStructure,
+                "Number of values and column indices must be the same",
+            ))
+        }
+    }
+
+    /// Try to construct a CSR matrix from raw CSR data with a generic integer index type.
+    ///
+    /// This is a convenience for interop with libraries that store offsets and indices with
+    /// e.g. `i32` or `u32` instead of `usize`. The index arrays are converted to `usize`
+    /// before the usual format validation is performed.
+    ///
+    /// In addition to the errors produced by [`try_from_csr_data`](Self::try_from_csr_data),
+    /// an error is returned if any offset or index is negative or cannot be represented
+    /// by `usize`.
+    ///
+    /// Note that the matrix still stores its indices as `usize` internally; see
+    /// [`csr_data_with_index_type`](Self::csr_data_with_index_type) for the converse
+    /// conversion when exporting to an external library.
+    pub fn try_from_csr_data_generic_indices<I>(
+        num_rows: usize,
+        num_cols: usize,
+        row_offsets: Vec<I>,
+        col_indices: Vec<I>,
+        values: Vec<T>,
+    ) -> Result<Self, SparseFormatError>
+    where
+        I: PrimInt,
+    {
+        let convert = |indices: Vec<I>| -> Result<Vec<usize>, SparseFormatError> {
+            indices
+                .into_iter()
+                .map(|i| {
+                    i.to_usize().ok_or_else(|| {
+                        SparseFormatError::from_kind_and_msg(
+                            SparseFormatErrorKind::IndexOutOfBounds,
+                            "An offset or index cannot be represented by usize.",
+                        )
+                    })
+                })
+                .collect()
+        };
+        Self::try_from_csr_data(
+            num_rows,
+            num_cols,
+            convert(row_offsets)?,
+            convert(col_indices)?,
+            values,
+        )
+    }
+
+    /// Returns the CSR data with offsets and column indices converted to the given integer
+    /// index type, or `None` if any of them cannot be represented by `I`.
+    ///
+    /// This is a convenience for exporting the matrix to libraries that expect e.g. `i32` or
+    /// `u32` index arrays. The values are borrowed as-is, while new index arrays are allocated
+    /// for the converted offsets and indices.
+    #[must_use]
+    pub fn csr_data_with_index_type<I>(&self) -> Option<(Vec<I>, Vec<I>, &[T])>
+    where
+        I: PrimInt,
+    {
+        let convert = |indices: &[usize]| -> Option<Vec<I>> {
+            indices.iter().map(|&i| I::from(i)).collect()
+        };
+        let (offsets, indices, values) = self.csr_data();
+        Some((convert(offsets)?, convert(indices)?, values))
+    }
+
     /// Try to construct a CSR matrix from raw CSR data with unsorted column indices.
     ///
     /// It is assumed that each row contains unique column indices that are in
@@ -219,6 +428,144 @@ impl<T> CsrMatrix<T> {
         }
     }
 
+    /// Try to construct a CSR matrix from raw CSR data with unsorted column indices,
+    /// summing duplicate entries.
+    ///
+    /// This is the robust import path for data produced by tools that guarantee neither
+    /// sorted column indices within each row nor the absence of duplicates: each row is
+    /// sorted during construction (with values permuted correspondingly), and entries that
+    /// share a column index within a row are coalesced by summation. Duplicates are summed
+    /// in the order in which they appear, which makes the result deterministic for
+    /// non-associative floating-point addition.
+    ///
+    /// Compared to [`try_from_csr_data`](Self::try_from_csr_data), which only validates,
+    /// this performs an additional `O(nnz log nnz)` sort and copies the index and value
+    /// arrays, so prefer the strict constructor when the input is known to be sorted.
+    /// [`try_from_unsorted_csr_data`](Self::try_from_unsorted_csr_data) also sorts, but
+    /// treats duplicate entries as an error instead of summing them.
+    ///
+    /// Returns an error if the data does not conform to the CSR storage format with the
+    /// exception of having unsorted or duplicate column indices.
+    /// See the documentation for [CsrMatrix](struct.CsrMatrix.html) for more information.
+    pub fn from_csr_data_unsorted(
+        num_rows: usize,
+        num_cols: usize,
+        row_offsets: Vec<usize>,
+        col_indices: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<Self, SparseFormatError>
+    where
+        T: Scalar + ClosedAdd,
+    {
+        use SparseFormatErrorKind as K;
+        if row_offsets.len() != num_rows + 1 {
+            return Err(SparseFormatError::from_kind_and_msg(
+                K::InvalidStructure,
+                "Length of row offset array is not equal to nrows + 1.",
+            ));
+        }
+        if col_indices.len() != values.len() {
+            return Err(SparseFormatError::from_kind_and_msg(
+                K::InvalidStructure,
+                "Number of col indices and values must be the same.",
+            ));
+        }
+        if row_offsets.first() != Some(&0) || *row_offsets.last().unwrap() != col_indices.len() {
+            return Err(SparseFormatError::from_kind_and_msg(
+                K::InvalidStructure,
+                "First or last row offset is inconsistent with format specification.",
+            ));
+        }
+        if !row_offsets.windows(2).all(|w| w[0] <= w[1]) {
+            return Err(SparseFormatError::from_kind_and_msg(
+                K::InvalidStructure,
+                "Row offsets are not monotonically increasing.",
+            ));
+        }
+
+        let mut offsets = Vec::with_capacity(num_rows + 1);
+        let mut sorted_cols = Vec::with_capacity(col_indices.len());
+        let mut sorted_vals = Vec::with_capacity(values.len());
+        offsets.push(0);
+
+        // Workspace holding the entry order of the current row, reused across rows
+        let mut order = Vec::new();
+        for r in 0..num_rows {
+            order.clear();
+            order.extend(row_offsets[r]..row_offsets[r + 1]);
+            // The sort is stable, so duplicates are summed in their order of appearance
+            order.sort_by_key(|&k| col_indices[k]);
+
+            let mut idx = 0;
+            while idx < order.len() {
+                let j = col_indices[order[idx]];
+                let mut value = values[order[idx]].clone();
+                idx += 1;
+                while idx < order.len() && col_indices[order[idx]] == j {
+                    value += values[order[idx]].clone();
+                    idx += 1;
+                }
+                sorted_cols.push(j);
+                sorted_vals.push(value);
+            }
+            offsets.push(sorted_cols.len());
+        }
+
+        // The remaining invariants (column indices in bounds) are verified by the strict
+        // constructor
+        Self::try_from_csr_data(num_rows, num_cols, offsets, sorted_cols, sorted_vals)
+    }
+
+    /// Construct a CSR matrix of the given dimensions by consuming an iterator of
+    /// (i, j, v) triplets, summing duplicate entries.
+    ///
+    /// The triplets are bucketed into rows as the iterator is consumed, and duplicate entries -
+    /// triplets that share both row and column index - are summed together. The indices of each
+    /// triplet are validated lazily during consumption, so that invalid input is rejected as
+    /// soon as it is encountered. This makes it possible to assemble a matrix directly from a
+    /// generator without materializing intermediate triplet arrays.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if any triplet has a row or column index out of bounds.
+    pub fn from_triplet_iter_summed<I>(nrows: usize, ncols: usize, iter: I) -> Self
+    where
+        T: Scalar + ClosedAdd,
+        I: IntoIterator<Item = (usize, usize, T)>,
+    {
+        let mut rows: Vec<Vec<(usize, T)>> = vec![Vec::new(); nrows];
+        for (i, j, v) in iter {
+            assert!(i < nrows, "Row index out of bounds.");
+            assert!(j < ncols, "Col index out of bounds.");
+            rows[i].push((j, v));
+        }
+
+        let mut row_offsets = Vec::with_capacity(nrows + 1);
+        let mut col_indices = Vec::new();
+        let mut values = Vec::new();
+        row_offsets.push(0);
+
+        for mut row in rows {
+            // A stable sort preserves the insertion order of duplicate entries, so that
+            // duplicates are summed in the order in which they were produced by the iterator
+            row.sort_by_key(|(j, _)| *j);
+            let row_begin = *row_offsets.last().unwrap();
+            for (j, v) in row {
+                if col_indices.len() > row_begin && *col_indices.last().unwrap() == j {
+                    let last_value = values.last_mut().unwrap();
+                    *last_value += v;
+                } else {
+                    col_indices.push(j);
+                    values.push(v);
+                }
+            }
+            row_offsets.push(col_indices.len());
+        }
+
+        Self::try_from_csr_data(nrows, ncols, row_offsets, col_indices, values)
+            .expect("Internal error: Invalid CSR data during iterator assembly")
+    }
+
     /// Try to construct a CSR matrix from a sparsity pattern and associated non-zero values.
     ///
     /// Returns an error if the number of values does not match the number of minor indices
@@ -393,6 +740,9 @@ impl<T> CsrMatrix<T> {
     }
 
     /// A mutable iterator over rows in the matrix.
+    ///
+    /// See [`CscMatrix::col_iter_mut`](crate::csc::CscMatrix::col_iter_mut) for the
+    /// corresponding column-oriented iterator in the CSC format.
     pub fn row_iter_mut(&mut self) -> CsrRowIterMut<'_, T> {
         let (pattern, values) = self.cs.pattern_and_values_mut();
         CsrRowIterMut {
@@ -429,6 +779,119 @@ impl<T> CsrMatrix<T> {
         self.cs.disassemble()
     }
 
+    /// Decomposes the matrix into a vector of owned sparse rows.
+    ///
+    /// Each row becomes an independent [`SparseVector`] of dimension `ncols`, which can be
+    /// reordered, modified or filtered as a first-class object and reassembled with
+    /// [`CsrMatrix::from_rows`]. No values are cloned; the storage of the matrix is moved
+    /// into the rows.
+    pub fn into_rows(self) -> Vec<SparseVector<T>> {
+        let nrows = self.nrows();
+        let ncols = self.ncols();
+        let (offsets, mut indices, mut values) = self.disassemble();
+
+        // Split the index and value arrays from the back so that each split is O(row nnz)
+        let mut rows = Vec::with_capacity(nrows);
+        for i in (0..nrows).rev() {
+            let row_indices = indices.split_off(offsets[i]);
+            let row_values = values.split_off(offsets[i]);
+            rows.push(SparseVector::from_parts_unchecked(
+                ncols,
+                row_indices,
+                row_values,
+            ));
+        }
+        rows.reverse();
+        rows
+    }
+
+    /// Assembles a CSR matrix from a vector of owned sparse rows.
+    ///
+    /// This is the inverse of [`CsrMatrix::into_rows`]: the result has one row per element
+    /// of `rows`, in order, and `ncols` columns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with kind
+    /// [`InvalidStructure`](SparseFormatErrorKind::InvalidStructure) if any row does not
+    /// have dimension `ncols`.
+    pub fn from_rows(rows: Vec<SparseVector<T>>, ncols: usize) -> Result<Self, SparseFormatError> {
+        let nrows = rows.len();
+        let mut offsets = Vec::with_capacity(nrows + 1);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        offsets.push(0);
+        for (i, row) in rows.into_iter().enumerate() {
+            if row.len() != ncols {
+                return Err(SparseFormatError::from_kind_and_error(
+                    SparseFormatErrorKind::InvalidStructure,
+                    format!(
+                        "Row {} has dimension {}, but {} columns were expected.",
+                        i,
+                        row.len(),
+                        ncols
+                    )
+                    .into(),
+                ));
+            }
+            let (row_indices, row_values) = row.disassemble();
+            indices.extend(row_indices);
+            values.extend(row_values);
+            offsets.push(indices.len());
+        }
+
+        Ok(Self::try_from_csr_data(nrows, ncols, offsets, indices, values)
+            .expect("Internal error: Sparse rows must produce valid CSR data"))
+    }
+
+    /// Assembles a CSR matrix from per-row column-index and value vectors.
+    ///
+    /// The result has one row per element of `rows`, in order, and `ncols` columns; the
+    /// per-row arrays are concatenated directly into the CSR index and value arrays. This is
+    /// the natural assembly path when rows are computed independently, e.g. one per worker in
+    /// a parallel assembly, and avoids both a COO intermediate and the [`SparseVector`]
+    /// wrapper required by [`CsrMatrix::from_rows`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with kind
+    /// [`InvalidStructure`](SparseFormatErrorKind::InvalidStructure) if any row has a
+    /// different number of column indices and values, or if the column indices of a row are
+    /// not sorted and unique, and an error with kind
+    /// [`IndexOutOfBounds`](SparseFormatErrorKind::IndexOutOfBounds) if a column index is out
+    /// of bounds.
+    pub fn from_rows_data(
+        ncols: usize,
+        rows: Vec<(Vec<usize>, Vec<T>)>,
+    ) -> Result<Self, SparseFormatError> {
+        let nrows = rows.len();
+        let mut offsets = Vec::with_capacity(nrows + 1);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        offsets.push(0);
+        for (i, (row_indices, row_values)) in rows.into_iter().enumerate() {
+            if row_indices.len() != row_values.len() {
+                return Err(SparseFormatError::from_kind_and_error(
+                    SparseFormatErrorKind::InvalidStructure,
+                    format!(
+                        "Row {} has {} column indices but {} values.",
+                        i,
+                        row_indices.len(),
+                        row_values.len()
+                    )
+                    .into(),
+                ));
+            }
+            indices.extend(row_indices);
+            values.extend(row_values);
+            offsets.push(indices.len());
+        }
+
+        // The remaining invariants (sorted, unique and in-bounds column indices per row) are
+        // validated by the raw data constructor
+        Self::try_from_csr_data(nrows, ncols, offsets, indices, values)
+    }
+
     /// Returns the sparsity pattern and values associated with this matrix.
     pub fn into_pattern_and_values(self) -> (SparsityPattern, Vec<T>) {
         self.cs.into_pattern_and_values()
@@ -490,6 +953,26 @@ impl<T> CsrMatrix<T> {
             .expect("Out of bounds matrix indices encountered")
     }
 
+    /// Returns the value at the given row/col indices, or zero if the entry is not explicitly
+    /// stored.
+    ///
+    /// This is a convenience shorthand for `self.index_entry(row_index, col_index).into_value()`,
+    /// intended for quick prototyping. Note that `Index<(usize, usize)>` is deliberately not
+    /// implemented: `Index::index` must return a reference, and there is no `&T` to return for
+    /// an entry that is only implicitly zero — a generic `static` zero cannot exist for
+    /// arbitrary `T`. Returning by value sidesteps the problem at the cost of a clone.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `row_index` or `col_index` is out of bounds.
+    #[must_use]
+    pub fn get_value(&self, row_index: usize, col_index: usize) -> T
+    where
+        T: Clone + Zero,
+    {
+        self.index_entry(row_index, col_index).into_value()
+    }
+
     /// Returns a mutable entry for the given row/col indices.
     ///
     /// Same as `get_entry_mut`, except that it directly panics upon encountering row/col indices
@@ -530,6 +1013,126 @@ impl<T> CsrMatrix<T> {
         }
     }
 
+    /// Returns a new matrix in which all explicitly stored zeros have been removed.
+    ///
+    /// Operations that combine entries, such as subtraction of matrices with overlapping
+    /// sparsity patterns, can produce entries that are exactly zero through cancellation but
+    /// remain explicitly stored. This method rebuilds the matrix with a tighter pattern that
+    /// omits such entries.
+    #[must_use]
+    pub fn compress(&self) -> Self
+    where
+        T: Clone + Zero,
+    {
+        self.filter(|_, _, v| !v.is_zero())
+    }
+
+    /// Removes all explicitly stored zeros from the matrix in place.
+    ///
+    /// This is the in-place counterpart to [`compress`](Self::compress). Note that the
+    /// sparsity pattern of the matrix is replaced by the rebuilt one, so a pattern shared
+    /// with other matrices through e.g.
+    /// [`try_from_shared_pattern_and_values`](Self::try_from_shared_pattern_and_values)
+    /// is no longer shared afterwards.
+    pub fn compress_in_place(&mut self)
+    where
+        T: Clone + Zero,
+    {
+        *self = self.compress();
+    }
+
+    /// Swaps rows `i` and `j` of the matrix in place.
+    ///
+    /// Since CSR rows generally store different numbers of entries, the column-index and
+    /// value arrays are rebuilt: the cost is proportional to the number of entries of the two
+    /// rows plus the shifting of all entries stored between them. Note that the sparsity
+    /// pattern of the matrix is replaced by the rebuilt one, so a pattern shared with other
+    /// matrices through e.g.
+    /// [`try_from_shared_pattern_and_values`](Self::try_from_shared_pattern_and_values)
+    /// is no longer shared afterwards.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `i` or `j` is out of bounds.
+    pub fn swap_rows(&mut self, i: usize, j: usize)
+    where
+        T: Scalar,
+    {
+        assert!(i < self.nrows(), "Row index out of bounds.");
+        assert!(j < self.nrows(), "Row index out of bounds.");
+        if i == j {
+            return;
+        }
+
+        let mut offsets = Vec::with_capacity(self.nrows() + 1);
+        let mut indices = Vec::with_capacity(self.nnz());
+        let mut values = Vec::with_capacity(self.nnz());
+        offsets.push(0);
+        for k in 0..self.nrows() {
+            let source = if k == i {
+                j
+            } else if k == j {
+                i
+            } else {
+                k
+            };
+            let row = self.row(source);
+            indices.extend_from_slice(row.col_indices());
+            values.extend_from_slice(row.values());
+            offsets.push(indices.len());
+        }
+
+        *self = Self::try_from_csr_data(self.nrows(), self.ncols(), offsets, indices, values)
+            .expect("Internal error: Invalid CSR data after row swap");
+    }
+
+    /// Swaps columns `i` and `j` of the matrix in place.
+    ///
+    /// This is more expensive than [`swap_rows`](Self::swap_rows) for the CSR format, since
+    /// every row must be scanned for entries in the two columns, and rows storing exactly one
+    /// of them must be re-sorted. As for row swaps, the sparsity pattern is replaced by the
+    /// rebuilt one and is therefore no longer shared with other matrices.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `i` or `j` is out of bounds.
+    pub fn swap_columns(&mut self, i: usize, j: usize)
+    where
+        T: Scalar,
+    {
+        assert!(i < self.ncols(), "Column index out of bounds.");
+        assert!(j < self.ncols(), "Column index out of bounds.");
+        if i == j {
+            return;
+        }
+
+        let mut offsets = Vec::with_capacity(self.nrows() + 1);
+        let mut indices = Vec::with_capacity(self.nnz());
+        let mut values = Vec::with_capacity(self.nnz());
+        offsets.push(0);
+        let mut row_workspace = Vec::new();
+        for row in self.row_iter() {
+            row_workspace.clear();
+            row_workspace.extend(row.col_indices().iter().zip(row.values()).map(|(&k, v)| {
+                let swapped = if k == i {
+                    j
+                } else if k == j {
+                    i
+                } else {
+                    k
+                };
+                (swapped, v.clone())
+            }));
+            row_workspace.sort_by_key(|&(k, _)| k);
+            indices.extend(row_workspace.iter().map(|&(k, _)| k));
+            values.extend(row_workspace.iter().map(|(_, v)| v.clone()));
+            offsets.push(indices.len());
+        }
+
+        *self = Self::try_from_csr_data(self.nrows(), self.ncols(), offsets, indices, values)
+            .expect("Internal error: Invalid CSR data after column swap");
+    }
+
     /// Returns a new matrix representing the upper triangular part of this matrix.
     ///
     /// The result includes the diagonal of the matrix.
@@ -571,81 +1174,2101 @@ impl<T> CsrMatrix<T> {
     {
         CscMatrix::from(self).transpose_as_csr()
     }
-}
 
-/// Convert pattern format errors into more meaningful CSR-specific errors.
-///
-/// This ensures that the terminology is consistent: we are talking about rows and columns,
-/// not lanes, major and minor dimensions.
-fn pattern_format_error_to_csr_error(err: SparsityPatternFormatError) -> SparseFormatError {
-    use SparseFormatError as E;
-    use SparseFormatErrorKind as K;
-    use SparsityPatternFormatError::DuplicateEntry as PatternDuplicateEntry;
-    use SparsityPatternFormatError::*;
+    /// Computes the fused multiply-add `y += alpha * self * x` in place.
+    ///
+    /// This is the classic BLAS `gaxpy` operation, the workhorse of explicit time steppers
+    /// and iterative solvers. Unlike [`spmm_csr_dense`](crate::ops::serial::spmm_csr_dense),
+    /// it is restricted to a single dense vector and performs no intermediate allocations.
+    ///
+    /// Panics
+    /// ------
+    ///
+    /// Panics if the lengths of `x` and `y` do not match the dimensions of the matrix.
+    pub fn spmv_axpy(&self, alpha: T, x: &DVector<T>, y: &mut DVector<T>)
+    where
+        T: Scalar + ClosedAdd + ClosedMul,
+    {
+        assert_eq!(y.nrows(), self.nrows(), "y.nrows() != self.nrows()");
+        assert_eq!(x.nrows(), self.ncols(), "x.nrows() != self.ncols()");
 
-    match err {
-        InvalidOffsetArrayLength => E::from_kind_and_msg(
-            K::InvalidStructure,
-            "Length of row offset array is not equal to nrows + 1.",
-        ),
-        InvalidOffsetFirstLast => E::from_kind_and_msg(
-            K::InvalidStructure,
-            "First or last row offset is inconsistent with format specification.",
-        ),
-        NonmonotonicOffsets => E::from_kind_and_msg(
-            K::InvalidStructure,
-            "Row offsets are not monotonically increasing.",
-        ),
-        NonmonotonicMinorIndices => E::from_kind_and_msg(
-            K::InvalidStructure,
-            "Column indices are not monotonically increasing (sorted) within each row.",
-        ),
-        MinorIndexOutOfBounds => {
-            E::from_kind_and_msg(K::IndexOutOfBounds, "Column indices are out of bounds.")
-        }
-        PatternDuplicateEntry => {
-            E::from_kind_and_msg(K::DuplicateEntry, "Matrix data contains duplicate entries.")
+        for (i, row) in self.row_iter().enumerate() {
+            for (&j, v) in row.col_indices().iter().zip(row.values()) {
+                y[i] += alpha.clone() * v.clone() * x[j].clone();
+            }
         }
     }
-}
-
-/// Iterator type for iterating over triplets in a CSR matrix.
-#[derive(Debug)]
-pub struct CsrTripletIter<'a, T> {
-    pattern_iter: SparsityPatternIter<'a>,
-    values_iter: Iter<'a, T>,
-}
 
-impl<'a, T: Clone> CsrTripletIter<'a, T> {
-    /// Adapts the triplet iterator to return owned values.
+    /// Computes the fused multiply-add `y += alpha * self.transpose() * x` in place.
     ///
-    /// The triplet iterator returns references to the values. This method adapts the iterator
-    /// so that the values are cloned.
-    #[inline]
-    pub fn cloned_values(self) -> impl 'a + Iterator<Item = (usize, usize, T)> {
-        self.map(|(i, j, v)| (i, j, v.clone()))
-    }
-}
-
-impl<'a, T> Iterator for CsrTripletIter<'a, T> {
-    type Item = (usize, usize, &'a T);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let next_entry = self.pattern_iter.next();
-        let next_value = self.values_iter.next();
+    /// The transpose is never materialized; the matrix is instead traversed row by row,
+    /// scattering each row's contribution into `y`.
+    ///
+    /// Panics
+    /// ------
+    ///
+    /// Panics if the lengths of `x` and `y` do not match the dimensions of the transposed
+    /// matrix.
+    pub fn spmv_transpose_axpy(&self, alpha: T, x: &DVector<T>, y: &mut DVector<T>)
+    where
+        T: Scalar + ClosedAdd + ClosedMul,
+    {
+        assert_eq!(y.nrows(), self.ncols(), "y.nrows() != self.ncols()");
+        assert_eq!(x.nrows(), self.nrows(), "x.nrows() != self.nrows()");
 
-        match (next_entry, next_value) {
-            (Some((i, j)), Some(v)) => Some((i, j, v)),
-            _ => None,
+        for (i, row) in self.row_iter().enumerate() {
+            for (&j, v) in row.col_indices().iter().zip(row.values()) {
+                y[j] += alpha.clone() * v.clone() * x[i].clone();
+            }
         }
     }
-}
 
-/// Iterator type for mutably iterating over triplets in a CSR matrix.
-#[derive(Debug)]
-pub struct CsrTripletIterMut<'a, T> {
-    pattern_iter: SparsityPatternIter<'a>,
-    values_mut_iter: IterMut<'a, T>,
+    /// Computes the sparse matrix-vector product `self * x` with a sparse vector `x`.
+    ///
+    /// The product stays sparse end-to-end: no dense intermediate proportional to the matrix
+    /// dimensions is allocated. The result stores an entry for every row whose stored columns
+    /// overlap the support of `x`, which may include explicit zeros when contributions
+    /// cancel. This is the core step of propagating a sparse state through a transition
+    /// matrix, where repeatedly densifying `x` would dominate the cost.
+    ///
+    /// Each row is intersected with the support of `x` by a linear merge, so the cost is
+    /// proportional to `self.nnz() + self.nrows() * x.nnz()` in the worst case.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the dimension of `x` does not equal the number of columns of the matrix.
+    #[must_use]
+    pub fn mul_sparse_vector(&self, x: &SparseVector<T>) -> SparseVector<T>
+    where
+        T: Scalar + ClosedAdd + ClosedMul + Zero,
+    {
+        assert_eq!(x.len(), self.ncols(), "x.len() != self.ncols()");
+
+        let x_indices = x.indices();
+        let x_values = x.values();
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        for (i, row) in self.row_iter().enumerate() {
+            let mut sum = T::zero();
+            let mut overlap = false;
+            let mut k = 0;
+            for (&j, v) in row.col_indices().iter().zip(row.values()) {
+                while k < x_indices.len() && x_indices[k] < j {
+                    k += 1;
+                }
+                if k < x_indices.len() && x_indices[k] == j {
+                    sum += v.clone() * x_values[k].clone();
+                    overlap = true;
+                }
+            }
+            if overlap {
+                indices.push(i);
+                values.push(sum);
+            }
+        }
+
+        SparseVector::from_parts_unchecked(self.nrows(), indices, values)
+    }
+
+    /// Returns a matrix with every explicitly stored value conjugated.
+    ///
+    /// The sparsity pattern is identical to that of `self`; only the values change. For real
+    /// scalars conjugation is the identity, so the result is a plain copy. Combined with
+    /// [`CsrMatrix::transpose`], this yields the conjugate transpose needed for Hermitian
+    /// operations on complex matrices.
+    #[must_use]
+    pub fn conjugate(&self) -> CsrMatrix<T>
+    where
+        T: ComplexField,
+    {
+        let values = self
+            .values()
+            .iter()
+            .map(|v| v.clone().conjugate())
+            .collect();
+        Self::try_from_pattern_and_values(self.pattern().clone(), values)
+            .expect("Internal error: Conjugation does not change the number of values")
+    }
+
+    /// Conjugates every explicitly stored value of the matrix in place.
+    ///
+    /// See [`CsrMatrix::conjugate`] for the allocating variant.
+    pub fn conjugate_in_place(&mut self)
+    where
+        T: ComplexField,
+    {
+        for v in self.values_mut() {
+            *v = v.clone().conjugate();
+        }
+    }
+
+    /// Produces a coarse boolean image of the sparsity structure of the matrix.
+    ///
+    /// Each stored entry is bucketed into a cell of an `out_rows x out_cols` grid, and the
+    /// cell is marked as occupied. This is a structural "spy plot" suitable for rendering
+    /// the sparsity of a huge matrix as a small image. If the matrix dimensions are smaller
+    /// than the grid, the corresponding part of the grid is simply the exact sparsity mask.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `out_rows` or `out_cols` is zero.
+    #[must_use]
+    pub fn spy(&self, out_rows: usize, out_cols: usize) -> DMatrix<bool> {
+        assert!(
+            out_rows > 0 && out_cols > 0,
+            "Spy grid dimensions must be non-zero."
+        );
+        let mut image = DMatrix::from_element(out_rows, out_cols, false);
+        for (i, j) in self.pattern().entries() {
+            let out_i = if self.nrows() <= out_rows {
+                i
+            } else {
+                i * out_rows / self.nrows()
+            };
+            let out_j = if self.ncols() <= out_cols {
+                j
+            } else {
+                j * out_cols / self.ncols()
+            };
+            image[(out_i, out_j)] = true;
+        }
+        image
+    }
+
+    /// Adds the rank-1 update `alpha * x * y^T` to the matrix, restricted to its
+    /// sparsity pattern.
+    ///
+    /// For each stored entry `(i, j)`, the value `alpha * x[i] * y[j]` is added in place.
+    /// Contributions at positions that are not present in the pattern are silently dropped:
+    /// the fixed-pattern restriction is the purpose of this method, as it permits e.g.
+    /// quasi-Newton-style updates without modifying the sparsity structure of the matrix.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the lengths of `x` and `y` are not equal to the number of rows and columns
+    /// of the matrix, respectively.
+    pub fn add_outer_product_in_pattern(&mut self, alpha: T, x: &DVector<T>, y: &DVector<T>)
+    where
+        T: Scalar + ClosedAdd + ClosedMul,
+    {
+        assert_eq!(x.nrows(), self.nrows(), "x.nrows() != A.nrows()");
+        assert_eq!(y.nrows(), self.ncols(), "y.nrows() != A.ncols()");
+
+        for (i, mut row) in self.row_iter_mut().enumerate() {
+            let alpha_xi = alpha.clone() * x[i].clone();
+            let (cols, vals) = row.cols_and_values_mut();
+            for (&j, v) in cols.iter().zip(vals) {
+                *v += alpha_xi.clone() * y[j].clone();
+            }
+        }
+    }
+
+    /// Constructs a matrix with the given coordinates by evaluating `f` at each coordinate.
+    ///
+    /// The matrix stores exactly the entries `(i, j, f(i, j))` for each coordinate `(i, j)` in
+    /// `coords`. The coordinates do not need to be sorted, and duplicate coordinates are
+    /// evaluated only once. This is convenient for constructing structured test matrices and
+    /// stencils without manually assembling offset and index arrays.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if any coordinate is out of bounds.
+    pub fn from_fn<F>(nrows: usize, ncols: usize, coords: &[(usize, usize)], f: F) -> Self
+    where
+        T: Scalar,
+        F: Fn(usize, usize) -> T,
+    {
+        let mut coords = coords.to_vec();
+        coords.sort_unstable();
+        coords.dedup();
+
+        let mut row_offsets = Vec::with_capacity(nrows + 1);
+        let mut col_indices = Vec::with_capacity(coords.len());
+        let mut values = Vec::with_capacity(coords.len());
+        row_offsets.push(0);
+        let mut current_row = 0;
+        for &(i, j) in &coords {
+            assert!(i < nrows, "Row index out of bounds.");
+            assert!(j < ncols, "Col index out of bounds.");
+            while current_row < i {
+                row_offsets.push(col_indices.len());
+                current_row += 1;
+            }
+            col_indices.push(j);
+            values.push(f(i, j));
+        }
+        while current_row < nrows {
+            row_offsets.push(col_indices.len());
+            current_row += 1;
+        }
+
+        Self::try_from_csr_data(nrows, ncols, row_offsets, col_indices, values)
+            .expect("Internal error: Sorted and deduplicated coordinates must form valid data")
+    }
+
+    /// Splits the rows of the matrix into `num_chunks` contiguous ranges with approximately
+    /// equal numbers of explicitly stored entries.
+    ///
+    /// This is a load-balancing helper for parallel kernels that process rows independently:
+    /// splitting rows by count can give severely imbalanced work when the number of entries
+    /// per row varies, whereas splitting by nnz gives each worker roughly the same amount of
+    /// work. The returned ranges are disjoint, in order, and cover `0 .. nrows`; ranges may
+    /// be empty if the matrix has fewer rows than chunks or if single rows dominate the nnz
+    /// count. Only the offset array is read, so the cost is `O(nrows)`.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `num_chunks` is zero.
+    #[must_use]
+    pub fn row_chunks_by_nnz(&self, num_chunks: usize) -> Vec<Range<usize>> {
+        assert!(num_chunks > 0, "The number of chunks must be non-zero.");
+        let offsets = self.row_offsets();
+        let total_nnz = self.nnz();
+        let mut chunks = Vec::with_capacity(num_chunks);
+        let mut start = 0;
+        for chunk_idx in 1..=num_chunks {
+            let end = if chunk_idx == num_chunks {
+                self.nrows()
+            } else {
+                // Extend the chunk while the cumulative nnz stays below the ideal
+                // cumulative count for this chunk boundary
+                let target = (total_nnz * chunk_idx) / num_chunks;
+                let mut end = start;
+                while end < self.nrows() && offsets[end + 1] <= target {
+                    end += 1;
+                }
+                end
+            };
+            chunks.push(start..end);
+            start = end;
+        }
+        chunks
+    }
+
+    /// Computes a histogram of the number of explicitly stored entries per row, bucketed into
+    /// `bins` equal-width buckets over the range `[0, max_nnz]`, where `max_nnz` is the
+    /// largest number of stored entries in any row.
+    ///
+    /// The distribution of row lengths is useful for choosing between storage formats - a
+    /// concentrated histogram suggests that an ELLPACK-style uniform-row format is
+    /// appropriate, while a long tail indicates that general CSR with load balancing (see
+    /// [`CsrMatrix::row_chunks_by_nnz`]) is preferable. Only the offset array is read, so the
+    /// cost is `O(nrows + bins)` regardless of the number of stored entries.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `bins` is zero.
+    #[must_use]
+    pub fn row_nnz_histogram(&self, bins: usize) -> Vec<usize> {
+        assert!(bins > 0, "The number of bins must be non-zero.");
+        let offsets = self.row_offsets();
+        let max_nnz = offsets
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .max()
+            .unwrap_or(0);
+        let mut histogram = vec![0; bins];
+        for w in offsets.windows(2) {
+            let nnz = w[1] - w[0];
+            // Each bucket covers a half-open interval of width (max_nnz + 1) / bins, so that
+            // rows with max_nnz entries land in the last bucket
+            let bin = (nnz * bins) / (max_nnz + 1);
+            histogram[bin] += 1;
+        }
+        histogram
+    }
+
+    /// Returns, for each row, the column index of the largest explicitly stored value, or
+    /// `None` for rows without stored entries.
+    ///
+    /// Only explicitly stored entries are considered: structurally absent entries are not
+    /// candidates and are in particular *not* treated as zero. This matters when all stored
+    /// values in a row are negative, in which case the result is still the largest stored
+    /// value rather than an implicit zero. Ties are resolved in favor of the lowest column
+    /// index, and values that do not compare (such as NaN) are skipped.
+    #[must_use]
+    pub fn row_argmax(&self) -> Vec<Option<usize>>
+    where
+        T: PartialOrd,
+    {
+        self.row_iter()
+            .map(|row| {
+                row_max_entry(row.col_indices(), row.values()).map(|(j, _)| j)
+            })
+            .collect()
+    }
+
+    /// Returns, for each row, the largest explicitly stored value, or `None` for rows without
+    /// stored entries.
+    ///
+    /// See [`CsrMatrix::row_argmax`] for the precise semantics regarding absent entries and
+    /// incomparable values.
+    #[must_use]
+    pub fn row_max_values(&self) -> Vec<Option<T>>
+    where
+        T: Clone + PartialOrd,
+    {
+        self.row_iter()
+            .map(|row| {
+                row_max_entry(row.col_indices(), row.values()).map(|(_, v)| v.clone())
+            })
+            .collect()
+    }
+
+    /// Computes the lower and upper bandwidth of the matrix.
+    ///
+    /// The lower bandwidth is the maximum of `i - j` over stored entries with `j < i`, and the
+    /// upper bandwidth the maximum of `j - i` over stored entries with `j > i`. A matrix whose
+    /// entries are confined to the diagonal - or which stores no entries at all - has
+    /// bandwidth `(0, 0)`. Since the column indices of each row are sorted, only the first and
+    /// last stored entry of each row needs to be inspected, so the cost is `O(nrows)`.
+    ///
+    /// This is useful for choosing between banded and general solvers, and for measuring the
+    /// effect of bandwidth-reducing orderings.
+    #[must_use]
+    pub fn bandwidth(&self) -> (usize, usize) {
+        let mut lower = 0;
+        let mut upper = 0;
+        for i in 0..self.nrows() {
+            let cols = self.pattern().lane(i);
+            if let (Some(&first), Some(&last)) = (cols.first(), cols.last()) {
+                if first < i {
+                    lower = usize::max(lower, i - first);
+                }
+                if last > i {
+                    upper = usize::max(upper, last - i);
+                }
+            }
+        }
+        (lower, upper)
+    }
+
+    /// Computes the total bandwidth `lower + upper + 1` of the matrix.
+    ///
+    /// See [`CsrMatrix::bandwidth`] for the definition of the lower and upper bandwidth.
+    #[must_use]
+    pub fn total_bandwidth(&self) -> usize {
+        let (lower, upper) = self.bandwidth();
+        lower + upper + 1
+    }
+
+    /// Computes the entrywise reciprocal `1 / a_ij` of the explicitly stored entries.
+    ///
+    /// The result has the same sparsity pattern as this matrix; implicit zeros remain
+    /// (implicit) zeros. For a diagonal matrix this directly yields the inverse, which makes
+    /// it convenient for building `D^{-1}`-style operators. Note that an explicitly stored
+    /// zero produces an infinity or NaN for floating point types, and panics with a division
+    /// by zero for integer types; use [`CsrMatrix::filter`] beforehand to drop explicit
+    /// zeros if this is a concern.
+    #[must_use]
+    pub fn recip(&self) -> CsrMatrix<T>
+    where
+        T: Scalar + ClosedDiv + One,
+    {
+        let values = self
+            .values()
+            .iter()
+            .map(|v| T::one() / v.clone())
+            .collect();
+        Self::try_from_pattern_and_values(self.pattern().clone(), values)
+            .expect("Internal error: Pattern and values must be compatible by construction")
+    }
+
+    /// Folds the given function over all explicitly stored entries, threading an accumulator.
+    ///
+    /// The function is invoked as `f(acc, i, j, v)` for each stored entry `(i, j, v)` in
+    /// row-major order, i.e. the same order as [`CsrMatrix::triplet_iter`]. Structurally
+    /// absent entries are not visited, so reductions that need to account for implicit zeros
+    /// - such as a minimum over all entries of a non-full matrix - must handle them
+    /// separately. This supports arbitrary custom reductions, e.g. the maximum absolute
+    /// value, the sum of squares or the number of entries above a threshold, without manual
+    /// loops over the raw arrays.
+    pub fn fold_entries<B, F>(&self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, usize, usize, &T) -> B,
+    {
+        let mut acc = init;
+        for (i, j, v) in self.triplet_iter() {
+            acc = f(acc, i, j, v);
+        }
+        acc
+    }
+
+    /// Splits the columns of the matrix into two matrices according to a boolean mask.
+    ///
+    /// The first returned matrix contains the columns for which the mask is `true`, the
+    /// second the remaining columns. In both matrices the columns are reindexed compactly,
+    /// preserving their relative order, and the row dimension is preserved. This is
+    /// convenient e.g. for feature selection, where a subset of columns is split off for
+    /// separate treatment.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `mask.len() != self.ncols()`.
+    #[must_use]
+    pub fn split_columns(&self, mask: &[bool]) -> (CsrMatrix<T>, CsrMatrix<T>)
+    where
+        T: Clone,
+    {
+        assert_eq!(
+            mask.len(),
+            self.ncols(),
+            "Length of mask must match the number of columns of the matrix."
+        );
+
+        // Compact new column indices; each column is only valid in the matrix it is
+        // assigned to by the mask
+        let mut new_indices = vec![0; self.ncols()];
+        let mut selected_cols = 0;
+        let mut unselected_cols = 0;
+        for (j, &selected) in mask.iter().enumerate() {
+            if selected {
+                new_indices[j] = selected_cols;
+                selected_cols += 1;
+            } else {
+                new_indices[j] = unselected_cols;
+                unselected_cols += 1;
+            }
+        }
+
+        let mut selected_offsets = Vec::with_capacity(self.nrows() + 1);
+        let mut selected_indices = Vec::new();
+        let mut selected_values = Vec::new();
+        let mut unselected_offsets = Vec::with_capacity(self.nrows() + 1);
+        let mut unselected_indices = Vec::new();
+        let mut unselected_values = Vec::new();
+
+        selected_offsets.push(0);
+        unselected_offsets.push(0);
+        for row in self.row_iter() {
+            for (&j, v) in row.col_indices().iter().zip(row.values()) {
+                if mask[j] {
+                    selected_indices.push(new_indices[j]);
+                    selected_values.push(v.clone());
+                } else {
+                    unselected_indices.push(new_indices[j]);
+                    unselected_values.push(v.clone());
+                }
+            }
+            selected_offsets.push(selected_indices.len());
+            unselected_offsets.push(unselected_indices.len());
+        }
+
+        let selected = Self::try_from_csr_data(
+            self.nrows(),
+            selected_cols,
+            selected_offsets,
+            selected_indices,
+            selected_values,
+        )
+        .expect("Internal error: Split CSR data must be valid by construction");
+        let unselected = Self::try_from_csr_data(
+            self.nrows(),
+            unselected_cols,
+            unselected_offsets,
+            unselected_indices,
+            unselected_values,
+        )
+        .expect("Internal error: Split CSR data must be valid by construction");
+        (selected, unselected)
+    }
+
+    /// Returns the inverse of the matrix if it is diagonal, and `None` otherwise.
+    ///
+    /// The matrix is considered diagonal if it is square and every row stores exactly one
+    /// entry, located on the diagonal. The inverse of such a matrix is again diagonal, with
+    /// each entry reciprocated, so it can be computed exactly and cheaply - in contrast to
+    /// the inverse of a general sparse matrix, which is typically dense. This is e.g. the
+    /// relevant operation when constructing a Jacobi preconditioner.
+    ///
+    /// Returns `None` if the matrix is not square, has off-diagonal entries, is missing a
+    /// diagonal entry, or stores an explicit zero on the diagonal (in which case the matrix
+    /// is singular).
+    #[must_use]
+    pub fn try_invert_diagonal(&self) -> Option<CsrMatrix<T>>
+    where
+        T: Scalar + Field,
+    {
+        if self.nrows() != self.ncols() || self.nnz() != self.nrows() {
+            return None;
+        }
+
+        let mut values = Vec::with_capacity(self.nrows());
+        for (i, row) in self.row_iter().enumerate() {
+            match (row.col_indices(), row.values()) {
+                ([j], [v]) if *j == i && *v != T::zero() => {
+                    values.push(T::one() / v.clone());
+                }
+                _ => return None,
+            }
+        }
+
+        Some(
+            Self::try_from_pattern_and_values(self.pattern().clone(), values)
+                .expect("Internal error: Pattern and values must be compatible by construction"),
+        )
+    }
+
+    /// Returns the `(row, col, magnitude)` of the stored entry with the largest magnitude.
+    ///
+    /// This is a single pass over the pattern and values, useful e.g. for scaling or for
+    /// locating ill-conditioning hotspots. Only explicitly stored entries are considered;
+    /// implicit zeros do not participate. Ties are resolved in favor of the first entry in
+    /// row-major order.
+    ///
+    /// Returns `None` if the matrix has no stored entries.
+    #[must_use]
+    pub fn max_abs_entry(&self) -> Option<(usize, usize, T::RealField)>
+    where
+        T: ComplexField,
+    {
+        self.fold_entries(None, |acc, i, j, v| {
+            let magnitude = v.clone().modulus();
+            match acc {
+                Some((_, _, ref max)) if magnitude <= *max => acc,
+                _ => Some((i, j, magnitude)),
+            }
+        })
+    }
+
+    /// Returns the `(row, col, magnitude)` of the stored entry with the smallest magnitude.
+    ///
+    /// The counterpart to [`max_abs_entry`](Self::max_abs_entry); see there for the details.
+    /// Note that only explicitly stored entries are considered, so the result is not the
+    /// minimum over all matrix elements unless the matrix is structurally full.
+    ///
+    /// Returns `None` if the matrix has no stored entries.
+    #[must_use]
+    pub fn min_abs_entry(&self) -> Option<(usize, usize, T::RealField)>
+    where
+        T: ComplexField,
+    {
+        self.fold_entries(None, |acc, i, j, v| {
+            let magnitude = v.clone().modulus();
+            match acc {
+                Some((_, _, ref min)) if magnitude >= *min => acc,
+                _ => Some((i, j, magnitude)),
+            }
+        })
+    }
+
+    /// The fraction of explicitly stored entries, i.e. `nnz / (nrows * ncols)`.
+    ///
+    /// Returns `0.0` for matrices without any elements. Note that explicitly stored zeros
+    /// count towards the density, since this is a structural rather than numerical measure.
+    #[must_use]
+    pub fn density(&self) -> f64 {
+        if self.nrows() == 0 || self.ncols() == 0 {
+            0.0
+        } else {
+            self.nnz() as f64 / (self.nrows() as f64 * self.ncols() as f64)
+        }
+    }
+
+    /// The number of bytes of memory used by the offset, index and value arrays.
+    ///
+    /// This accounts for the *capacities* of the backing arrays rather than their lengths, so
+    /// that over-allocation is visible; use [`CsrMatrix::shrink_to_fit`] to trim excess
+    /// capacity. Memory used by the struct itself or by any padding is not included.
+    #[must_use]
+    pub fn memory_bytes(&self) -> usize {
+        self.cs.memory_bytes()
+    }
+
+    /// Shrinks the capacity of the backing arrays to their lengths.
+    pub fn shrink_to_fit(&mut self) {
+        self.cs.shrink_to_fit();
+    }
+
+    /// Inserts an entry at the given position, growing the sparsity pattern if the position is
+    /// structurally absent or overwriting the stored value if it is present.
+    ///
+    /// Inserting a new structural entry shifts the column-index and value arrays and rebuilds
+    /// the offsets, which costs `O(nnz)` in the worst case; overwriting an existing entry only
+    /// costs a lookup. This is intended for occasional tweaks of an already constructed
+    /// matrix. For bulk construction, assemble a [`CooMatrix`] and convert it instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with kind
+    /// [`IndexOutOfBounds`](SparseFormatErrorKind::IndexOutOfBounds) if the row or column
+    /// index is out of bounds.
+    pub fn insert_entry(&mut self, row: usize, col: usize, value: T) -> Result<(), SparseFormatError>
+    where
+        T: Scalar,
+    {
+        if row >= self.nrows() {
+            return Err(SparseFormatError::from_kind_and_msg(
+                SparseFormatErrorKind::IndexOutOfBounds,
+                "Row index out of bounds.",
+            ));
+        }
+        if col >= self.ncols() {
+            return Err(SparseFormatError::from_kind_and_msg(
+                SparseFormatErrorKind::IndexOutOfBounds,
+                "Column index out of bounds.",
+            ));
+        }
+
+        if let SparseEntryMut::NonZero(v) = self.index_entry_mut(row, col) {
+            *v = value;
+            return Ok(());
+        }
+
+        let (nrows, ncols) = (self.nrows(), self.ncols());
+        let (mut offsets, mut indices, mut values) =
+            std::mem::replace(self, Self::zeros(0, 0)).disassemble();
+        let insert_pos = offsets[row]
+            + indices[offsets[row]..offsets[row + 1]]
+                .binary_search(&col)
+                .expect_err("Internal error: Entry verified to be structurally absent above");
+        indices.insert(insert_pos, col);
+        values.insert(insert_pos, value);
+        for offset in &mut offsets[row + 1..] {
+            *offset += 1;
+        }
+        *self = Self::try_from_csr_data(nrows, ncols, offsets, indices, values)
+            .expect("Internal error: Insertion must preserve the CSR invariants");
+        Ok(())
+    }
+
+    /// Extracts the `offset`-th diagonal of the matrix as a dense vector, i.e. the entries at
+    /// positions `(i, j)` with `j - i == offset`.
+    ///
+    /// A positive offset selects a superdiagonal, a negative offset a subdiagonal, and zero
+    /// the main diagonal. Structurally absent positions are filled with zeros. The length of
+    /// the result is `min(nrows, ncols - offset)` for non-negative offsets and
+    /// `min(nrows + offset, ncols)` for negative ones. This supports e.g. banded-solver
+    /// construction and stencil inspection.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the offset does not lie in the open interval `(-nrows, ncols)`.
+    #[must_use]
+    pub fn diagonal(&self, offset: isize) -> DVector<T>
+    where
+        T: Scalar + Zero,
+    {
+        assert!(
+            -(self.nrows() as isize) < offset && offset < self.ncols() as isize,
+            "Diagonal offset out of bounds."
+        );
+        let len = if offset >= 0 {
+            std::cmp::min(self.nrows(), self.ncols() - offset as usize)
+        } else {
+            std::cmp::min(self.nrows() - (-offset) as usize, self.ncols())
+        };
+
+        DVector::from_iterator(
+            len,
+            (0..len).map(|t| {
+                let (i, j) = if offset >= 0 {
+                    (t, t + offset as usize)
+                } else {
+                    (t + (-offset) as usize, t)
+                };
+                let row = self.row(i);
+                match row.col_indices().binary_search(&j) {
+                    Ok(local_idx) => row.values()[local_idx].clone(),
+                    Err(_) => T::zero(),
+                }
+            }),
+        )
+    }
+
+    /// Casts the values of the matrix to another scalar type, leaving the sparsity pattern
+    /// unchanged.
+    ///
+    /// Only the value buffer is converted; the pattern is reused as-is. This is intended for
+    /// mixed-precision schemes, e.g. factorizing in single precision and refining in double
+    /// precision, without rebuilding the structure. See also the [`CsrMatrix::to_f32`] and
+    /// [`CsrMatrix::to_f64`] conveniences.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if a value is not representable in the target scalar type, e.g. when casting a
+    /// large integer to a narrower integer type. Casts between float types always succeed,
+    /// with the usual rounding.
+    #[must_use]
+    pub fn cast<U>(&self) -> CsrMatrix<U>
+    where
+        T: Scalar + ToPrimitive,
+        U: Scalar + NumCast,
+    {
+        let values = self
+            .values()
+            .iter()
+            .map(|v| U::from(v.clone()).expect("Cannot represent value in the target scalar type"))
+            .collect();
+        CsrMatrix::try_from_pattern_and_values(self.pattern().clone(), values)
+            .expect("Internal error: Pattern and values must be compatible by construction")
+    }
+
+    /// Casts the values of the matrix to `f32`. See [`CsrMatrix::cast`].
+    #[must_use]
+    pub fn to_f32(&self) -> CsrMatrix<f32>
+    where
+        T: Scalar + ToPrimitive,
+    {
+        self.cast()
+    }
+
+    /// Casts the values of the matrix to `f64`. See [`CsrMatrix::cast`].
+    #[must_use]
+    pub fn to_f64(&self) -> CsrMatrix<f64>
+    where
+        T: Scalar + ToPrimitive,
+    {
+        self.cast()
+    }
+
+    /// Returns the indices of all rows without any explicitly stored entries.
+    ///
+    /// Note that this detects *structural* emptiness: a row that stores explicit zeros is not
+    /// reported, even though it is numerically zero. See
+    /// [`CsrMatrix::numerically_zero_rows`] for the numerical counterpart. Only the offset
+    /// array is read, so the cost is `O(nrows)`. This is useful e.g. for pivoting and cheap
+    /// singularity detection.
+    #[must_use]
+    pub fn empty_rows(&self) -> Vec<usize> {
+        self.row_offsets()
+            .windows(2)
+            .enumerate()
+            .filter(|(_, w)| w[0] == w[1])
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns `true` if any row has no explicitly stored entries.
+    ///
+    /// See [`CsrMatrix::empty_rows`] for the precise semantics.
+    #[must_use]
+    pub fn has_empty_row(&self) -> bool {
+        self.row_offsets().windows(2).any(|w| w[0] == w[1])
+    }
+
+    /// Returns the indices of all rows whose stored entries all have modulus at most `tol`.
+    ///
+    /// In contrast to [`CsrMatrix::empty_rows`], this detects *numerical* emptiness:
+    /// structurally empty rows are reported, but so are rows that only store entries
+    /// indistinguishable from zero up to the given tolerance.
+    #[must_use]
+    pub fn numerically_zero_rows(&self, tol: T::RealField) -> Vec<usize>
+    where
+        T: ComplexField,
+    {
+        self.row_iter()
+            .enumerate()
+            .filter(|(_, row)| {
+                row.values()
+                    .iter()
+                    .all(|v| v.clone().modulus() <= tol.clone())
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Computes the entrywise absolute value of the matrix.
+    ///
+    /// The result has the same sparsity pattern as this matrix, with each stored value replaced
+    /// by its magnitude: the absolute value for real input, and the modulus for complex input.
+    /// This is useful e.g. for entrywise spectral radius bounds.
+    #[must_use]
+    pub fn abs(&self) -> CsrMatrix<T::RealField>
+    where
+        T: ComplexField,
+    {
+        let values = self.values().iter().map(|v| v.clone().modulus()).collect();
+        CsrMatrix::try_from_pattern_and_values(self.pattern().clone(), values)
+            .expect("Internal error: Pattern and values must be compatible by construction")
+    }
+
+    /// Solves the system `A * X = B` by densifying the matrix and using a dense LU
+    /// decomposition, where `A` is `self` and `B` may have multiple right-hand side columns.
+    ///
+    /// This is a convenience for small systems where a true sparse solver is not needed.
+    /// Note that the dense representation requires memory proportional to `nrows * ncols`
+    /// regardless of the number of explicitly stored entries, so this method is only
+    /// appropriate for matrices of modest dimensions.
+    ///
+    /// Returns `None` if the matrix is not square, if the dimensions of `b` are not compatible
+    /// with the system, or if the matrix is singular.
+    #[must_use]
+    pub fn dense_lu_solve(&self, b: &DMatrix<T>) -> Option<DMatrix<T>>
+    where
+        T: RealField,
+    {
+        if self.nrows() != self.ncols() || self.nrows() != b.nrows() {
+            return None;
+        }
+        DMatrix::from(self).lu().solve(b)
+    }
+
+    /// Estimates the spectral condition number of a symmetric positive definite matrix.
+    ///
+    /// The largest eigenvalue is estimated with `iterations` steps of power iteration, and the
+    /// smallest with `iterations` steps of inverse power iteration backed by a sparse Cholesky
+    /// factorization; the returned value is their ratio. This gives a quick ill-conditioning
+    /// warning without a full eigendecomposition, but note that it is only an estimate: power
+    /// iteration converges slowly when the extremal eigenvalues are not well separated, so the
+    /// quality of the result depends on `iterations`.
+    ///
+    /// Returns `None` if the matrix is not square or has dimension zero, if the Cholesky
+    /// factorization fails (e.g. because the matrix is not positive definite), or if an
+    /// iterate degenerates numerically.
+    #[must_use]
+    pub fn estimate_spectral_condition(&self, iterations: usize) -> Option<T::RealField>
+    where
+        T: RealField,
+    {
+        if self.nrows() != self.ncols() || self.nrows() == 0 {
+            return None;
+        }
+        let n = self.nrows();
+        let normalized = |v: DVector<T>| {
+            let norm = v.norm();
+            if norm > T::zero() {
+                Some(v / norm)
+            } else {
+                None
+            }
+        };
+
+        // Largest eigenvalue by power iteration
+        let mut x = normalized(DVector::from_element(n, T::one()))?;
+        for _ in 0..iterations {
+            x = normalized(self * &x)?;
+        }
+        let lambda_max = x.dot(&(self * &x));
+        if lambda_max <= T::zero() {
+            return None;
+        }
+
+        // Largest eigenvalue of the inverse by inverse power iteration, which is the
+        // reciprocal of the smallest eigenvalue of the matrix
+        let cholesky = CscCholesky::factor(&CscMatrix::from(self)).ok()?;
+        let solve = |v: &DVector<T>| {
+            let solution = cholesky.solve(v);
+            DVector::from_column_slice(solution.as_slice())
+        };
+        let mut x = normalized(DVector::from_element(n, T::one()))?;
+        for _ in 0..iterations {
+            x = normalized(solve(&x))?;
+        }
+        let lambda_max_inv = x.dot(&solve(&x));
+        if lambda_max_inv <= T::zero() {
+            return None;
+        }
+
+        Some(lambda_max * lambda_max_inv)
+    }
+
+    /// Computes the maximum modulus of the stored entries of each row.
+    ///
+    /// Entry `i` of the result is the largest modulus among the explicitly stored entries of
+    /// row `i`, or zero for rows without stored entries. This is the per-row half of matrix
+    /// equilibration; see [`CscMatrix::column_max_abs`](crate::csc::CscMatrix::column_max_abs)
+    /// for the column counterpart and [`CsrMatrix::ruiz_equilibration`] for the full scaling
+    /// loop. The cost is a single pass over the stored entries.
+    #[must_use]
+    pub fn row_max_abs(&self) -> DVector<T::RealField>
+    where
+        T: ComplexField,
+    {
+        DVector::from_iterator(
+            self.nrows(),
+            self.row_iter().map(|row| {
+                row.values()
+                    .iter()
+                    .fold(T::RealField::zero(), |max, v| max.max(v.clone().modulus()))
+            }),
+        )
+    }
+
+    /// Equilibrates the matrix with Ruiz scaling and returns the scaled matrix together with
+    /// the left and right scaling vectors.
+    ///
+    /// Each of the `iters` iterations scales every row and column by the reciprocal square
+    /// root of its current maximum modulus, driving all row and column maxima towards one.
+    /// The returned triple `(scaled, d_r, d_c)` satisfies
+    /// `scaled = diag(d_r) * self * diag(d_c)`, so a system `A x = b` can be solved in the
+    /// equilibrated variables via `scaled y = diag(d_r) b` followed by `x = diag(d_c) y`.
+    /// Rows and columns without stored entries (or containing only explicit zeros) are left
+    /// unscaled. A handful of iterations usually suffices for convergence.
+    #[must_use]
+    pub fn ruiz_equilibration(
+        &self,
+        iters: usize,
+    ) -> (CsrMatrix<T>, DVector<T::RealField>, DVector<T::RealField>)
+    where
+        T: ComplexField,
+    {
+        let mut scaled = self.clone();
+        let mut d_r = DVector::from_element(self.nrows(), T::RealField::one());
+        let mut d_c = DVector::from_element(self.ncols(), T::RealField::one());
+
+        for _ in 0..iters {
+            let inv_sqrt = |max: T::RealField| {
+                if max > T::RealField::zero() {
+                    T::RealField::one() / max.sqrt()
+                } else {
+                    T::RealField::one()
+                }
+            };
+            let r_scale = scaled.row_max_abs().map(&inv_sqrt);
+
+            // The column maxima are computed directly from the triplets to avoid converting
+            // to CSC in every iteration
+            let mut c_max = vec![T::RealField::zero(); self.ncols()];
+            for (_, j, v) in scaled.triplet_iter() {
+                c_max[j] = c_max[j].clone().max(v.clone().modulus());
+            }
+            let c_scale = DVector::from_vec(c_max).map(&inv_sqrt);
+
+            for (i, j, v) in scaled.triplet_iter_mut() {
+                *v = v.clone().scale(r_scale[i].clone() * c_scale[j].clone());
+            }
+            d_r.component_mul_assign(&r_scale);
+            d_c.component_mul_assign(&c_scale);
+        }
+
+        (scaled, d_r, d_c)
+    }
+
+    /// Computes the matrix 1-norm, i.e. the maximum absolute column sum.
+    #[must_use]
+    pub fn norm_l1(&self) -> T::RealField
+    where
+        T: ComplexField,
+    {
+        let mut column_sums = vec![T::RealField::zero(); self.ncols()];
+        for (_, j, v) in self.triplet_iter() {
+            column_sums[j] += v.clone().modulus();
+        }
+        column_sums
+            .into_iter()
+            .fold(T::RealField::zero(), |max, sum| max.max(sum))
+    }
+
+    /// Estimates the 1-norm condition number `||A||_1 * ||A^{-1}||_1` of the matrix.
+    ///
+    /// The norm of the inverse is estimated with Hager's algorithm, the standard LAPACK-style
+    /// condition estimator: starting from a uniform vector, each iteration performs one solve
+    /// with `A` and one with `A^T` through the given [`LinearSolver`], and either converges or
+    /// restarts from the unit vector that maximizes the estimate. At most five iterations are
+    /// performed, which is the customary cap; each iteration thus costs two solves plus
+    /// vector work.
+    ///
+    /// The result is a lower bound on the true condition number. In practice the estimate is
+    /// usually within a small factor of the exact value, but adversarial matrices exist for
+    /// which it is a severe underestimate; it should be treated as an order-of-magnitude
+    /// indicator rather than an exact quantity.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the matrix is not square or has dimension zero.
+    #[must_use]
+    pub fn estimate_norm1_condition(&self, solver: &impl LinearSolver<T>) -> T::RealField
+    where
+        T: RealField,
+    {
+        assert_eq!(
+            self.nrows(),
+            self.ncols(),
+            "Cannot estimate the condition number of a non-square matrix."
+        );
+        assert!(
+            self.nrows() > 0,
+            "Cannot estimate the condition number of an empty matrix."
+        );
+
+        let n = self.nrows();
+        let mut x = DVector::from_element(n, T::one() / T::from_usize(n).unwrap());
+        let mut estimate = T::zero();
+        for _ in 0..5 {
+            let y = solver.solve_vector(&x);
+            estimate = y.iter().fold(T::zero(), |sum, y_i| sum + y_i.clone().abs());
+
+            let xi = y.map(|y_i| {
+                if y_i >= T::zero() {
+                    T::one()
+                } else {
+                    -T::one()
+                }
+            });
+            let z = solver.solve_transpose_vector(&xi);
+
+            let (j_max, z_max) = z
+                .iter()
+                .enumerate()
+                .map(|(j, z_j)| (j, z_j.clone().abs()))
+                .fold((0, T::zero()), |acc, cur| {
+                    if cur.1 > acc.1 {
+                        cur
+                    } else {
+                        acc
+                    }
+                });
+            // Convergence test: no unit vector can improve on the current estimate
+            if z_max <= z.dot(&x) {
+                break;
+            }
+            x = DVector::zeros(n);
+            x[j_max] = T::one();
+        }
+
+        self.norm_l1() * estimate
+    }
+
+    /// Performs one in-place SOR (successive over-relaxation) sweep for the system
+    /// `self * x = b`.
+    ///
+    /// Each row is visited once in the given [`SweepDirection`] and its unknown updated as
+    /// `x_i <- (1 - omega) * x_i + omega * (b_i - sum_{j != i} a_ij * x_j) / a_ii`, using the
+    /// already-updated values of previously visited rows. With `omega = 1` this is a classic
+    /// forward or backward Gauss-Seidel sweep; a forward sweep followed by a backward sweep
+    /// yields the symmetric SSOR smoother. This is the standard relaxation smoother of
+    /// multigrid methods.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the matrix is not square, if the dimensions of `b` and `x` do not match the
+    /// matrix, or if the diagonal is not fully present (see
+    /// [`CsrMatrix::has_full_diagonal`]).
+    pub fn sor_sweep(
+        &self,
+        b: &DVector<T>,
+        x: &mut DVector<T>,
+        omega: T,
+        direction: SweepDirection,
+    ) where
+        T: RealField,
+    {
+        assert_eq!(
+            self.nrows(),
+            self.ncols(),
+            "Cannot perform an SOR sweep with a non-square matrix."
+        );
+        assert_eq!(b.nrows(), self.nrows(), "b.nrows() != A.nrows()");
+        assert_eq!(x.nrows(), self.ncols(), "x.nrows() != A.ncols()");
+        assert!(
+            self.has_full_diagonal(),
+            "An SOR sweep requires every diagonal entry to be explicitly stored."
+        );
+
+        let mut sweep = |i: usize| {
+            let row = self.row(i);
+            let mut sigma = T::zero();
+            let mut diagonal = T::zero();
+            for (&j, v) in row.col_indices().iter().zip(row.values()) {
+                if j == i {
+                    diagonal = v.clone();
+                } else {
+                    sigma += v.clone() * x[j].clone();
+                }
+            }
+            x[i] = (T::one() - omega.clone()) * x[i].clone()
+                + omega.clone() * (b[i].clone() - sigma) / diagonal;
+        };
+
+        match direction {
+            SweepDirection::Forward => (0..self.nrows()).for_each(&mut sweep),
+            SweepDirection::Backward => (0..self.nrows()).rev().for_each(&mut sweep),
+        }
+    }
+
+    /// Gathers the selected rows into a dense `rows.len() x ncols` matrix.
+    ///
+    /// Row `k` of the result is the dense representation of row `rows[k]` of this matrix,
+    /// with the stored entries scattered into place and all remaining positions filled with
+    /// zeros. Rows may be repeated and listed in any order. This is the densification
+    /// primitive for hybrid sparse/dense kernels that process row blocks densely.
+    ///
+    /// Note that the result requires memory proportional to `rows.len() * ncols` regardless of
+    /// the number of explicitly stored entries, so for wide matrices even a few rows can be
+    /// costly to materialize.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if any row index is out of bounds.
+    #[must_use]
+    pub fn gather_rows_dense(&self, rows: &[usize]) -> DMatrix<T>
+    where
+        T: Scalar + Zero,
+    {
+        let mut result = DMatrix::zeros(rows.len(), self.ncols());
+        for (k, &i) in rows.iter().enumerate() {
+            assert!(i < self.nrows(), "Row index out of bounds.");
+            let row = self.row(i);
+            for (&j, v) in row.col_indices().iter().zip(row.values()) {
+                result[(k, j)] = v.clone();
+            }
+        }
+        result
+    }
+
+    /// Returns the largest candidate block size to which the sparsity pattern aligns, if any.
+    ///
+    /// This is a heuristic intended to guide the decision of whether a conversion to a block
+    /// format such as BSR would pay off. The pattern *aligns* to a block size `b` if both
+    /// dimensions are divisible by `b` and, when the matrix is partitioned into a grid of
+    /// `b x b` blocks, every block that contains at least one explicitly stored entry is
+    /// fully populated, i.e. all `b * b` positions of the block are explicitly stored. Under
+    /// this condition a block format stores exactly the same values while replacing `b * b`
+    /// column indices by a single block index, so the index storage shrinks by a factor of
+    /// `b * b`.
+    ///
+    /// Candidates that do not divide both dimensions are skipped; among the remaining
+    /// candidates the largest aligning block size is returned, or `None` if none aligns.
+    /// Note that a block size of `1` trivially aligns, so including it among the candidates
+    /// makes the result always `Some`.
+    #[must_use]
+    pub fn detect_block_size(&self, candidates: &[usize]) -> Option<usize> {
+        let mut candidates: Vec<_> = candidates
+            .iter()
+            .copied()
+            .filter(|&b| b > 0 && self.nrows() % b == 0 && self.ncols() % b == 0)
+            .collect();
+        candidates.sort_unstable();
+
+        candidates.into_iter().rev().find(|&b| {
+            let mut block_counts = HashMap::new();
+            for (i, j) in self.pattern().entries() {
+                *block_counts.entry((i / b, j / b)).or_insert(0usize) += 1;
+            }
+            block_counts.values().all(|&count| count == b * b)
+        })
+    }
+
+    /// Verifies that the matrix satisfies the invariants of the CSR format.
+    ///
+    /// Specifically, this checks that the row offsets are monotonically increasing and
+    /// consistent with the dimensions of the matrix, that column indices are in bounds and
+    /// strictly increasing within each row, and that the number of values matches the number
+    /// of explicitly stored entries.
+    ///
+    /// The invariants are always upheld by matrices constructed through checked paths, but may
+    /// be violated by incorrect use of unchecked constructors. This method is primarily
+    /// intended for asserting validity in tests of code that uses such paths.
+    pub fn check_invariants(&self) -> Result<(), SparseFormatError> {
+        self.pattern()
+            .check_invariants()
+            .map_err(pattern_format_error_to_csr_error)?;
+        if self.values().len() != self.pattern().nnz() {
+            return Err(SparseFormatError::from_kind_and_msg(
+                SparseFormatErrorKind::InvalidStructure,
+                "Number of values and column indices must be the same",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Dumps the raw CSR arrays of the matrix as a compact, paste-friendly string.
+    ///
+    /// The output has the form
+    /// `offsets=[0, 2, 3], indices=[0, 2, 1], values=[1.0, 2.0, 3.0], dims=(2, 3)`, with the
+    /// values formatted via `Debug`. The arrays are exactly the arguments expected by
+    /// [`CsrMatrix::try_from_csr_data`], so for primitive scalar types the dump can be pasted
+    /// directly into a regression test to reconstruct the matrix. In contrast to a
+    /// visualization of the entries, this deliberately exposes the storage representation,
+    /// which is what matters when filing bug reports about malformed or unexpected sparsity
+    /// structure.
+    #[must_use]
+    pub fn debug_dump(&self) -> String
+    where
+        T: Scalar,
+    {
+        format!(
+            "offsets={:?}, indices={:?}, values={:?}, dims=({}, {})",
+            self.row_offsets(),
+            self.col_indices(),
+            self.values(),
+            self.nrows(),
+            self.ncols()
+        )
+    }
+
+    /// Returns a matrix whose sparsity pattern is the union of the pattern of this matrix
+    /// and the pattern of its transpose.
+    ///
+    /// Values at positions present in this matrix are preserved, while structural entries that
+    /// are only introduced by the union are filled with (explicitly stored) zeros. This is a
+    /// standard preprocessing step for algorithms that require structural symmetry, such as
+    /// symmetric reordering algorithms.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the matrix is not square.
+    #[must_use]
+    pub fn symmetrize_pattern(&self) -> Self
+    where
+        T: Scalar + Zero,
+    {
+        assert_eq!(
+            self.nrows(),
+            self.ncols(),
+            "Cannot symmetrize the pattern of a non-square matrix."
+        );
+        let pattern = spadd_pattern(self.pattern(), &self.pattern().transpose());
+        let mut values = vec![T::zero(); pattern.nnz()];
+
+        // Scatter the original values into the union pattern. Each entry of the original
+        // pattern is by construction also present in the union, so the merge below always
+        // finds a matching column index.
+        {
+            let offsets = pattern.major_offsets();
+            let indices = pattern.minor_indices();
+            for (i, row) in self.row_iter().enumerate() {
+                let range = offsets[i]..offsets[i + 1];
+                let union_cols = &indices[range.clone()];
+                let union_vals = &mut values[range];
+                let mut k = 0;
+                for (&j, v) in row.col_indices().iter().zip(row.values()) {
+                    while union_cols[k] != j {
+                        k += 1;
+                    }
+                    union_vals[k] = v.clone();
+                }
+            }
+        }
+
+        Self::try_from_pattern_and_values(pattern, values)
+            .expect("Internal error: Pattern and values must be compatible by construction")
+    }
+
+    /// Determines whether the matrix is numerically symmetric within the given tolerances.
+    ///
+    /// Each stored entry `(i, j)` is compared against the transposed entry `(j, i)`, which is
+    /// looked up directly, without materializing the transpose. The entries are considered
+    /// equal if `|a_ij - a_ji| <= abs_tol + rel_tol * max(|a_ij|, |a_ji|)`, which combines an
+    /// absolute tolerance for entries close to zero with a relative tolerance for large
+    /// entries. This is useful for confirming that e.g. a discretized operator is symmetric
+    /// despite floating-point noise in its assembly.
+    ///
+    /// Returns `false` immediately upon encountering a structural asymmetry, i.e. a stored
+    /// off-diagonal entry `(i, j)` for which `(j, i)` is not explicitly stored.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the matrix is not square.
+    #[must_use]
+    pub fn is_symmetric_within(&self, abs_tol: T::RealField, rel_tol: T::RealField) -> bool
+    where
+        T: ComplexField,
+    {
+        assert_eq!(
+            self.nrows(),
+            self.ncols(),
+            "Cannot check symmetry of a non-square matrix."
+        );
+        for (i, row) in self.row_iter().enumerate() {
+            for (&j, v) in row.col_indices().iter().zip(row.values()) {
+                // Diagonal entries are trivially symmetric
+                if i == j {
+                    continue;
+                }
+                match self.index_entry(j, i) {
+                    SparseEntry::NonZero(w) => {
+                        let diff = (v.clone() - w.clone()).modulus();
+                        let scale = v.clone().modulus().max(w.clone().modulus());
+                        if diff > abs_tol.clone() + rel_tol.clone() * scale {
+                            return false;
+                        }
+                    }
+                    SparseEntry::Zero => return false,
+                }
+            }
+        }
+        true
+    }
+
+    /// Constructs the graph Laplacian `L = D - W` of the matrix, interpreted as the weight
+    /// matrix `W` of an undirected graph.
+    ///
+    /// `D` is the diagonal degree matrix whose entries are the row sums of `W`. The pattern of
+    /// the result is the union of the pattern of `W` with the full diagonal, so that the degree
+    /// entries are always explicitly stored. Symmetry of the input is not checked; for an
+    /// asymmetric input the degrees are simply the row sums.
+    ///
+    /// If `normalized` is `true`, the symmetric normalized Laplacian
+    /// `I - D^{-1/2} W D^{-1/2}` is computed instead. Isolated nodes - nodes with zero
+    /// degree - produce a zero row in the normalized Laplacian instead of a division by zero.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the matrix is not square.
+    #[must_use]
+    pub fn graph_laplacian(&self, normalized: bool) -> Self
+    where
+        T: RealField,
+    {
+        assert_eq!(
+            self.nrows(),
+            self.ncols(),
+            "Cannot construct the graph Laplacian of a non-square matrix."
+        );
+        let n = self.nrows();
+
+        let mut degrees = vec![T::zero(); n];
+        for (i, row) in self.row_iter().enumerate() {
+            for w_ij in row.values() {
+                degrees[i] += w_ij.clone();
+            }
+        }
+
+        // Scaling factors applied to the entries of W: 1 for the standard Laplacian,
+        // D^{-1/2} for the normalized one, with isolated nodes mapped to zero.
+        let scales: Vec<T> = if normalized {
+            degrees
+                .iter()
+                .map(|d| {
+                    if d.is_zero() {
+                        T::zero()
+                    } else {
+                        T::one() / d.clone().sqrt()
+                    }
+                })
+                .collect()
+        } else {
+            vec![T::one(); n]
+        };
+
+        let diagonal =
+            SparsityPattern::try_from_offsets_and_indices(n, n, (0..=n).collect(), (0..n).collect())
+                .expect("Internal error: Diagonal pattern must be valid by construction");
+        let pattern = spadd_pattern(self.pattern(), &diagonal);
+        let mut values = vec![T::zero(); pattern.nnz()];
+
+        {
+            let offsets = pattern.major_offsets();
+            let indices = pattern.minor_indices();
+            for (i, row) in self.row_iter().enumerate() {
+                let range = offsets[i]..offsets[i + 1];
+                let union_cols = &indices[range.clone()];
+                let union_vals = &mut values[range];
+                let mut k = 0;
+                for (&j, w_ij) in row.col_indices().iter().zip(row.values()) {
+                    while union_cols[k] != j {
+                        k += 1;
+                    }
+                    union_vals[k] = -(scales[i].clone() * w_ij.clone() * scales[j].clone());
+                }
+                // The diagonal entry is present in the union pattern by construction
+                let diag_pos = union_cols
+                    .iter()
+                    .position(|&j| j == i)
+                    .expect("Internal error: Union pattern must contain the diagonal");
+                if normalized {
+                    if !degrees[i].is_zero() {
+                        union_vals[diag_pos] += T::one();
+                    }
+                } else {
+                    union_vals[diag_pos] += degrees[i].clone();
+                }
+            }
+        }
+
+        Self::try_from_pattern_and_values(pattern, values)
+            .expect("Internal error: Pattern and values must be compatible by construction")
+    }
+
+    /// Returns a zero-copy view of the given contiguous range of rows.
+    ///
+    /// See [`CsrMatrixRowsView`] for the operations available on the view.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the row range is decreasing or extends beyond the number of rows.
+    #[must_use]
+    pub fn rows(&self, range: Range<usize>) -> CsrMatrixRowsView<'_, T> {
+        assert!(
+            range.start <= range.end && range.end <= self.nrows(),
+            "Row range out of bounds."
+        );
+        CsrMatrixRowsView {
+            matrix: self,
+            rows: range,
+        }
+    }
+
+    /// Writes `d[i]` into each diagonal position of the matrix.
+    ///
+    /// The sparsity pattern is left unchanged; for iterative schemes that repeatedly adjust
+    /// only the diagonal of a fixed-pattern matrix, this is much cheaper than a full sparse
+    /// addition against a diagonal matrix. The presence of every diagonal position is
+    /// verified before any value is written, so on error the matrix is unmodified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with kind
+    /// [`InvalidStructure`](SparseFormatErrorKind::InvalidStructure) if any diagonal position
+    /// is not explicitly stored in the pattern.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the matrix is not square, or if the dimension of `d` does not match the
+    /// number of rows.
+    pub fn set_diagonal(&mut self, d: &DVector<T>) -> Result<(), SparseFormatError>
+    where
+        T: Scalar,
+    {
+        assert_eq!(
+            self.nrows(),
+            self.ncols(),
+            "Matrix must be square to set its diagonal."
+        );
+        assert_eq!(
+            d.nrows(),
+            self.nrows(),
+            "Dimension of d must match the number of rows of the matrix."
+        );
+
+        let pattern = self.pattern();
+        for i in 0..pattern.major_dim() {
+            if pattern.lane(i).binary_search(&i).is_err() {
+                return Err(SparseFormatError::from_kind_and_error(
+                    SparseFormatErrorKind::InvalidStructure,
+                    format!(
+                        "Diagonal entry ({}, {}) is not explicitly stored in the pattern.",
+                        i, i
+                    )
+                    .into(),
+                ));
+            }
+        }
+
+        for i in 0..d.nrows() {
+            match self.index_entry_mut(i, i) {
+                SparseEntryMut::NonZero(a_ii) => *a_ii = d[i].clone(),
+                SparseEntryMut::Zero => {
+                    unreachable!("Internal error: Diagonal presence verified above")
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds `d[i]` to each diagonal position of the matrix.
+    ///
+    /// Like [`CsrMatrix::set_diagonal`], this leaves the sparsity pattern unchanged and is
+    /// intended for repeated diagonal adjustments of a fixed-pattern matrix.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the matrix is not square, if the dimension of `d` does not match the number
+    /// of rows, or if any diagonal position is not explicitly stored in the pattern.
+    pub fn add_to_diagonal(&mut self, d: &DVector<T>)
+    where
+        T: Scalar + ClosedAdd,
+    {
+        assert_eq!(
+            self.nrows(),
+            self.ncols(),
+            "Matrix must be square to add to its diagonal."
+        );
+        assert_eq!(
+            d.nrows(),
+            self.nrows(),
+            "Dimension of d must match the number of rows of the matrix."
+        );
+
+        for i in 0..d.nrows() {
+            match self.index_entry_mut(i, i) {
+                SparseEntryMut::NonZero(a_ii) => *a_ii += d[i].clone(),
+                SparseEntryMut::Zero => {
+                    panic!("Diagonal entry ({}, {}) is not explicitly stored in the pattern.", i, i)
+                }
+            }
+        }
+    }
+
+    /// Applies `f` to each diagonal entry of the matrix.
+    ///
+    /// This generalizes [`CsrMatrix::set_diagonal`] and [`CsrMatrix::add_to_diagonal`] to
+    /// arbitrary, possibly nonlinear transformations of the diagonal, such as the Tikhonov
+    /// shift `d -> d + lambda` or clamping `d -> max(d, floor)`. The sparsity pattern is left
+    /// unchanged, and the presence of every diagonal position is verified before any value is
+    /// modified, so on error the matrix is unmodified. Use [`CsrMatrix::ensure_diagonal`]
+    /// first if the diagonal may not be fully present.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with kind
+    /// [`InvalidStructure`](SparseFormatErrorKind::InvalidStructure) if any diagonal position
+    /// is not explicitly stored in the pattern.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the matrix is not square.
+    pub fn map_diagonal<F>(&mut self, f: F) -> Result<(), SparseFormatError>
+    where
+        T: Scalar,
+        F: Fn(T) -> T,
+    {
+        assert_eq!(
+            self.nrows(),
+            self.ncols(),
+            "Matrix must be square to map its diagonal."
+        );
+
+        let pattern = self.pattern();
+        for i in 0..pattern.major_dim() {
+            if pattern.lane(i).binary_search(&i).is_err() {
+                return Err(SparseFormatError::from_kind_and_error(
+                    SparseFormatErrorKind::InvalidStructure,
+                    format!(
+                        "Diagonal entry ({}, {}) is not explicitly stored in the pattern.",
+                        i, i
+                    )
+                    .into(),
+                ));
+            }
+        }
+
+        for i in 0..self.nrows() {
+            match self.index_entry_mut(i, i) {
+                SparseEntryMut::NonZero(a_ii) => *a_ii = f(a_ii.clone()),
+                SparseEntryMut::Zero => {
+                    unreachable!("Internal error: Diagonal presence verified above")
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if every diagonal position is a structural (explicitly stored) entry.
+    ///
+    /// The diagonal positions are `(i, i)` for `i in 0..min(nrows, ncols)`; an explicitly
+    /// stored zero counts as present. This is the precondition for in-place diagonal
+    /// operations such as [`CsrMatrix::set_diagonal`] and [`CsrMatrix::add_to_diagonal`], as
+    /// well as for factorizations that require a structurally non-zero diagonal, so it can be
+    /// used to cheaply guard these operations.
+    #[must_use]
+    pub fn has_full_diagonal(&self) -> bool {
+        let pattern = self.pattern();
+        (0..self.nrows().min(self.ncols())).all(|i| pattern.lane(i).binary_search(&i).is_ok())
+    }
+
+    /// Returns a copy of the matrix in which every diagonal position is explicitly stored.
+    ///
+    /// Diagonal positions missing from the sparsity pattern are inserted with an explicit
+    /// value of zero; all other entries are unchanged. If the diagonal is already fully
+    /// present (see [`CsrMatrix::has_full_diagonal`]), the result is equal to a clone of the
+    /// matrix.
+    #[must_use]
+    pub fn ensure_diagonal(&self) -> CsrMatrix<T>
+    where
+        T: Scalar + Zero,
+    {
+        if self.has_full_diagonal() {
+            return self.clone();
+        }
+
+        let diag_dim = self.nrows().min(self.ncols());
+        let mut offsets = Vec::with_capacity(self.nrows() + 1);
+        let mut indices = Vec::with_capacity(self.nnz() + diag_dim);
+        let mut values = Vec::with_capacity(self.nnz() + diag_dim);
+        offsets.push(0);
+        for (i, row) in self.row_iter().enumerate() {
+            let mut diag_present = i >= diag_dim;
+            for (&j, v) in row.col_indices().iter().zip(row.values()) {
+                if !diag_present && j >= i {
+                    if j > i {
+                        indices.push(i);
+                        values.push(T::zero());
+                    }
+                    diag_present = true;
+                }
+                indices.push(j);
+                values.push(v.clone());
+            }
+            if !diag_present {
+                indices.push(i);
+                values.push(T::zero());
+            }
+            offsets.push(indices.len());
+        }
+
+        Self::try_from_csr_data(self.nrows(), self.ncols(), offsets, indices, values)
+            .expect("Internal error: Diagonal insertion must produce valid CSR data")
+    }
+
+    /// Computes the matrix product `self * other`, checking that the dimensions are compatible
+    /// instead of panicking.
+    ///
+    /// Returns an error with kind
+    /// [`IncompatibleDimensions`](OperationErrorKind::IncompatibleDimensions) if
+    /// `self.ncols() != other.nrows()`. The check is performed before any allocation or
+    /// symbolic work, so the mismatch case is cheap. On success the result is identical to the
+    /// one produced by the `*` operator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dimensions are incompatible.
+    pub fn checked_mul(&self, other: &CsrMatrix<T>) -> Result<CsrMatrix<T>, OperationError>
+    where
+        T: Scalar + ClosedAdd + ClosedSub + ClosedMul + Zero + One + Neg<Output = T>,
+    {
+        if self.ncols() != other.nrows() {
+            return Err(OperationError::from_kind_and_message(
+                OperationErrorKind::IncompatibleDimensions {
+                    a_cols: self.ncols(),
+                    b_rows: other.nrows(),
+                },
+                format!(
+                    "Cannot multiply a matrix with {} columns by a matrix with {} rows.",
+                    self.ncols(),
+                    other.nrows()
+                ),
+            ));
+        }
+        Ok(self * other)
+    }
+
+    /// Returns `true` if `self + other` is a valid operation, i.e. the dimensions of the two
+    /// matrices match.
+    ///
+    /// This is a cheap predicate intended for pre-validating operations, e.g. to disable an
+    /// invalid operation in user-facing code without catching panics.
+    #[must_use]
+    pub fn can_add(&self, other: &CsrMatrix<T>) -> bool {
+        self.nrows() == other.nrows() && self.ncols() == other.ncols()
+    }
+
+    /// Returns `true` if `self * other` is a valid operation, i.e.
+    /// `self.ncols() == other.nrows()`.
+    ///
+    /// See also [`can_add`](Self::can_add) and
+    /// [`can_mul_transposed`](Self::can_mul_transposed).
+    #[must_use]
+    pub fn can_mul(&self, other: &CsrMatrix<T>) -> bool {
+        self.ncols() == other.nrows()
+    }
+
+    /// Returns `true` if `op(self) * op(other)` is a valid operation, where `op(x)` denotes
+    /// either `x` or its transpose as selected by the corresponding flag.
+    ///
+    /// This matches the dimension logic of the `spmm` operations in
+    /// [`ops::serial`](crate::ops::serial) exactly: the product is valid if the number of
+    /// columns of `op(self)` equals the number of rows of `op(other)`.
+    #[must_use]
+    pub fn can_mul_transposed(&self, trans_a: bool, other: &CsrMatrix<T>, trans_b: bool) -> bool {
+        let a_cols = if trans_a { self.nrows() } else { self.ncols() };
+        let b_rows = if trans_b { other.ncols() } else { other.nrows() };
+        a_cols == b_rows
+    }
+
+    /// Computes the diagonally preconditioned residual norm `||D^{-1/2} r||`, where `D` is the
+    /// diagonal of this matrix.
+    ///
+    /// This bundles diagonal extraction and the weighted 2-norm into a single pass over the
+    /// matrix, which is convenient for stopping criteria in iterative solvers. Rows whose
+    /// diagonal entry is not explicitly stored or not strictly positive are skipped, i.e. the
+    /// corresponding component of `r` does not contribute to the norm. For the symmetric
+    /// positive definite matrices this is intended for, all diagonal entries are positive and
+    /// no component is skipped.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the matrix is not square, or if the dimension of `r` does not match the
+    /// number of rows.
+    #[must_use]
+    pub fn diagonal_scaled_norm(&self, r: &DVector<T>) -> T
+    where
+        T: RealField,
+    {
+        assert_eq!(
+            self.nrows(),
+            self.ncols(),
+            "Matrix must be square for diagonal_scaled_norm."
+        );
+        assert_eq!(
+            r.nrows(),
+            self.nrows(),
+            "Dimension of r must match the number of rows of the matrix."
+        );
+
+        let mut acc = T::zero();
+        for (i, row) in self.row_iter().enumerate() {
+            if let Ok(local_idx) = row.col_indices().binary_search(&i) {
+                let d = row.values()[local_idx].clone();
+                if d > T::zero() {
+                    acc += r[i].clone() * r[i].clone() / d;
+                }
+            }
+        }
+        acc.sqrt()
+    }
+
+    /// Computes the product `D * self`, where `D` is a diagonal matrix stored in CSR format.
+    ///
+    /// This is equivalent to scaling row `i` of `self` by the diagonal entry `D[(i, i)]`, and
+    /// runs in `O(nnz)` time instead of invoking the general sparse-sparse multiplication
+    /// kernel. Rows whose diagonal entry is not explicitly stored in `d` are scaled by zero.
+    /// The result has the same sparsity pattern as `self`.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `d` is not square, stores an entry off the diagonal, or if the dimensions
+    /// of the matrices are not compatible with the product.
+    #[must_use]
+    pub fn mul_diagonal_left(&self, d: &CsrMatrix<T>) -> Self
+    where
+        T: Scalar + ClosedMul + Zero,
+    {
+        assert_eq!(d.nrows(), d.ncols(), "The scaling matrix must be square.");
+        assert_eq!(d.ncols(), self.nrows(), "D.ncols() != A.nrows()");
+
+        let mut scales = vec![T::zero(); d.nrows()];
+        for (i, j, d_ii) in d.triplet_iter() {
+            assert_eq!(i, j, "The scaling matrix must be diagonal.");
+            scales[i] = d_ii.clone();
+        }
+
+        let mut result = self.clone();
+        for (i, mut row) in result.row_iter_mut().enumerate() {
+            for a_ij in row.values_mut() {
+                *a_ij = scales[i].clone() * a_ij.clone();
+            }
+        }
+        result
+    }
+
+    /// Converts the matrix to a [`CooMatrix`], with one triplet per explicitly stored entry.
+    ///
+    /// The triplets are produced in row-major order, i.e. the order in which the entries are
+    /// stored in the CSR format. This is a convenience for feeding matrices into tools that
+    /// consume triplets; a round trip `coo -> csr -> coo` preserves the content of the
+    /// matrix, with duplicate entries summed by the first conversion.
+    #[must_use]
+    pub fn to_coo(&self) -> CooMatrix<T>
+    where
+        T: Scalar,
+    {
+        convert_csr_coo(self)
+    }
+
+    /// Computes the Gram matrix `A^T * A` of the matrix `A = self`.
+    ///
+    /// The product is accumulated directly from the row-wise outer product expansion
+    /// `A^T A = sum_i a_i a_i^T`, where `a_i` is row `i` of `A`. This avoids materializing
+    /// the transpose of `A` and the generic sparse-sparse multiplication kernel, which makes
+    /// it well-suited for forming least-squares normal equations.
+    ///
+    /// Since the result is symmetric, `upper_triangle_only` can be used to store only the
+    /// entries on or above the diagonal.
+    #[must_use]
+    pub fn gram(&self, upper_triangle_only: bool) -> Self
+    where
+        T: Scalar + ClosedAdd + ClosedMul,
+    {
+        let n = self.ncols();
+        let triplets = self.row_iter().flat_map(|row| {
+            let cols = row.col_indices();
+            let vals = row.values();
+            let mut products = Vec::with_capacity(cols.len() * cols.len());
+            for (&j, a_ij) in cols.iter().zip(vals) {
+                for (&k, a_ik) in cols.iter().zip(vals) {
+                    if !upper_triangle_only || k >= j {
+                        products.push((j, k, a_ij.clone() * a_ik.clone()));
+                    }
+                }
+            }
+            products
+        });
+        Self::from_triplet_iter_summed(n, n, triplets)
+    }
+
+    /// Computes the transpose product `A * A^T` of the matrix `A = self`.
+    ///
+    /// The dual of [`CsrMatrix::gram`], needed e.g. by least-squares formulations that work
+    /// with the row space of `A`. Entry `(i, k)` of the result is the dot product of rows `i`
+    /// and `k`, which is non-zero only where their column supports intersect. The product is
+    /// accumulated from the column-wise outer product expansion `A A^T = sum_j a^j (a^j)^T`,
+    /// where `a^j` is column `j` of `A`; the columns are scattered into transient adjacency
+    /// lists, which avoids both a CSR transpose allocation and the generic sparse-sparse
+    /// multiplication kernel.
+    ///
+    /// Since the result is symmetric, `upper_triangle_only` can be used to store only the
+    /// entries on or above the diagonal.
+    #[must_use]
+    pub fn gram_transpose(&self, upper_triangle_only: bool) -> Self
+    where
+        T: Scalar + ClosedAdd + ClosedMul,
+    {
+        let m = self.nrows();
+        let mut columns: Vec<Vec<(usize, T)>> = vec![Vec::new(); self.ncols()];
+        for (i, row) in self.row_iter().enumerate() {
+            for (&j, a_ij) in row.col_indices().iter().zip(row.values()) {
+                columns[j].push((i, a_ij.clone()));
+            }
+        }
+        let triplets = columns.into_iter().flat_map(|column| {
+            let mut products = Vec::with_capacity(column.len() * column.len());
+            for &(i, ref a_ij) in &column {
+                for &(k, ref a_kj) in &column {
+                    if !upper_triangle_only || k >= i {
+                        products.push((i, k, a_ij.clone() * a_kj.clone()));
+                    }
+                }
+            }
+            products
+        });
+        Self::from_triplet_iter_summed(m, m, triplets)
+    }
+
+    /// Normalizes each row so that its entries sum to one, producing a right-stochastic matrix.
+    ///
+    /// Every explicitly stored entry in row `i` is divided by the sum of the stored entries in
+    /// that row. This is the core operation for constructing Markov chain transition matrices
+    /// from non-negative weights. Rows whose entries sum to zero cannot be normalized;
+    /// the `zero_row_policy` argument determines how such rows are treated.
+    pub fn normalize_rows(&mut self, zero_row_policy: ZeroRowPolicy)
+    where
+        T: Scalar + ClosedAdd + ClosedDiv + Zero + One,
+    {
+        for mut row in self.row_iter_mut() {
+            let sum = row
+                .values()
+                .iter()
+                .fold(T::zero(), |acc, v| acc + v.clone());
+            if sum != T::zero() {
+                for v in row.values_mut() {
+                    *v = v.clone() / sum.clone();
+                }
+            } else if zero_row_policy == ZeroRowPolicy::Uniform && row.nnz() > 0 {
+                // The number of stored entries, expressed in T without requiring a
+                // usize conversion bound
+                let count = (0..row.nnz()).fold(T::zero(), |acc, _| acc + T::one());
+                for v in row.values_mut() {
+                    *v = T::one() / count.clone();
+                }
+            }
+        }
+    }
+
+    /// Computes the dot product of the row at the given row index with the dense vector `x`.
+    ///
+    /// This corresponds to entry `i` of the matrix-vector product `A * x`, which makes it
+    /// possible to probe the contribution of a single row without computing the whole product.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the row index is out of bounds, or if the length of `x` is not equal to the
+    /// number of columns in the matrix.
+    #[must_use]
+    pub fn row_dot(&self, i: usize, x: &DVector<T>) -> T
+    where
+        T: Scalar + ClosedAdd + ClosedMul + Zero,
+    {
+        assert_eq!(self.ncols(), x.nrows(), "A.ncols() != x.nrows()");
+        let row = self.row(i);
+        let mut dot = T::zero();
+        for (&j, a_ij) in row.col_indices().iter().zip(row.values()) {
+            dot += a_ij.clone() * x[j].clone();
+        }
+        dot
+    }
+}
+
+#[cfg(feature = "rand")]
+impl CsrMatrix<f64> {
+    /// Constructs a random `n x n` symmetric positive definite matrix with approximately the
+    /// given off-diagonal density.
+    ///
+    /// Each off-diagonal position `(i, j)` with `i < j` is included with probability `density`
+    /// and assigned a value drawn uniformly from `(-1, 1)`, mirrored to `(j, i)` to make the
+    /// matrix symmetric. Each diagonal entry is then set to the sum of the absolute values of
+    /// the off-diagonal entries in its row plus one, which makes the matrix strictly diagonally
+    /// dominant and hence positive definite. All diagonal entries are explicitly stored.
+    ///
+    /// The construction is deterministic given the state of the RNG, so that seeding the RNG
+    /// yields reproducible test inputs for e.g. iterative solvers and factorizations.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if `density` is not in the interval `[0, 1]`.
+    #[must_use]
+    pub fn random_spd(n: usize, density: f64, rng: &mut impl Rng) -> CsrMatrix<f64> {
+        assert!(
+            (0.0..=1.0).contains(&density),
+            "density must be in the interval [0, 1]."
+        );
+        let mut coo = CooMatrix::new(n, n);
+        let mut offdiag_abs_sums = vec![0.0; n];
+        for i in 0..n {
+            for j in (i + 1)..n {
+                if rng.gen_bool(density) {
+                    let v = rng.gen_range(-1.0..1.0);
+                    coo.push(i, j, v);
+                    coo.push(j, i, v);
+                    offdiag_abs_sums[i] += v.abs();
+                    offdiag_abs_sums[j] += v.abs();
+                }
+            }
+        }
+        for (i, sum) in offdiag_abs_sums.into_iter().enumerate() {
+            coo.push(i, i, sum + 1.0);
+        }
+        CsrMatrix::from(&coo)
+    }
+}
+
+/// A zero-copy view of a contiguous range of rows of a [`CsrMatrix`].
+///
+/// The view borrows the source matrix and shares its pattern and value arrays: no offsets,
+/// indices or values are copied. This makes it suitable for algorithms that repeatedly
+/// process a band of rows, where constructing submatrices by copy would be wasteful. A view
+/// is created with [`CsrMatrix::rows`].
+#[derive(Debug, Clone)]
+pub struct CsrMatrixRowsView<'a, T> {
+    matrix: &'a CsrMatrix<T>,
+    rows: Range<usize>,
+}
+
+impl<'a, T> CsrMatrixRowsView<'a, T> {
+    /// The number of rows in the view.
+    #[inline]
+    #[must_use]
+    pub fn nrows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The number of columns in the view, which equals the number of columns of the source
+    /// matrix.
+    #[inline]
+    #[must_use]
+    pub fn ncols(&self) -> usize {
+        self.matrix.ncols()
+    }
+
+    /// The number of explicitly stored entries in the rows covered by the view.
+    #[inline]
+    #[must_use]
+    pub fn nnz(&self) -> usize {
+        let offsets = self.matrix.row_offsets();
+        offsets[self.rows.end] - offsets[self.rows.start]
+    }
+
+    /// An iterator over the rows covered by the view, in order.
+    ///
+    /// The returned rows borrow the source matrix, not the view, so they may outlive the
+    /// view itself.
+    pub fn row_iter(&self) -> impl Iterator<Item = CsrRow<'a, T>> {
+        let matrix = self.matrix;
+        self.rows.clone().map(move |i| matrix.row(i))
+    }
+
+    /// Computes the matrix-vector product of the view with the given dense vector.
+    ///
+    /// The result has one entry per row of the view.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the dimension of `x` does not match the number of columns of the view.
+    #[must_use]
+    pub fn spmv(&self, x: &DVector<T>) -> DVector<T>
+    where
+        T: Scalar + ClosedAdd + ClosedMul + Zero,
+    {
+        assert_eq!(self.ncols(), x.nrows(), "A.ncols() != x.nrows()");
+        DVector::from_iterator(
+            self.nrows(),
+            self.rows.clone().map(|i| self.matrix.row_dot(i, x)),
+        )
+    }
+}
+
+/// Returns the (column index, value) pair of the largest value among the given entries,
+/// skipping values that do not compare.
+fn row_max_entry<'a, T: PartialOrd>(cols: &[usize], values: &'a [T]) -> Option<(usize, &'a T)> {
+    let mut best: Option<(usize, &T)> = None;
+    for (&j, v) in cols.iter().zip(values) {
+        // Skip values that are not comparable to themselves, such as NaN
+        if v.partial_cmp(v).is_none() {
+            continue;
+        }
+        match &best {
+            Some((_, best_val)) if !(v > best_val) => {}
+            _ => best = Some((j, v)),
+        }
+    }
+    best
+}
+
+/// Convert pattern format errors into more meaningful CSR-specific errors.
+///
+/// This ensures that the terminology is consistent: we are talking about rows and columns,
+/// not lanes, major and minor dimensions.
+fn pattern_format_error_to_csr_error(err: SparsityPatternFormatError) -> SparseFormatError {
+    use SparseFormatError as E;
+    use SparseFormatErrorKind as K;
+    use SparsityPatternFormatError::DuplicateEntry as PatternDuplicateEntry;
+    use SparsityPatternFormatError::*;
+
+    match err {
+        InvalidOffsetArrayLength => E::from_kind_and_msg(
+            K::InvalidStructure,
+            "Length of row offset array is not equal to nrows + 1.",
+        ),
+        InvalidOffsetFirstLast => E::from_kind_and_msg(
+            K::InvalidStructure,
+            "First or last row offset is inconsistent with format specification.",
+        ),
+        NonmonotonicOffsets => E::from_kind_and_msg(
+            K::InvalidStructure,
+            "Row offsets are not monotonically increasing.",
+        ),
+        NonmonotonicMinorIndices => E::from_kind_and_msg(
+            K::InvalidStructure,
+            "Column indices are not monotonically increasing (sorted) within each row.",
+        ),
+        MinorIndexOutOfBounds => {
+            E::from_kind_and_msg(K::IndexOutOfBounds, "Column indices are out of bounds.")
+        }
+        PatternDuplicateEntry => {
+            E::from_kind_and_msg(K::DuplicateEntry, "Matrix data contains duplicate entries.")
+        }
+    }
+}
+
+/// Iterator type for iterating over triplets in a CSR matrix.
+#[derive(Debug)]
+pub struct CsrTripletIter<'a, T> {
+    pattern_iter: SparsityPatternIter<'a>,
+    values_iter: Iter<'a, T>,
+}
+
+impl<'a, T: Clone> CsrTripletIter<'a, T> {
+    /// Adapts the triplet iterator to return owned values.
+    ///
+    /// The triplet iterator returns references to the values. This method adapts the iterator
+    /// so that the values are cloned.
+    #[inline]
+    pub fn cloned_values(self) -> impl 'a + Iterator<Item = (usize, usize, T)> {
+        self.map(|(i, j, v)| (i, j, v.clone()))
+    }
+}
+
+impl<'a, T> Iterator for CsrTripletIter<'a, T> {
+    type Item = (usize, usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_entry = self.pattern_iter.next();
+        let next_value = self.values_iter.next();
+
+        match (next_entry, next_value) {
+            (Some((i, j)), Some(v)) => Some((i, j, v)),
+            _ => None,
+        }
+    }
+}
+
+/// Iterator type for mutably iterating over triplets in a CSR matrix.
+#[derive(Debug)]
+pub struct CsrTripletIterMut<'a, T> {
+    pattern_iter: SparsityPatternIter<'a>,
+    values_mut_iter: IterMut<'a, T>,
 }
 
 impl<'a, T> Iterator for CsrTripletIterMut<'a, T> {
@@ -762,8 +3385,20 @@ impl<'a, T> Iterator for CsrRowIter<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.lane_iter.next().map(|lane| CsrRow { lane })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lane_iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for CsrRowIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.lane_iter.next_back().map(|lane| CsrRow { lane })
+    }
 }
 
+impl<'a, T> ExactSizeIterator for CsrRowIter<'a, T> {}
+
 /// Mutable row iterator for [CsrMatrix](struct.CsrMatrix.html).
 pub struct CsrRowIterMut<'a, T> {
     lane_iter: CsLaneIterMut<'a, T>,
@@ -778,4 +3413,19 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         self.lane_iter.next().map(|lane| CsrRowMut { lane })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lane_iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for CsrRowIterMut<'a, T>
+where
+    T: 'a,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.lane_iter.next_back().map(|lane| CsrRowMut { lane })
+    }
 }
+
+impl<'a, T> ExactSizeIterator for CsrRowIterMut<'a, T> where T: 'a {}
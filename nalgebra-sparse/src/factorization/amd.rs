@@ -0,0 +1,133 @@
+//! An approximate minimum degree ordering for fill reduction.
+use crate::pattern::SparsityPattern;
+
+/// Computes an approximate minimum degree (AMD) ordering of a structurally symmetric
+/// sparsity pattern.
+///
+/// Minimum degree orderings greatly reduce the fill-in produced by sparse Cholesky
+/// factorization: at each step, the variable with the (approximately) fewest remaining
+/// neighbors is eliminated next. The elimination is carried out symbolically on a quotient
+/// graph, where eliminated variables become *elements* whose adjacency implicitly represents
+/// the fill produced so far, and the degree of a variable is bounded from above by the sum of
+/// the sizes of its adjacent elements rather than computed exactly. This is the approximation
+/// introduced by Amestoy, Davis and Duff, which makes the method far cheaper than exact
+/// minimum degree while producing orderings of comparable quality.
+///
+/// Returns a permutation `perm` of `0 .. n` such that `perm[k]` is the index of the variable
+/// eliminated in step `k`. Applying the ordering to a matrix `A` amounts to forming the
+/// permuted matrix `B` with `B[i, j] = A[perm[i], perm[j]]`, which is then factored in the
+/// natural order.
+///
+/// The pattern is interpreted as an undirected graph: an edge connects `i` and `j` if either
+/// `(i, j)` or `(j, i)` is explicitly stored. Diagonal entries are ignored. For patterns that
+/// are not structurally symmetric, the ordering is computed for the symmetrized pattern
+/// `A + A^T`.
+///
+/// # Panics
+///
+/// Panics if the pattern is not square.
+///
+/// # References
+///
+/// Amestoy, Patrick R., Timothy A. Davis, and Iain S. Duff.
+/// "An approximate minimum degree ordering algorithm."
+/// SIAM Journal on Matrix Analysis and Applications 17.4 (1996): 886-905.
+pub fn approximate_minimum_degree(pattern: &SparsityPattern) -> Vec<usize> {
+    assert_eq!(
+        pattern.major_dim(),
+        pattern.minor_dim(),
+        "The pattern must be square."
+    );
+    let n = pattern.major_dim();
+
+    // Symmetrized adjacency lists without diagonal entries
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (i, j) in pattern.entries() {
+        if i != j {
+            adjacency[i].push(j);
+        }
+    }
+    for i in 0..n {
+        for lane_idx in 0..adjacency[i].len() {
+            let j = adjacency[i][lane_idx];
+            if !adjacency[j].contains(&i) {
+                adjacency[j].push(i);
+            }
+        }
+    }
+
+    // Quotient graph state. When a variable p is eliminated it becomes an element whose
+    // variable list `element_vars[p]` holds the uneliminated variables adjacent to it;
+    // `elements[i]` lists the elements adjacent to variable i.
+    let mut elements: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut element_vars: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut degree: Vec<usize> = adjacency.iter().map(Vec::len).collect();
+    let mut eliminated = vec![false; n];
+    let mut marker = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+
+    for _ in 0..n {
+        // Select the uneliminated variable of minimum approximate degree, breaking ties by
+        // index for determinism
+        let p = (0..n)
+            .filter(|&i| !eliminated[i])
+            .min_by_key(|&i| (degree[i], i))
+            .expect("Internal error: There must be at least one uneliminated variable.");
+
+        // The variables reachable from p through its adjacency list and through its adjacent
+        // elements form the variable list of the new element
+        let mut reachable = Vec::new();
+        for &j in &adjacency[p] {
+            if !eliminated[j] && !marker[j] {
+                marker[j] = true;
+                reachable.push(j);
+            }
+        }
+        for &e in &elements[p] {
+            for &j in &element_vars[e] {
+                if !eliminated[j] && j != p && !marker[j] {
+                    marker[j] = true;
+                    reachable.push(j);
+                }
+            }
+        }
+
+        eliminated[p] = true;
+        order.push(p);
+
+        // The elements adjacent to p are absorbed into the new element p
+        let absorbed = std::mem::take(&mut elements[p]);
+        for &e in &absorbed {
+            element_vars[e].clear();
+        }
+
+        for &i in &reachable {
+            // Adjacency covered by the new element is pruned; `marker` still flags exactly
+            // the members of `reachable`
+            adjacency[i].retain(|&j| !eliminated[j] && !marker[j]);
+            elements[i].retain(|e| !absorbed.contains(e));
+            elements[i].push(p);
+
+            // Approximate degree: an upper bound that counts each adjacent element by the
+            // full size of its variable list instead of the exact union
+            let element_bound: usize = elements[i]
+                .iter()
+                .map(|&e| {
+                    if e == p {
+                        reachable.len() - 1
+                    } else {
+                        element_vars[e].len()
+                    }
+                })
+                .sum();
+            degree[i] = (adjacency[i].len() + element_bound).min(n - order.len() - 1);
+        }
+
+        for &j in &reachable {
+            marker[j] = false;
+        }
+        element_vars[p] = reachable;
+    }
+
+    order
+}
@@ -1,6 +1,12 @@
 //! Matrix factorization for sparse matrices.
 //!
 //! Currently, the only factorization provided here is the [`CscCholesky`] factorization.
+//! In addition, the [`approximate_minimum_degree`] fill-reducing ordering is provided to
+//! permute a matrix before factorization.
+mod amd;
 mod cholesky;
+mod solver;
 
+pub use amd::*;
 pub use cholesky::*;
+pub use solver::*;
@@ -0,0 +1,28 @@
+//! A common interface for factorizations that can solve linear systems.
+use nalgebra::{DVector, RealField};
+
+use crate::factorization::CscCholesky;
+
+/// A linear solver for systems involving a matrix `A` and its transpose.
+///
+/// This trait abstracts over factorizations (or other solution methods) of a fixed matrix
+/// `A`, so that algorithms requiring repeated solves — such as condition estimators and
+/// iterative refinement — can be written independently of the concrete factorization.
+pub trait LinearSolver<T: RealField> {
+    /// Solves the system `A x = b` and returns the solution.
+    fn solve_vector(&self, b: &DVector<T>) -> DVector<T>;
+
+    /// Solves the transposed system `A^T x = b` and returns the solution.
+    fn solve_transpose_vector(&self, b: &DVector<T>) -> DVector<T>;
+}
+
+impl<T: RealField> LinearSolver<T> for CscCholesky<T> {
+    fn solve_vector(&self, b: &DVector<T>) -> DVector<T> {
+        DVector::from_column_slice(self.solve(b).as_slice())
+    }
+
+    fn solve_transpose_vector(&self, b: &DVector<T>) -> DVector<T> {
+        // The factored matrix is symmetric, so the transposed system is the same system
+        self.solve_vector(b)
+    }
+}
@@ -4,7 +4,8 @@
 use crate::SparseFormatError;
 use crate::SparseFormatErrorKind;
 use crate::{CooMatrix, CscMatrix, CsrMatrix};
-use nalgebra::Complex;
+use nalgebra::{ClosedAdd, Complex, DMatrix};
+use num_traits::Zero;
 use pest::iterators::Pairs;
 use pest::Parser;
 use std::cmp::PartialEq;
@@ -13,7 +14,7 @@ use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::Formatter;
 use std::fs::{self, File};
-use std::io::{BufWriter, Write};
+use std::io::{BufRead, BufWriter, Write};
 use std::num::ParseIntError;
 use std::num::TryFromIntError;
 use std::path::Path;
@@ -360,23 +361,40 @@ impl From<Infallible> for MatrixMarketError {
     }
 }
 
-#[derive(Debug, PartialEq)]
-enum Sparsity {
+/// The sparsity qualifier of a matrix market file, i.e. whether the entries are stored in
+/// `coordinate` (sparse) or `array` (dense) format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sparsity {
+    /// The entries are stored in `coordinate` format, i.e. as `(row, col, value)` triplets.
     Sparse,
+    /// The entries are stored in `array` format, i.e. as a dense column-major list of values.
     Dense,
 }
-#[derive(Debug, PartialEq)]
-enum DataType {
+/// The data type qualifier of a matrix market file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataType {
+    /// The entries are real numbers.
     Real,
+    /// The entries are complex numbers, stored as a pair of real numbers.
     Complex,
+    /// Only the sparsity pattern is stored; the entries carry no values.
     Pattern,
+    /// The entries are integers.
     Integer,
 }
-#[derive(Debug, PartialEq)]
-enum StorageScheme {
+/// The storage scheme qualifier of a matrix market file.
+///
+/// For schemes other than `general`, only the lower triangle is stored, and the remaining
+/// entries are implied by (skew-)symmetry or conjugate symmetry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageScheme {
+    /// The matrix is symmetric; only the lower triangle is stored.
     Symmetric,
+    /// All entries of the matrix are stored explicitly.
     General,
+    /// The matrix is skew-symmetric; only the strict lower triangle is stored.
     Skew,
+    /// The matrix is hermitian; only the lower triangle is stored.
     Hermitian,
 }
 #[derive(Debug, PartialEq)]
@@ -852,6 +870,40 @@ where
     load_coo_from_matrix_market_str(&file)
 }
 
+/// Parses a gzip-compressed Matrix Market file at the given path as a `CooMatrix`.
+///
+/// This is the counterpart to [load_coo_from_matrix_market_file] for `.mtx.gz` files, which
+/// avoids a manual decompress-to-temp-file step: the file is transparently decompressed while
+/// reading, and the decompressed text is parsed with the same logic as the plain-text path.
+///
+/// Errors
+/// --------
+///
+/// See [MatrixMarketErrorKind] for a list of possible error conditions. Corrupt gzip data
+/// surfaces as an [IOError](MatrixMarketErrorKind::IOError).
+///
+/// Examples
+/// --------
+/// ```no_run
+/// use nalgebra_sparse::io::load_coo_from_matrix_market_gz;
+/// let matrix = load_coo_from_matrix_market_gz::<f64, _>("path/to/matrix.mtx.gz").unwrap();
+/// ```
+#[cfg(feature = "flate2")]
+pub fn load_coo_from_matrix_market_gz<T, P: AsRef<Path>>(
+    path: P,
+) -> Result<CooMatrix<T>, MatrixMarketError>
+where
+    T: MatrixMarketScalar,
+{
+    use std::io::{BufReader, Read};
+
+    let file = File::open(path)?;
+    let mut decoder = flate2::bufread::GzDecoder::new(BufReader::new(file));
+    let mut data = String::new();
+    decoder.read_to_string(&mut data)?;
+    load_coo_from_matrix_market_str(&data)
+}
+
 /// Parses a Matrix Market file described by the given string as a `CooMatrix`.
 ///
 /// See [load_coo_from_matrix_market_file] for more information.
@@ -1078,6 +1130,430 @@ where
     )?)
 }
 
+/// Parses a Matrix Market file in `array` (dense) format described by the given string as a
+/// `DMatrix`.
+///
+/// The `array` format stores all entries of a dense matrix in column-major order and is
+/// commonly used for right-hand-side vectors accompanying a sparse system matrix. Storage
+/// schemes other than `general` (e.g. `symmetric` or `skew-symmetric`) are supported; the full
+/// matrix is reconstructed from the stored triangle. A matrix in `coordinate` (sparse) format
+/// is rejected with a [TypeMismatch](MatrixMarketErrorKind::TypeMismatch) error; use
+/// [load_coo_from_matrix_market_str] for those.
+///
+/// Errors
+/// --------
+///
+/// See [MatrixMarketErrorKind] for a list of possible error conditions.
+///
+/// Examples
+/// --------
+/// ```
+/// use nalgebra_sparse::io::load_dense_from_matrix_market_str;
+/// let str = r#"
+/// %%matrixmarket matrix array real general
+/// 3 1
+/// 1.0
+/// 2.0
+/// 3.0
+/// "#;
+/// let rhs = load_dense_from_matrix_market_str::<f64>(str).unwrap();
+/// assert_eq!(rhs.shape(), (3, 1));
+/// ```
+pub fn load_dense_from_matrix_market_str<T>(data: &str) -> Result<DMatrix<T>, MatrixMarketError>
+where
+    T: MatrixMarketScalar + Zero + ClosedAdd,
+{
+    // unwrap() here is guaranteed by parsing the data
+    let file = MatrixMarketParser::parse(Rule::Document, data)
+        .map_err(MatrixMarketError::from_pest_error)?
+        .next()
+        .unwrap();
+    let header_line = file.into_inner().next().unwrap();
+    let header_type = parse_header(&mut header_line.into_inner());
+    if header_type.sparsity != Sparsity::Dense {
+        return Err(MatrixMarketError::from_kind_and_message(
+            MatrixMarketErrorKind::TypeMismatch,
+            String::from(
+                "Matrix is stored in coordinate (sparse) format, but array (dense) format was expected",
+            ),
+        ));
+    }
+
+    // The coordinate importer already handles the dense entry ordering and the reconstruction
+    // of symmetric/skew-symmetric storage schemes; every entry is explicitly represented, so
+    // converting the result to a dense matrix is lossless.
+    let coo = load_coo_from_matrix_market_str::<T>(data)?;
+    Ok(DMatrix::from(&coo))
+}
+
+/// The header of a matrix market file, containing the dimensions, the number of stored
+/// entries and the typecode qualifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatrixMarketHeader {
+    /// The number of rows of the matrix.
+    pub nrows: usize,
+    /// The number of columns of the matrix.
+    pub ncols: usize,
+    /// The number of entries stored in the file.
+    ///
+    /// Note that for storage schemes other than `general`, the number of logical non-zero
+    /// entries of the matrix may be larger, since only the lower triangle is stored.
+    pub nnz: usize,
+    /// Whether the entries are stored in `coordinate` (sparse) or `array` (dense) format.
+    pub sparsity: Sparsity,
+    /// The data type of the stored entries.
+    pub datatype: DataType,
+    /// The storage scheme of the stored entries.
+    pub storagescheme: StorageScheme,
+}
+
+/// Parses the header of a matrix market file from a [BufRead] reader and returns a lazy
+/// iterator over its entries.
+///
+/// In contrast to [load_coo_from_matrix_market_str], this does not materialize the complete
+/// set of triplets in memory, which makes it possible to e.g. assemble very large files
+/// directly into a preallocated matrix, or to stream the entries into a reduction. The header -
+/// dimensions, number of stored entries and typecode qualifiers - is returned up front, and the
+/// iterator yields zero-based `(row, col, value)` triplets in file order as the input is read.
+///
+/// For storage schemes other than `general`, the entries implied by (skew-)symmetry are
+/// yielded directly after their stored counterparts, consistent with the expansion performed
+/// by [load_coo_from_matrix_market_str]. The iterator therefore may yield more triplets than
+/// the entry count declared in the header.
+///
+/// Only matrices in `coordinate` (sparse) format can be streamed; a matrix in `array` (dense)
+/// format is rejected with a [TypeMismatch](MatrixMarketErrorKind::TypeMismatch) error.
+///
+/// Errors
+/// --------
+///
+/// See [MatrixMarketErrorKind] for a list of possible error conditions. Errors encountered
+/// while reading or parsing entries are yielded by the iterator, which does not produce any
+/// further items after the first error.
+///
+/// Examples
+/// --------
+/// ```
+/// use nalgebra_sparse::io::matrix_market_entries;
+/// let str = r#"
+/// %%matrixmarket matrix coordinate integer general
+/// 5 4 2
+/// 1 1 10
+/// 2 3 5
+/// "#;
+/// let (header, entries) = matrix_market_entries::<i32, _>(str.trim_start().as_bytes()).unwrap();
+/// assert_eq!((header.nrows, header.ncols, header.nnz), (5, 4, 2));
+/// for entry in entries {
+///     let (row, col, value) = entry.unwrap();
+///     // ...
+/// }
+/// ```
+pub fn matrix_market_entries<T, R>(
+    mut reader: R,
+) -> Result<(MatrixMarketHeader, MatrixMarketEntryIter<R, T>), MatrixMarketError>
+where
+    T: MatrixMarketScalar,
+    R: BufRead,
+{
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(MatrixMarketError::from_kind_and_message(
+            MatrixMarketErrorKind::InvalidHeader,
+            String::from("The matrix market data is empty"),
+        ));
+    }
+    let lowercase_header = line.to_ascii_lowercase();
+    let mut words = lowercase_header.split_whitespace();
+    if words.next() != Some("%%matrixmarket") || words.next() != Some("matrix") {
+        return Err(MatrixMarketError::from_kind_and_message(
+            MatrixMarketErrorKind::InvalidHeader,
+            format!(
+                "The header line should start with %%matrixmarket matrix, but {} was provided",
+                line.trim()
+            ),
+        ));
+    }
+    let mut next_qualifier = || {
+        words.next().ok_or_else(|| {
+            MatrixMarketError::from_kind_and_message(
+                MatrixMarketErrorKind::InvalidHeader,
+                String::from("The header line is missing a typecode qualifier"),
+            )
+        })
+    };
+    let typecode = Typecode {
+        sparsity: next_qualifier()?.parse()?,
+        datatype: next_qualifier()?.parse()?,
+        storagescheme: next_qualifier()?.parse()?,
+    };
+    typecode_precheck(&typecode)?;
+    if typecode.sparsity != Sparsity::Sparse {
+        return Err(MatrixMarketError::from_kind_and_message(
+            MatrixMarketErrorKind::TypeMismatch,
+            String::from(
+                "Only matrices in coordinate format can be streamed; use load_dense_from_matrix_market_str for array format",
+            ),
+        ));
+    }
+
+    // The shape line is the first non-comment, non-blank line after the header
+    let shape_line = loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(MatrixMarketError::from_kind_and_message(
+                MatrixMarketErrorKind::ParsingError,
+                String::from("The matrix market data is missing its shape line"),
+            ));
+        }
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('%') {
+            break trimmed;
+        }
+    };
+    let mut shape_numbers = shape_line.split_whitespace();
+    let mut next_shape_number = || -> Result<usize, MatrixMarketError> {
+        let word = shape_numbers.next().ok_or_else(|| {
+            MatrixMarketError::from_kind_and_message(
+                MatrixMarketErrorKind::ParsingError,
+                format!(
+                    "Shape line requires 3 int numbers as number of rows, columns and non-zeros, but line {} was provided here.",
+                    shape_line
+                ),
+            )
+        })?;
+        Ok(word.parse::<usize>()?)
+    };
+    let (nrows, ncols, nnz) = (
+        next_shape_number()?,
+        next_shape_number()?,
+        next_shape_number()?,
+    );
+    // check for square matrix, when it's not a general matrix
+    if typecode.storagescheme != StorageScheme::General && nrows != ncols {
+        return Err(MatrixMarketError::from_kind_and_message(MatrixMarketErrorKind::NonSquare, format!("(Skew-)Symmetric or hermitian matrix should be square matrix, but it has dimension {} and {}", nrows, ncols)));
+    }
+
+    let header = MatrixMarketHeader {
+        nrows,
+        ncols,
+        nnz,
+        sparsity: typecode.sparsity,
+        datatype: typecode.datatype,
+        storagescheme: typecode.storagescheme,
+    };
+    let iter = MatrixMarketEntryIter {
+        reader,
+        header,
+        entries_read: 0,
+        mirrored: None,
+        finished: false,
+    };
+    Ok((header, iter))
+}
+
+/// A lazy iterator over the entries of a matrix market file, produced by
+/// [matrix_market_entries].
+///
+/// Yields zero-based `(row, col, value)` triplets, including the mirrored entries implied by
+/// storage schemes other than `general`. After the first error, no further items are produced.
+#[derive(Debug)]
+pub struct MatrixMarketEntryIter<R, T> {
+    reader: R,
+    header: MatrixMarketHeader,
+    entries_read: usize,
+    // Entry implied by the storage scheme, yielded directly after its stored counterpart
+    mirrored: Option<(usize, usize, T)>,
+    finished: bool,
+}
+
+impl<R, T> MatrixMarketEntryIter<R, T> {
+    /// The header of the matrix market file being streamed.
+    #[must_use]
+    pub fn header(&self) -> &MatrixMarketHeader {
+        &self.header
+    }
+}
+
+impl<R, T> MatrixMarketEntryIter<R, T>
+where
+    T: MatrixMarketScalar,
+{
+    /// Parses a single data line, stores the entry implied by the storage scheme (if any) for
+    /// the next call, and returns the stored entry.
+    fn parse_data_line(&mut self, line: &str) -> Result<(usize, usize, T), MatrixMarketError> {
+        let (r, c, d) = parse_streamed_entry::<T>(line, self.header.datatype)?;
+        if r >= self.header.nrows || c >= self.header.ncols {
+            return Err(MatrixMarketError::from_kind_and_message(
+                MatrixMarketErrorKind::SparseFormatError(SparseFormatErrorKind::IndexOutOfBounds),
+                format!(
+                    "Entry: row {} col {} is out of bounds for a matrix with dimension {} and {}",
+                    r + 1,
+                    c + 1,
+                    self.header.nrows,
+                    self.header.ncols
+                ),
+            ));
+        }
+        match self.header.storagescheme {
+            StorageScheme::General => {}
+            StorageScheme::Symmetric => {
+                check_lower_triangle(r, c)?;
+                // don't need to add twice if the element in on diagonal
+                if r != c {
+                    self.mirrored = Some((c, r, d.clone()));
+                }
+            }
+            StorageScheme::Skew => {
+                check_lower_triangle(r, c)?;
+                // skew-symmetric matrix shouldn't have diagonal element
+                if r == c {
+                    return Err(MatrixMarketError::from_kind_and_message(
+                        MatrixMarketErrorKind::DiagonalError,
+                        format!(
+                            "There is a diagonal element in skew matrix, in row(and column) {}",
+                            r + 1
+                        ),
+                    ));
+                }
+                self.mirrored = Some((c, r, d.clone().negative()?));
+            }
+            StorageScheme::Hermitian => {
+                check_lower_triangle(r, c)?;
+                if r == c && d != d.clone().conjugate()? {
+                    return Err(MatrixMarketError::from_kind_and_message(
+                        MatrixMarketErrorKind::DiagonalError,
+                        format!(
+                            "There is a diagonal element in hermitian matrix, which is not a real number, in row(and column) {}",
+                            r + 1
+                        ),
+                    ));
+                }
+                // don't need to add twice if the element in on diagonal
+                if r != c {
+                    self.mirrored = Some((c, r, d.clone().conjugate()?));
+                }
+            }
+        }
+        Ok((r, c, d))
+    }
+}
+
+impl<R, T> Iterator for MatrixMarketEntryIter<R, T>
+where
+    R: BufRead,
+    T: MatrixMarketScalar,
+{
+    type Item = Result<(usize, usize, T), MatrixMarketError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        if let Some(entry) = self.mirrored.take() {
+            return Some(Ok(entry));
+        }
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => {
+                    self.finished = true;
+                    if self.entries_read != self.header.nnz {
+                        return Some(Err(MatrixMarketError::from_kind_and_message(
+                            MatrixMarketErrorKind::EntryMismatch,
+                            format!(
+                                "{} entries required for the matrix, but {} was provided",
+                                self.header.nnz, self.entries_read,
+                            ),
+                        )));
+                    }
+                    return None;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err.into()));
+                }
+            }
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('%') {
+                continue;
+            }
+            if self.entries_read == self.header.nnz {
+                self.finished = true;
+                return Some(Err(MatrixMarketError::from_kind_and_message(
+                    MatrixMarketErrorKind::EntryMismatch,
+                    format!(
+                        "{} entries required for the matrix, but more were provided",
+                        self.header.nnz,
+                    ),
+                )));
+            }
+            self.entries_read += 1;
+            let result = self.parse_data_line(trimmed);
+            if result.is_err() {
+                self.finished = true;
+            }
+            return Some(result);
+        }
+    }
+}
+
+/// Parses a single whitespace-separated data line of a sparse matrix market file.
+fn parse_streamed_entry<T>(
+    line: &str,
+    datatype: DataType,
+) -> Result<(usize, usize, T), MatrixMarketError>
+where
+    T: MatrixMarketScalar,
+{
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let expected_fields = match datatype {
+        DataType::Pattern => 2,
+        DataType::Real | DataType::Integer => 3,
+        DataType::Complex => 4,
+    };
+    if fields.len() != expected_fields {
+        return Err(MatrixMarketError::from_kind_and_message(
+            MatrixMarketErrorKind::ParsingError,
+            format!(
+                "Sparse {:?} matrix requires {} whitespace-separated fields per entry, but line {} was provided",
+                datatype, expected_fields, line
+            ),
+        ));
+    }
+    let r = fields[0].parse::<usize>()?;
+    let c = fields[1].parse::<usize>()?;
+    if r * c == 0 {
+        return Err(MatrixMarketError::from_kind_and_message(
+            MatrixMarketErrorKind::ZeroError,
+            String::from("The data has to be one-indexed"),
+        ));
+    }
+    let d = match datatype {
+        DataType::Real => T::from_f64(parse_streamed_f64(fields[2])?)?,
+        // Parsing as i128 guarantees that the data is an integer number
+        DataType::Integer => T::from_i128(fields[2].parse::<i128>()?)?,
+        DataType::Complex => T::from_c64(Complex::<f64>::new(
+            parse_streamed_f64(fields[2])?,
+            parse_streamed_f64(fields[3])?,
+        ))?,
+        DataType::Pattern => T::from_pattern(())?,
+    };
+    // The coordinates in matrix market is one-based, but in CooMatrix is zero-based.
+    Ok((r - 1, c - 1, d))
+}
+
+/// Parses a real number field of a matrix market data line.
+fn parse_streamed_f64(field: &str) -> Result<f64, MatrixMarketError> {
+    field.parse::<f64>().map_err(|_| {
+        MatrixMarketError::from_kind_and_message(
+            MatrixMarketErrorKind::ParsingError,
+            format!("{} can't be parsed as a real number", field),
+        )
+    })
+}
+
 #[inline]
 /// do a quick check it the entry is in the lower triangle part of the matrix
 fn check_lower_triangle(r: usize, c: usize) -> Result<(), MatrixMarketError> {
@@ -1549,3 +2025,98 @@ where
     }
     Ok(())
 }
+
+/// Save a `CooMatrix` to a Matrix Market format string, optionally with the `symmetric`
+/// qualifier.
+///
+/// In contrast to [save_to_matrix_market_str], which always writes the `general` format, this
+/// writes the triplets of the COO matrix as-is - including duplicate entries - in their
+/// insertion order, so that the output is reproducible and a subsequent import reconstructs
+/// the same triplets. This makes it possible to dump a matrix during assembly without first
+/// converting to CSR or CSC.
+///
+/// If `symmetric` is `true`, the matrix is written with the `symmetric` qualifier and only
+/// the triplets on or below the diagonal are emitted; triplets in the strictly upper triangle
+/// are skipped, under the assumption that they mirror the lower triangle. It is the caller's
+/// responsibility to ensure that the matrix is actually symmetric - this is not verified.
+///
+/// Panics
+/// --------
+///
+/// Panics if `symmetric` is `true` and the matrix is not square.
+///
+/// Examples
+/// --------
+/// ```
+/// # use nalgebra_sparse::CooMatrix;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use nalgebra_sparse::io::save_coo_to_matrix_market_str;
+/// let expected_str = r#"%%matrixmarket matrix coordinate integer symmetric
+/// % matrixmarket file generated by nalgebra-sparse.
+/// 3 3 2
+/// 1 1 10
+/// 3 2 5
+/// "#;
+/// let mut matrix = CooMatrix::new(3, 3);
+/// matrix.push(0, 0, 10);
+/// matrix.push(2, 1, 5);
+/// matrix.push(1, 2, 5); // mirror of (2, 1), not written
+/// let generated_matrixmarket_str = save_coo_to_matrix_market_str(&matrix, true);
+/// assert_eq!(expected_str, generated_matrixmarket_str);
+/// # Ok(()) }
+/// ```
+pub fn save_coo_to_matrix_market_str<T>(coo: &CooMatrix<T>, symmetric: bool) -> String
+where
+    T: MatrixMarketScalar,
+{
+    use std::fmt::Write;
+
+    if symmetric {
+        assert_eq!(
+            coo.nrows(),
+            coo.ncols(),
+            "Matrix must be square to be saved as symmetric."
+        );
+    }
+
+    let mut output = String::new();
+
+    // write header
+    let qualifier = if symmetric { "symmetric" } else { "general" };
+    writeln!(
+        output,
+        "%%matrixmarket matrix coordinate {} {}",
+        T::typename(),
+        qualifier
+    )
+    .expect("Unexpected format error was generated when write to String");
+
+    //write comment
+    writeln!(output, "% matrixmarket file generated by nalgebra-sparse.")
+        .expect("Unexpected format error was generated when write to String");
+
+    // write shape information; for the symmetric format only the emitted (lower-triangle)
+    // triplets count towards the number of entries
+    let nnz = if symmetric {
+        coo.triplet_iter().filter(|(r, c, _)| r >= c).count()
+    } else {
+        coo.nnz()
+    };
+    writeln!(output, "{} {} {}", coo.nrows(), coo.ncols(), nnz)
+        .expect("Unexpected format error was generated when write to String");
+
+    //write triplets
+    let mut buffer = String::new();
+    for (r, c, d) in coo.triplet_iter() {
+        if symmetric && r < c {
+            continue;
+        }
+        buffer.clear();
+        d.write_matrix_market(&mut buffer)
+            .expect("Unexpected format error was generated when write to String");
+        writeln!(output, "{} {} {}", r + 1, c + 1, buffer)
+            .expect("Unexpected format error was generated when write to String");
+    }
+
+    output
+}
@@ -34,8 +34,12 @@
 //! > "*The Matrix Market Exchange Formats: Initial Design.*" (1996).
 
 pub use self::matrix_market::{
-    load_coo_from_matrix_market_file, load_coo_from_matrix_market_str, save_to_matrix_market,
-    save_to_matrix_market_file, save_to_matrix_market_str, MatrixMarketError,
-    MatrixMarketErrorKind, MatrixMarketExport, MatrixMarketScalar,
+    load_coo_from_matrix_market_file, load_coo_from_matrix_market_str,
+    load_dense_from_matrix_market_str, matrix_market_entries, save_coo_to_matrix_market_str,
+    save_to_matrix_market, save_to_matrix_market_file, save_to_matrix_market_str, DataType,
+    MatrixMarketEntryIter, MatrixMarketError, MatrixMarketErrorKind, MatrixMarketExport,
+    MatrixMarketHeader, MatrixMarketScalar, Sparsity, StorageScheme,
 };
+#[cfg(feature = "flate2")]
+pub use self::matrix_market::load_coo_from_matrix_market_gz;
 mod matrix_market;
@@ -156,6 +156,9 @@ pub mod factorization;
 pub mod io;
 pub mod ops;
 pub mod pattern;
+pub mod permutation;
+pub mod sparse_vector;
+pub mod sym_csr;
 
 pub(crate) mod cs;
 pub(crate) mod utils;
@@ -173,6 +176,8 @@ use std::fmt;
 pub use self::coo::CooMatrix;
 pub use self::csc::CscMatrix;
 pub use self::csr::CsrMatrix;
+pub use self::sparse_vector::SparseVector;
+pub use self::sym_csr::SymCsrMatrix;
 
 /// Errors produced by functions that expect well-formed sparse format data.
 #[derive(Debug)]
@@ -11,7 +11,7 @@ use nalgebra::base::storage::RawStorage;
 use nalgebra::constraint::{DimEq, ShapeConstraint};
 use nalgebra::{
     ClosedAdd, ClosedDiv, ClosedMul, ClosedSub, DefaultAllocator, Dim, Dynamic, Matrix, OMatrix,
-    Scalar, U1,
+    Scalar,
 };
 use num_traits::{One, Zero};
 use std::ops::{Add, Div, DivAssign, Mul, MulAssign, Neg, Sub};
@@ -116,6 +116,14 @@ impl_spmm!(CsrMatrix, spmm_csr_pattern, spmm_csr_prealloc_unchecked);
 // Need to switch order of operations for CSC pattern
 impl_spmm!(CscMatrix, spmm_csc_pattern, spmm_csc_prealloc_unchecked);
 
+// Mixed-format multiplication. The CSC operand is converted to CSR up front, after which the
+// standard CSR kernel applies; the conversion is linear in the number of entries, which is
+// cheaper than running a merge-based kernel on mixed formats.
+impl_mul!(<'a, T>(a: &'a CsrMatrix<T>, b: &'a CscMatrix<T>) -> CsrMatrix<T> { a * CsrMatrix::from(b) });
+impl_mul!(<'a, T>(a: &'a CsrMatrix<T>, b: CscMatrix<T>) -> CsrMatrix<T> { a * &b });
+impl_mul!(<'a, T>(a: CsrMatrix<T>, b: &'a CscMatrix<T>) -> CsrMatrix<T> { &a * b });
+impl_mul!(<T>(a: CsrMatrix<T>, b: CscMatrix<T>) -> CsrMatrix<T> { &a * &b });
+
 /// Implements Scalar * Matrix operations for *concrete* scalar types. The reason this is necessary
 /// is that we are not able to implement Mul<Matrix<T>> for all T generically due to orphan rules.
 macro_rules! impl_concrete_scalar_matrix_mul {
@@ -306,11 +314,11 @@ macro_rules! impl_spmm_cs_dense {
             // TODO: Is it possible to simplify these bounds?
             ShapeConstraint:
                 // Bounds so that we can turn OMatrix<T, Dynamic, C> into a DMatrixSliceMut
-                  DimEq<U1, <<DefaultAllocator as Allocator<T, Dynamic, C>>::Buffer as RawStorage<T, Dynamic, C>>::RStride>
+                  DimEq<Dynamic, <<DefaultAllocator as Allocator<T, Dynamic, C>>::Buffer as RawStorage<T, Dynamic, C>>::RStride>
                 + DimEq<C, Dynamic>
                 + DimEq<Dynamic, <<DefaultAllocator as Allocator<T, Dynamic, C>>::Buffer as RawStorage<T, Dynamic, C>>::CStride>
                 // Bounds so that we can turn &Matrix<T, R, C, S> into a DMatrixSlice
-                + DimEq<U1, S::RStride>
+                + DimEq<Dynamic, S::RStride>
                 + DimEq<R, Dynamic>
                 + DimEq<Dynamic, S::CStride>
         {
@@ -329,3 +337,63 @@ macro_rules! impl_spmm_cs_dense {
 
 impl_spmm_cs_dense!(CsrMatrix, spmm_csr_dense);
 impl_spmm_cs_dense!(CscMatrix, spmm_csc_dense);
+
+macro_rules! impl_dense_mul_cs {
+    ($matrix_type_name:ident) => {
+        // Implement ref-ref
+        impl_dense_mul_cs!(&'a Matrix<T, R, C, S>, &'a $matrix_type_name<T>, |lhs, rhs| {
+            assert_eq!(lhs.ncols(), rhs.nrows(), "D.ncols() != A.nrows()");
+            let (nrows, _) = lhs.shape_generic();
+            let ncols = Dynamic::new(rhs.ncols());
+            let mut result = OMatrix::<T, R, Dynamic>::zeros_generic(nrows, ncols);
+            // Compute C <- D * A column-by-column: each stored entry A(k, j) contributes
+            // the scaled column D[:, k] * A(k, j) to the output column C[:, j]
+            for (k, j, a_kj) in rhs.triplet_iter() {
+                for i in 0..lhs.nrows() {
+                    result[(i, j)] += lhs.index((i, k)).clone() * a_kj.clone();
+                }
+            }
+            result
+        });
+
+        // Implement the other combinations by deferring to ref-ref
+        impl_dense_mul_cs!(&'a Matrix<T, R, C, S>, $matrix_type_name<T>, |lhs, rhs| {
+            lhs * &rhs
+        });
+        impl_dense_mul_cs!(Matrix<T, R, C, S>, &'a $matrix_type_name<T>, |lhs, rhs| {
+            &lhs * rhs
+        });
+        impl_dense_mul_cs!(Matrix<T, R, C, S>, $matrix_type_name<T>, |lhs, rhs| {
+            &lhs * &rhs
+        });
+    };
+
+    // Main body of the macro. The first pattern just forwards to this pattern but with
+    // different arguments
+    ($dense_matrix_type:ty, $sparse_matrix_type:ty, |$lhs:ident, $rhs:ident| $body:tt) => {
+        impl<'a, T, R, C, S> Mul<$sparse_matrix_type> for $dense_matrix_type
+        where
+            T: Scalar + ClosedMul + ClosedAdd + Zero + One,
+            R: Dim,
+            C: Dim,
+            S: RawStorage<T, R, C>,
+            DefaultAllocator: Allocator<T, R, Dynamic>,
+        {
+            // We need the row dimension to be generic, so that if LHS is a row vector, then
+            // we also get a row vector (and not a matrix)
+            type Output = OMatrix<T, R, Dynamic>;
+
+            /// Computes the dense-sparse product `D * A` into a freshly allocated dense matrix.
+            ///
+            /// The number of columns of `D` must be equal to the number of rows of `A`.
+            fn mul(self, rhs: $sparse_matrix_type) -> Self::Output {
+                let $lhs = self;
+                let $rhs = rhs;
+                $body
+            }
+        }
+    };
+}
+
+impl_dense_mul_cs!(CsrMatrix);
+impl_dense_mul_cs!(CscMatrix);
@@ -6,8 +6,9 @@
 //! offer more control over allocation, and allow fusing some low-level operations for higher
 //! performance.
 //!
-//! The available operations are organized by backend. Currently, only the [`serial`] backend
-//! is available. In the future, backends that expose parallel operations may become available.
+//! The available operations are organized by backend. The [`serial`] backend is always
+//! available, and the `parallel` backend provides rayon-based counterparts of some kernels
+//! when the `rayon` feature is enabled.
 //! All `std::ops` implementations will remain single-threaded and powered by the
 //! `serial` backend.
 //!
@@ -45,7 +46,7 @@
 //!         <th>CSR</th>
 //!         <td></td>
 //!         <td>+ - *</td>
-//!         <td></td>
+//!         <td>*</td>
 //!         <td>*</td>
 //!     </tr>
 //!     <tr>
@@ -58,14 +59,20 @@
 //!     <tr>
 //!         <th>Dense</th>
 //!         <td></td>
-//!         <td></td>
-//!         <td></td>
+//!         <td>*</td>
+//!         <td>*</td>
 //!         <td>+ - *</td>
 //!     </tr>
 //! </table>
 //!
-//! As can be seen from the table, only `CSR * Dense` and `CSC * Dense` are supported.
-//! The other way around, i.e. `Dense * CSR` and `Dense * CSC` are not implemented.
+//! As can be seen from the table, mixed sparse-dense multiplication is supported in both
+//! directions: `CSR * Dense`, `CSC * Dense`, `Dense * CSR` and `Dense * CSC` all return a
+//! freshly allocated dense matrix. The mixed sparse-sparse product `CSR * CSC` is also
+//! supported and returns a CSR matrix.
+//!
+//! Since operators cannot return `Result`, all binary operators panic if the dimensions of
+//! the operands are incompatible. When a recoverable error is preferred, use the checked
+//! entry points instead, e.g. [`CsrMatrix::checked_mul`](`crate::csr::CsrMatrix::checked_mul`).
 //!
 //! Additionally, [CsrMatrix](`crate::csr::CsrMatrix`) and [CscMatrix](`crate::csc::CscMatrix`)
 //! support multiplication with scalars, in addition to division by a scalar.
@@ -123,8 +130,36 @@
 //! computational kernel that avoids many of the intermediate steps listed out before. Therefore
 //! directly calling kernels may sometimes lead to better performance. However, this should
 //! always be verified by performance profiling!
+//!
+//! ## Requirements for custom scalar types
+//!
+//! The operations are generic over the scalar type, and several kernels compare scalar
+//! arguments against `T::zero()` or `T::one()` with `PartialEq` in order to skip work that is
+//! algebraically redundant. For example, the dense-output kernels skip reading the prior
+//! contents of `C` entirely when `beta == T::zero()` (so that e.g. uninitialized or `NaN`
+//! data in `C` is ignored, matching BLAS conventions), and scaling by `beta` is skipped when
+//! `beta == T::one()`. The triangular solvers also compare diagonal entries against
+//! `T::zero()` to detect singularity.
+//!
+//! For the standard numeric types these fast paths are exact. Custom scalar types - interval
+//! arithmetic, dual numbers and similar wrappers - must uphold the following contract for the
+//! operations to be correct:
+//!
+//! - `T::zero()` must be the additive identity and an annihilator of multiplication, and any
+//!   value comparing equal to it via `PartialEq` must behave identically in those roles.
+//! - `T::one()` must be the multiplicative identity, and any value comparing equal to it must
+//!   behave identically.
+//!
+//! In other words, `PartialEq` must not identify values with distinguishable arithmetic
+//! behavior with the identities. A type for which two values can compare equal yet produce
+//! different results under addition or multiplication (e.g. a dual number type that compares
+//! only the real part) would silently take fast paths that change the result. If such
+//! semantics are required, the comparison must be implemented so that only true identities
+//! compare equal to `T::zero()`/`T::one()`.
 
 mod impl_std_ops;
+#[cfg(feature = "rayon")]
+pub mod parallel;
 pub mod serial;
 
 /// Determines whether a matrix should be transposed in a given operation.
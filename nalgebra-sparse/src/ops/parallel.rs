@@ -0,0 +1,102 @@
+//! Parallel sparse matrix arithmetic routines.
+//!
+//! **Available only when the `rayon` feature is enabled.**
+//!
+//! The routines in this module are parallel counterparts of the kernels in
+//! [`serial`](crate::ops::serial). Work is distributed across the rayon thread pool in
+//! contiguous row ranges balanced by the number of stored entries (see
+//! [`CsrMatrix::row_chunks_by_nnz`](crate::csr::CsrMatrix::row_chunks_by_nnz)), so that
+//! matrices with strongly varying row densities still utilize all threads. The results are
+//! bitwise identical to the corresponding serial kernels, since each row is processed by the
+//! same scalar operations in the same order.
+
+use crate::csr::CsrMatrix;
+use crate::ops::serial::{OperationError, OperationErrorKind};
+use nalgebra::{ClosedAdd, ClosedMul, Scalar};
+use num_traits::One;
+use rayon::prelude::*;
+
+fn spadd_unexpected_entry() -> OperationError {
+    OperationError::from_kind_and_message(
+        OperationErrorKind::InvalidPattern,
+        String::from("Found entry in `a` that is not present in `c`."),
+    )
+}
+
+/// Parallel sparse matrix addition `C <- beta * C + alpha * A`.
+///
+/// This is the parallel counterpart of the non-transposed branch of
+/// [`spadd_csr_prealloc`](crate::ops::serial::spadd_csr_prealloc): the sparsity pattern of
+/// `C` must be able to hold the pattern of `A`. Each row of `C` is updated independently,
+/// so no synchronization is needed; rows are distributed across threads in nnz-balanced
+/// contiguous chunks.
+///
+/// # Errors
+///
+/// If the pattern of `C` is not able to hold the entries of `A`, an error is returned.
+///
+/// # Panics
+///
+/// Panics if the dimensions of `C` and `A` are not the same.
+pub fn par_spadd_csr<T>(
+    beta: T,
+    c: &mut CsrMatrix<T>,
+    alpha: T,
+    a: &CsrMatrix<T>,
+) -> Result<(), OperationError>
+where
+    T: Scalar + ClosedAdd + ClosedMul + One + Send + Sync,
+{
+    assert_eq!(c.nrows(), a.nrows(), "C.nrows() != A.nrows()");
+    assert_eq!(c.ncols(), a.ncols(), "C.ncols() != A.ncols()");
+
+    let chunks = c.row_chunks_by_nnz(rayon::current_num_threads());
+    let (c_pattern, c_values) = c.pattern_and_values_mut();
+    let offsets = c_pattern.major_offsets();
+
+    // Split the value array of C into disjoint mutable slices along the chunk boundaries,
+    // so that each task owns the values of its row range
+    let mut work = Vec::with_capacity(chunks.len());
+    let mut tail = c_values;
+    let mut consumed = 0;
+    for range in chunks {
+        let chunk_end = offsets[range.end];
+        let (head, rest) = tail.split_at_mut(chunk_end - consumed);
+        consumed = chunk_end;
+        tail = rest;
+        work.push((range, head));
+    }
+
+    work.into_par_iter()
+        .map(|(range, chunk_values)| {
+            let mut values = chunk_values;
+            for i in range {
+                let lane_nnz = offsets[i + 1] - offsets[i];
+                let (c_vals, rest) = values.split_at_mut(lane_nnz);
+                values = rest;
+
+                if beta != T::one() {
+                    for c_ij in c_vals.iter_mut() {
+                        *c_ij *= beta.clone();
+                    }
+                }
+
+                let mut c_minors = c_pattern.lane(i);
+                let mut c_vals = c_vals;
+                let a_lane_i = a.row(i);
+                let (a_minors, a_vals) = (a_lane_i.col_indices(), a_lane_i.values());
+                for (a_col, a_val) in a_minors.iter().zip(a_vals) {
+                    let (c_idx, _) = c_minors
+                        .iter()
+                        .enumerate()
+                        .find(|(_, c_col)| *c_col == a_col)
+                        .ok_or_else(spadd_unexpected_entry)?;
+                    c_vals[c_idx] += alpha.clone() * a_val.clone();
+                    c_minors = &c_minors[c_idx..];
+                    c_vals = &mut c_vals[c_idx..];
+                }
+            }
+            Ok(())
+        })
+        .collect()
+}
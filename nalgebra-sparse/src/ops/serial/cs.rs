@@ -1,8 +1,8 @@
 use crate::cs::CsMatrix;
-use crate::ops::serial::{OperationError, OperationErrorKind};
+use crate::ops::serial::{OperationError, OperationErrorKind, SpmmStats};
 use crate::ops::Op;
 use crate::SparseEntryMut;
-use nalgebra::{ClosedAdd, ClosedMul, DMatrixSlice, DMatrixSliceMut, Scalar};
+use nalgebra::{ClosedAdd, ClosedMul, DMatrixSlice, DMatrixSliceMut, Dynamic, Scalar};
 use num_traits::{One, Zero};
 
 fn spmm_cs_unexpected_entry() -> OperationError {
@@ -20,6 +20,12 @@ fn spmm_cs_unexpected_entry() -> OperationError {
 /// reversed (since transpose(AB) = transpose(B) * transpose(A) and CSC(A) = transpose(CSR(A)).
 ///
 /// We assume here that the matrices have already been verified to be dimensionally compatible.
+///
+/// The pattern of `c` is assumed to be a superset of the pattern of the product. In contrast to
+/// [`spmm_cs_prealloc`], this is not verified at runtime in release builds, which removes the
+/// per-entry search for the output position from the hot loop. Debug builds verify the
+/// assumption and panic on violation; in release builds a violation silently produces
+/// incorrect results.
 pub fn spmm_cs_prealloc_unchecked<T>(
     beta: T,
     c: &mut CsMatrix<T>,
@@ -32,6 +38,25 @@ where
 {
     assert_eq!(c.pattern().major_dim(), a.pattern().major_dim());
     assert_eq!(c.pattern().minor_dim(), b.pattern().minor_dim());
+
+    // Verify the superset assumption in debug builds. A missing entry would leave stale
+    // values in the scratchpad below, silently corrupting subsequent lanes.
+    #[cfg(debug_assertions)]
+    for i in 0..c.pattern().major_dim() {
+        let c_lane = c.pattern().lane(i);
+        for &k in a.pattern().lane(i) {
+            for j in b.pattern().lane(k) {
+                debug_assert!(
+                    c_lane.binary_search(j).is_ok(),
+                    "Pattern of `c` is not a superset of the product pattern: \
+                     entry ({}, {}) is missing.",
+                    i,
+                    j
+                );
+            }
+        }
+    }
+
     let some_val = Zero::zero();
     let mut scratchpad_values: Vec<T> = vec![some_val; b.pattern().minor_dim()];
     for i in 0..c.pattern().major_dim() {
@@ -104,6 +129,53 @@ where
     Ok(())
 }
 
+/// Same as [`spmm_cs_prealloc`], but additionally counts the work performed by the kernel.
+///
+/// The returned [`SpmmStats`] records one multiply-add per scalar contribution to the output
+/// and one search step per comparison made while locating the output position of each
+/// contribution.
+pub fn spmm_cs_prealloc_with_stats<T>(
+    beta: T,
+    c: &mut CsMatrix<T>,
+    alpha: T,
+    a: &CsMatrix<T>,
+    b: &CsMatrix<T>,
+) -> Result<SpmmStats, OperationError>
+where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    let mut stats = SpmmStats::default();
+    for i in 0..c.pattern().major_dim() {
+        let a_lane_i = a.get_lane(i).unwrap();
+        let mut c_lane_i = c.get_lane_mut(i).unwrap();
+        for c_ij in c_lane_i.values_mut() {
+            *c_ij = beta.clone() * c_ij.clone();
+        }
+
+        for (&k, a_ik) in a_lane_i.minor_indices().iter().zip(a_lane_i.values()) {
+            let b_lane_k = b.get_lane(k).unwrap();
+            let (mut c_lane_i_cols, mut c_lane_i_values) = c_lane_i.indices_and_values_mut();
+            let alpha_aik = alpha.clone() * a_ik.clone();
+            for (j, b_kj) in b_lane_k.minor_indices().iter().zip(b_lane_k.values()) {
+                // Determine the location in C to append the value
+                let (c_local_idx, _) = c_lane_i_cols
+                    .iter()
+                    .enumerate()
+                    .find(|(_, c_col)| *c_col == j)
+                    .ok_or_else(spmm_cs_unexpected_entry)?;
+                stats.search_steps += c_local_idx + 1;
+                stats.multiply_adds += 1;
+
+                c_lane_i_values[c_local_idx] += alpha_aik.clone() * b_kj.clone();
+                c_lane_i_cols = &c_lane_i_cols[c_local_idx..];
+                c_lane_i_values = &mut c_lane_i_values[c_local_idx..];
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
 fn spadd_cs_unexpected_entry() -> OperationError {
     OperationError::from_kind_and_message(
         OperationErrorKind::InvalidPattern,
@@ -112,6 +184,10 @@ fn spadd_cs_unexpected_entry() -> OperationError {
 }
 
 /// Helper functionality for implementing CSR/CSC SPADD.
+///
+/// Following BLAS semantics, `beta == 0` is special-cased to overwrite the entries of `c`
+/// without reading their prior contents, so that e.g. a NaN-filled `c` does not propagate
+/// into the result.
 pub fn spadd_cs_prealloc<T>(
     beta: T,
     c: &mut CsMatrix<T>,
@@ -123,8 +199,31 @@ where
 {
     match a {
         Op::NoOp(a) => {
+            // When the patterns are structurally equal, the value arrays are aligned
+            // entry-by-entry, so we can zip them directly instead of searching for the
+            // position of each entry of `a` in `c`. This situation is common: equal but
+            // distinct patterns arise e.g. from deserialization or repeated assembly.
+            // The pointer comparison short-circuits the structural one for matrices that
+            // share a pattern allocation, e.g. through `PatternInterner`.
+            if std::ptr::eq(c.pattern(), a.pattern()) || c.pattern() == a.pattern() {
+                if beta == T::zero() {
+                    for (c_ij, a_ij) in c.values_mut().iter_mut().zip(a.values()) {
+                        *c_ij = alpha.clone() * a_ij.clone();
+                    }
+                } else {
+                    for (c_ij, a_ij) in c.values_mut().iter_mut().zip(a.values()) {
+                        *c_ij = beta.clone() * c_ij.clone() + alpha.clone() * a_ij.clone();
+                    }
+                }
+                return Ok(());
+            }
+
             for (mut c_lane_i, a_lane_i) in c.lane_iter_mut().zip(a.lane_iter()) {
-                if beta != T::one() {
+                if beta == T::zero() {
+                    for c_ij in c_lane_i.values_mut() {
+                        *c_ij = T::zero();
+                    }
+                } else if beta != T::one() {
                     for c_ij in c_lane_i.values_mut() {
                         *c_ij *= beta.clone();
                     }
@@ -149,7 +248,11 @@ where
             }
         }
         Op::Transpose(a) => {
-            if beta != T::one() {
+            if beta == T::zero() {
+                for c_ij in c.values_mut() {
+                    *c_ij = T::zero();
+                }
+            } else if beta != T::one() {
                 for c_ij in c.values_mut() {
                     *c_ij *= beta.clone();
                 }
@@ -174,12 +277,15 @@ where
 ///
 /// The implementation essentially assumes that `a` is a CSR matrix. To use it with CSC matrices,
 /// the transposed operation must be specified for the CSC matrix.
+///
+/// Following BLAS semantics, `beta == 0` is special-cased to overwrite `c` without reading its
+/// prior contents, so that e.g. a NaN-filled `c` does not propagate into the result.
 pub fn spmm_cs_dense<T>(
     beta: T,
-    mut c: DMatrixSliceMut<'_, T>,
+    mut c: DMatrixSliceMut<'_, T, Dynamic, Dynamic>,
     alpha: T,
     a: Op<&CsMatrix<T>>,
-    b: Op<DMatrixSlice<'_, T>>,
+    b: Op<DMatrixSlice<'_, T, Dynamic, Dynamic>>,
 ) where
     T: Scalar + ClosedAdd + ClosedMul + Zero + One,
 {
@@ -196,13 +302,21 @@ pub fn spmm_cs_dense<T>(
                         };
                         dot_ij += a_ik.clone() * b_contrib.clone();
                     }
-                    *c_ij = beta.clone() * c_ij.clone() + alpha.clone() * dot_ij;
+                    *c_ij = if beta == T::zero() {
+                        alpha.clone() * dot_ij
+                    } else {
+                        beta.clone() * c_ij.clone() + alpha.clone() * dot_ij
+                    };
                 }
             }
         }
         Op::Transpose(a) => {
             // In this case, we have to pre-multiply C by beta
-            c *= beta;
+            if beta == T::zero() {
+                c.fill(T::zero());
+            } else {
+                c *= beta;
+            }
 
             for k in 0..a.pattern().major_dim() {
                 let a_row_k = a.get_lane(k).unwrap();
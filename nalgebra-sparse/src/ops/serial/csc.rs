@@ -4,7 +4,7 @@ use crate::ops::serial::cs::{
 };
 use crate::ops::serial::{OperationError, OperationErrorKind};
 use crate::ops::Op;
-use nalgebra::{ClosedAdd, ClosedMul, DMatrixSlice, DMatrixSliceMut, RealField, Scalar};
+use nalgebra::{ClosedAdd, ClosedMul, DMatrixSlice, DMatrixSliceMut, Dynamic, RealField, Scalar};
 use num_traits::{One, Zero};
 
 use std::borrow::Cow;
@@ -16,10 +16,10 @@ use std::borrow::Cow;
 /// Panics if the dimensions of the matrices involved are not compatible with the expression.
 pub fn spmm_csc_dense<'a, T>(
     beta: T,
-    c: impl Into<DMatrixSliceMut<'a, T>>,
+    c: impl Into<DMatrixSliceMut<'a, T, Dynamic, Dynamic>>,
     alpha: T,
     a: Op<&CscMatrix<T>>,
-    b: Op<impl Into<DMatrixSlice<'a, T>>>,
+    b: Op<impl Into<DMatrixSlice<'a, T, Dynamic, Dynamic>>>,
 ) where
     T: Scalar + ClosedAdd + ClosedMul + Zero + One,
 {
@@ -29,10 +29,10 @@ pub fn spmm_csc_dense<'a, T>(
 
 fn spmm_csc_dense_<T>(
     beta: T,
-    c: DMatrixSliceMut<'_, T>,
+    c: DMatrixSliceMut<'_, T, Dynamic, Dynamic>,
     alpha: T,
     a: Op<&CscMatrix<T>>,
-    b: Op<DMatrixSlice<'_, T>>,
+    b: Op<DMatrixSlice<'_, T, Dynamic, Dynamic>>,
 ) where
     T: Scalar + ClosedAdd + ClosedMul + Zero + One,
 {
@@ -44,6 +44,10 @@ fn spmm_csc_dense_<T>(
 
 /// Sparse matrix addition `C <- beta * C + alpha * op(A)`.
 ///
+/// Following BLAS semantics, `beta == 0` is special-cased to overwrite the entries of `C`
+/// without reading their prior contents, so that e.g. a NaN-filled `C` does not propagate
+/// into the result.
+///
 /// If the pattern of `c` does not accommodate all the non-zero entries in `a`, an error is
 /// returned.
 ///
@@ -98,7 +102,10 @@ where
 
 /// Faster sparse-sparse matrix multiplication, `C <- beta * C + alpha * op(A) * op(B)`.
 /// This will not return an error even if the patterns don't match.
-/// Should be used for situations where pattern creation immediately preceeds multiplication.
+/// Should be used for situations where pattern creation immediately preceeds multiplication,
+/// or where `C` accumulates the result of several products over a pattern known to be a
+/// superset of each product pattern. Debug builds verify the superset assumption and panic on
+/// violation.
 ///
 /// Panics if the dimensions of the matrices involved are not compatible with the expression.
 pub fn spmm_csc_prealloc_unchecked<T>(
@@ -1,20 +1,27 @@
 use crate::csr::CsrMatrix;
 use crate::ops::serial::cs::{
     spadd_cs_prealloc, spmm_cs_dense, spmm_cs_prealloc, spmm_cs_prealloc_unchecked,
+    spmm_cs_prealloc_with_stats,
 };
-use crate::ops::serial::OperationError;
+use crate::ops::serial::pattern::{spadd_pattern, spmm_csr_pattern};
+use crate::ops::serial::{OperationError, OperationErrorKind, SpmmStats};
+use crate::pattern::SparsityPattern;
 use crate::ops::Op;
-use nalgebra::{ClosedAdd, ClosedMul, DMatrixSlice, DMatrixSliceMut, Scalar};
+use nalgebra::{ClosedAdd, ClosedMul, DMatrixSlice, DMatrixSliceMut, DVectorSlice, DVectorSliceMut, Dynamic, Scalar};
 use num_traits::{One, Zero};
 use std::borrow::Cow;
 
 /// Sparse-dense matrix-matrix multiplication `C <- beta * C + alpha * op(A) * op(B)`.
+///
+/// The dense arguments are accepted as slices with arbitrary row and column strides; all
+/// accesses go through nalgebra's stride-aware indexing, so views into row-major or padded
+/// buffers (e.g. slices handed out by external libraries) work without copying.
 pub fn spmm_csr_dense<'a, T>(
     beta: T,
-    c: impl Into<DMatrixSliceMut<'a, T>>,
+    c: impl Into<DMatrixSliceMut<'a, T, Dynamic, Dynamic>>,
     alpha: T,
     a: Op<&CsrMatrix<T>>,
-    b: Op<impl Into<DMatrixSlice<'a, T>>>,
+    b: Op<impl Into<DMatrixSlice<'a, T, Dynamic, Dynamic>>>,
 ) where
     T: Scalar + ClosedAdd + ClosedMul + Zero + One,
 {
@@ -24,10 +31,10 @@ pub fn spmm_csr_dense<'a, T>(
 
 fn spmm_csr_dense_<T>(
     beta: T,
-    c: DMatrixSliceMut<'_, T>,
+    c: DMatrixSliceMut<'_, T, Dynamic, Dynamic>,
     alpha: T,
     a: Op<&CsrMatrix<T>>,
-    b: Op<DMatrixSlice<'_, T>>,
+    b: Op<DMatrixSlice<'_, T, Dynamic, Dynamic>>,
 ) where
     T: Scalar + ClosedAdd + ClosedMul + Zero + One,
 {
@@ -35,8 +42,228 @@ fn spmm_csr_dense_<T>(
     spmm_cs_dense(beta, c, alpha, a.map_same_op(|a| &a.cs), b)
 }
 
+/// The number of right-hand-side columns processed together by [`spmm_csr_dense_blocked`].
+///
+/// The tile must be small enough that a tile of `B` columns together with the accumulators
+/// stays cache-resident while a row of `A` is traversed.
+const SPMM_COLUMN_BLOCK: usize = 8;
+
+/// Cache-blocked sparse-dense matrix-matrix multiplication `C <- beta * C + alpha * A * B`.
+///
+/// Computes the same result as [`spmm_csr_dense`] with non-transposed arguments, but with a
+/// different loop order: the rows of `A` are traversed in the outer loop while a small tile of
+/// columns of `B` is processed in the inner loop. [`spmm_csr_dense`] instead traverses the
+/// sparsity structure of `A` once per column of `C`, so when `C` has many columns — i.e. when
+/// multiplying against many right-hand-side vectors at once — the blocked variant makes far
+/// fewer passes over the index data of `A` and benefits from correspondingly better cache
+/// reuse.
+///
+/// # Panics
+///
+/// Panics if the dimensions of the matrices involved are not compatible with the expression.
+pub fn spmm_csr_dense_blocked<'a, T>(
+    beta: T,
+    c: impl Into<DMatrixSliceMut<'a, T, Dynamic, Dynamic>>,
+    alpha: T,
+    a: &CsrMatrix<T>,
+    b: impl Into<DMatrixSlice<'a, T, Dynamic, Dynamic>>,
+) where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    spmm_csr_dense_blocked_(beta, c.into(), alpha, a, b.into())
+}
+
+fn spmm_csr_dense_blocked_<T>(
+    beta: T,
+    mut c: DMatrixSliceMut<'_, T, Dynamic, Dynamic>,
+    alpha: T,
+    a: &CsrMatrix<T>,
+    b: DMatrixSlice<'_, T, Dynamic, Dynamic>,
+) where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    assert_eq!(
+        c.nrows(),
+        a.nrows(),
+        "C.nrows() must be equal to A.nrows()."
+    );
+    assert_eq!(
+        c.ncols(),
+        b.ncols(),
+        "C.ncols() must be equal to B.ncols()."
+    );
+    assert_eq!(
+        a.ncols(),
+        b.nrows(),
+        "A.ncols() must be equal to B.nrows()."
+    );
+
+    let mut accumulators = vec![T::zero(); SPMM_COLUMN_BLOCK];
+    for block_start in (0..c.ncols()).step_by(SPMM_COLUMN_BLOCK) {
+        let block_width = SPMM_COLUMN_BLOCK.min(c.ncols() - block_start);
+        for (i, a_row_i) in a.row_iter().enumerate() {
+            for acc in &mut accumulators[..block_width] {
+                *acc = T::zero();
+            }
+            // A single pass over the entries of row i updates all columns in the tile
+            for (&k, a_ik) in a_row_i.col_indices().iter().zip(a_row_i.values()) {
+                for (t, acc) in accumulators[..block_width].iter_mut().enumerate() {
+                    *acc += a_ik.clone() * b[(k, block_start + t)].clone();
+                }
+            }
+            for (t, acc) in accumulators[..block_width].iter().enumerate() {
+                let c_ij = &mut c[(i, block_start + t)];
+                *c_ij = if beta == T::zero() {
+                    alpha.clone() * acc.clone()
+                } else {
+                    beta.clone() * c_ij.clone() + alpha.clone() * acc.clone()
+                };
+            }
+        }
+    }
+}
+
+/// Sparse matrix-vector multiplication `y <- beta * y + alpha * A * x`.
+///
+/// Each entry `y[i]` is computed as the sparse dot product of row `i` of `A` with the dense
+/// vector `x`. See also [`CsrMatrix::row_dot`](crate::csr::CsrMatrix::row_dot) for probing the
+/// contribution of a single row.
+///
+/// With the `simd-accel` feature enabled, the accumulation is explicitly vectorized for `f32`
+/// and `f64` scalars, which can give a substantial speedup for matrices with many non-zeros
+/// per row. Other scalar types always use the generic scalar loop.
+///
+/// # Panics
+///
+/// Panics if the dimensions of the vectors and the matrix are not compatible with the expression.
+pub fn spmv_csr<'a, T>(
+    beta: T,
+    y: impl Into<DVectorSliceMut<'a, T>>,
+    alpha: T,
+    a: &CsrMatrix<T>,
+    x: impl Into<DVectorSlice<'a, T>>,
+) where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    spmv_csr_(beta, y.into(), alpha, a, x.into())
+}
+
+fn spmv_csr_<T>(
+    beta: T,
+    mut y: DVectorSliceMut<'_, T>,
+    alpha: T,
+    a: &CsrMatrix<T>,
+    x: DVectorSlice<'_, T>,
+) where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    assert_eq!(y.nrows(), a.nrows(), "y.nrows() != A.nrows()");
+    assert_eq!(a.ncols(), x.nrows(), "A.ncols() != x.nrows()");
+
+    for (y_i, a_row_i) in y.iter_mut().zip(a.row_iter()) {
+        let dot_i = spmv_sparse_dot(a_row_i.col_indices(), a_row_i.values(), &x);
+        *y_i = beta.clone() * y_i.clone() + alpha.clone() * dot_i;
+    }
+}
+
+/// Computes the sparse dot product between a sparse row given by its column indices and values,
+/// and a dense vector.
+///
+/// With the `simd-accel` feature enabled, `f32` and `f64` scalars are dispatched to explicitly
+/// vectorized kernels; all other scalar types use the generic scalar loop.
+fn spmv_sparse_dot<T>(cols: &[usize], vals: &[T], x: &DVectorSlice<'_, T>) -> T
+where
+    T: Scalar + ClosedAdd + ClosedMul + Zero,
+{
+    #[cfg(feature = "simd-accel")]
+    {
+        use std::any::TypeId;
+        // `Scalar` requires `'static`, so we can recover the concrete scalar type at runtime.
+        // The pointer casts below are sound because the `TypeId` checks guarantee that `T` is
+        // exactly the primitive type we reinterpret it as.
+        if TypeId::of::<T>() == TypeId::of::<f64>() {
+            let vals_f64 =
+                unsafe { std::slice::from_raw_parts(vals.as_ptr() as *const f64, vals.len()) };
+            let dot = simd_dot_f64(cols, vals_f64, |j| unsafe {
+                *(&x[j] as *const T as *const f64)
+            });
+            return unsafe { std::ptr::read(&dot as *const f64 as *const T) };
+        } else if TypeId::of::<T>() == TypeId::of::<f32>() {
+            let vals_f32 =
+                unsafe { std::slice::from_raw_parts(vals.as_ptr() as *const f32, vals.len()) };
+            let dot = simd_dot_f32(cols, vals_f32, |j| unsafe {
+                *(&x[j] as *const T as *const f32)
+            });
+            return unsafe { std::ptr::read(&dot as *const f32 as *const T) };
+        }
+    }
+
+    let mut dot = T::zero();
+    for (&j, a_ij) in cols.iter().zip(vals) {
+        dot += a_ij.clone() * x[j].clone();
+    }
+    dot
+}
+
+/// Accumulates the sparse dot product four lanes at a time with fused multiply-add,
+/// gathering the dense operand through `x`.
+#[cfg(feature = "simd-accel")]
+fn simd_dot_f64(cols: &[usize], vals: &[f64], x: impl Fn(usize) -> f64) -> f64 {
+    use wide::f64x4;
+
+    let mut acc = f64x4::ZERO;
+    let col_chunks = cols.chunks_exact(4);
+    let val_chunks = vals.chunks_exact(4);
+    let col_rem = col_chunks.remainder();
+    let val_rem = val_chunks.remainder();
+    for (c, v) in col_chunks.zip(val_chunks) {
+        let gathered = f64x4::from([x(c[0]), x(c[1]), x(c[2]), x(c[3])]);
+        acc = f64x4::from([v[0], v[1], v[2], v[3]]).mul_add(gathered, acc);
+    }
+    let mut dot = acc.reduce_add();
+    for (&j, &v) in col_rem.iter().zip(val_rem) {
+        dot += v * x(j);
+    }
+    dot
+}
+
+/// Accumulates the sparse dot product eight lanes at a time with fused multiply-add,
+/// gathering the dense operand through `x`.
+#[cfg(feature = "simd-accel")]
+fn simd_dot_f32(cols: &[usize], vals: &[f32], x: impl Fn(usize) -> f32) -> f32 {
+    use wide::f32x8;
+
+    let mut acc = f32x8::ZERO;
+    let col_chunks = cols.chunks_exact(8);
+    let val_chunks = vals.chunks_exact(8);
+    let col_rem = col_chunks.remainder();
+    let val_rem = val_chunks.remainder();
+    for (c, v) in col_chunks.zip(val_chunks) {
+        let gathered = f32x8::from([
+            x(c[0]),
+            x(c[1]),
+            x(c[2]),
+            x(c[3]),
+            x(c[4]),
+            x(c[5]),
+            x(c[6]),
+            x(c[7]),
+        ]);
+        acc = f32x8::from([v[0], v[1], v[2], v[3], v[4], v[5], v[6], v[7]]).mul_add(gathered, acc);
+    }
+    let mut dot = acc.reduce_add();
+    for (&j, &v) in col_rem.iter().zip(val_rem) {
+        dot += v * x(j);
+    }
+    dot
+}
+
 /// Sparse matrix addition `C <- beta * C + alpha * op(A)`.
 ///
+/// Following BLAS semantics, `beta == 0` is special-cased to overwrite the entries of `C`
+/// without reading their prior contents, so that e.g. a NaN-filled `C` does not propagate
+/// into the result.
+///
 /// # Errors
 ///
 /// If the pattern of `c` does not accommodate all the non-zero entries in `a`, an error is
@@ -58,6 +285,54 @@ where
     spadd_cs_prealloc(beta, &mut c.cs, alpha, a.map_same_op(|a| &a.cs))
 }
 
+/// Sparse matrix linear combination `C = alpha_a * A + alpha_b * B`, producing a new matrix.
+///
+/// Unlike [`spadd_csr_prealloc`], the operands do not need to share a sparsity pattern and no
+/// preallocated result matrix is required: the pattern of `C` is the union of the patterns of
+/// `A` and `B`, computed as part of the operation. This is the general linear-combination
+/// primitive; when the same combination is performed repeatedly with unchanged patterns,
+/// computing the union pattern once and using [`spadd_csr_prealloc`] is more efficient.
+///
+/// # Errors
+///
+/// Returns an error of kind
+/// [`IncompatibleDimensions`](OperationErrorKind::IncompatibleDimensions) if `A` and `B` do
+/// not have the same dimensions.
+pub fn spadd_csr_into_new<T>(
+    alpha_a: T,
+    a: &CsrMatrix<T>,
+    alpha_b: T,
+    b: &CsrMatrix<T>,
+) -> Result<CsrMatrix<T>, OperationError>
+where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    if (a.nrows(), a.ncols()) != (b.nrows(), b.ncols()) {
+        return Err(incompatible_dims_error(
+            a.nrows(),
+            b.nrows(),
+            format!(
+                "A and B must have the same dimensions (A: {}x{}, B: {}x{}).",
+                a.nrows(),
+                a.ncols(),
+                b.nrows(),
+                b.ncols()
+            ),
+        ));
+    }
+
+    let pattern = spadd_pattern(a.pattern(), b.pattern());
+    let values = vec![T::zero(); pattern.nnz()];
+    let mut c = CsrMatrix::try_from_pattern_and_values(pattern, values)
+        .expect("Internal error: Pattern and values must be compatible by construction");
+
+    spadd_csr_prealloc(T::one(), &mut c, alpha_a, Op::NoOp(a))
+        .expect("Internal error: The union pattern must accommodate all entries of A");
+    spadd_csr_prealloc(T::one(), &mut c, alpha_b, Op::NoOp(b))
+        .expect("Internal error: The union pattern must accommodate all entries of B");
+    Ok(c)
+}
+
 /// Sparse-sparse matrix multiplication, `C <- beta * C + alpha * op(A) * op(B)`.
 ///
 /// # Errors
@@ -87,9 +362,60 @@ where
     }
 }
 
+/// Same as [`spmm_csr_prealloc`], but additionally returns runtime-measured work counters.
+///
+/// This computes exactly the same result with the same entry-placement loop, and records the
+/// number of scalar multiply-adds and linear-search comparison steps actually performed. See
+/// [`SpmmStats`] for how to interpret the counters. The default API is unaffected; use this
+/// variant only when profiling.
+///
+/// # Errors
+///
+/// If the pattern of `C` is not able to hold the result of the operation, an error is returned.
+///
+/// # Panics
+///
+/// Panics if the dimensions of the matrices involved are not compatible with the expression.
+pub fn spmm_csr_prealloc_with_stats<T>(
+    beta: T,
+    c: &mut CsrMatrix<T>,
+    alpha: T,
+    a: Op<&CsrMatrix<T>>,
+    b: Op<&CsrMatrix<T>>,
+) -> Result<SpmmStats, OperationError>
+where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    assert_compatible_spmm_dims!(c, a, b);
+
+    use Op::{NoOp, Transpose};
+
+    match (&a, &b) {
+        (NoOp(ref a), NoOp(ref b)) => {
+            spmm_cs_prealloc_with_stats(beta, &mut c.cs, alpha, &a.cs, &b.cs)
+        }
+        _ => {
+            // Transposition is handled as in the other spmm kernels, by explicitly
+            // precomputing transposed matrices and multiplying without transposition
+            let a = match a {
+                NoOp(a) => Cow::Borrowed(a),
+                Transpose(a) => Cow::Owned(a.transpose()),
+            };
+            let b = match b {
+                NoOp(b) => Cow::Borrowed(b),
+                Transpose(b) => Cow::Owned(b.transpose()),
+            };
+            spmm_cs_prealloc_with_stats(beta, &mut c.cs, alpha, &a.cs, &b.cs)
+        }
+    }
+}
+
 /// Faster sparse-sparse matrix multiplication, `C <- beta * C + alpha * op(A) * op(B)`.
 /// This will not return an error even if the patterns don't match.
-/// Should be used for situations where pattern creation immediately preceeds multiplication.
+/// Should be used for situations where pattern creation immediately preceeds multiplication,
+/// or where `C` accumulates the result of several products over a pattern known to be a
+/// superset of each product pattern. Debug builds verify the superset assumption and panic on
+/// violation.
 ///
 /// Panics if the dimensions of the matrices involved are not compatible with the expression.
 pub fn spmm_csr_prealloc_unchecked<T>(
@@ -114,6 +440,86 @@ where
     }
 }
 
+/// Masked sparse-sparse matrix multiplication, `C<M> = alpha * op(A) * op(B)`.
+///
+/// The product is computed only at the positions present in the `mask` pattern, and all other
+/// contributions are discarded. The result has exactly the pattern of `mask`, where positions
+/// not reached by the product are explicit zeros. This is a GraphBLAS-style primitive which is
+/// far cheaper than a full product followed by filtering when the mask is sparse.
+///
+/// # Errors
+///
+/// Currently this operation does not fail, but the `Result` return type anticipates errors in
+/// line with the other sparse-sparse multiplication kernels.
+///
+/// # Panics
+///
+/// Panics if the dimensions of the mask and the matrices are not compatible with the
+/// expression.
+pub fn spmm_csr_masked<T>(
+    mask: &SparsityPattern,
+    alpha: T,
+    a: Op<&CsrMatrix<T>>,
+    b: Op<&CsrMatrix<T>>,
+) -> Result<CsrMatrix<T>, OperationError>
+where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    use Op::{NoOp, Transpose};
+
+    // As in the other kernels, transposition is handled by explicitly precomputing the
+    // transposed matrices
+    let (a, b) = {
+        use Cow::*;
+        match (&a, &b) {
+            (NoOp(a), NoOp(b)) => (Borrowed(*a), Borrowed(*b)),
+            (Transpose(a), NoOp(b)) => (Owned(a.transpose()), Borrowed(*b)),
+            (NoOp(a), Transpose(b)) => (Borrowed(*a), Owned(b.transpose())),
+            (Transpose(a), Transpose(b)) => (Owned(a.transpose()), Owned(b.transpose())),
+        }
+    };
+    let (a, b) = (a.as_ref(), b.as_ref());
+
+    assert_eq!(mask.major_dim(), a.nrows(), "M.nrows() != A.nrows()");
+    assert_eq!(a.ncols(), b.nrows(), "A.ncols() != B.nrows()");
+    assert_eq!(mask.minor_dim(), b.ncols(), "M.ncols() != B.ncols()");
+
+    let mut values = vec![T::zero(); mask.nnz()];
+    // Maps a column index to its local position within the current mask row, with
+    // usize::MAX marking columns absent from the mask
+    let mut local_pos = vec![usize::MAX; mask.minor_dim()];
+    let offsets = mask.major_offsets();
+    let indices = mask.minor_indices();
+
+    for i in 0..mask.major_dim() {
+        let range = offsets[i]..offsets[i + 1];
+        let mask_cols = &indices[range.clone()];
+        let row_vals = &mut values[range];
+        for (local, &j) in mask_cols.iter().enumerate() {
+            local_pos[j] = local;
+        }
+
+        let a_row_i = a.row(i);
+        for (&k, a_ik) in a_row_i.col_indices().iter().zip(a_row_i.values()) {
+            let b_row_k = b.row(k);
+            let alpha_aik = alpha.clone() * a_ik.clone();
+            for (&j, b_kj) in b_row_k.col_indices().iter().zip(b_row_k.values()) {
+                let local = local_pos[j];
+                if local != usize::MAX {
+                    row_vals[local] += alpha_aik.clone() * b_kj.clone();
+                }
+            }
+        }
+
+        for &j in mask_cols {
+            local_pos[j] = usize::MAX;
+        }
+    }
+
+    Ok(CsrMatrix::try_from_pattern_and_values(mask.clone(), values)
+        .expect("Internal error: Mask pattern and values must be compatible by construction"))
+}
+
 fn spmm_csr_transposed<T, F>(
     beta: T,
     c: &mut CsrMatrix<T>,
@@ -149,3 +555,233 @@ where
     };
     spmm_kernel(beta, c, alpha, NoOp(a.as_ref()), NoOp(b.as_ref()))
 }
+
+/// Constructs an `IncompatibleDimensions` error from the two disagreeing dimensions and a
+/// message recording the actual shapes.
+fn incompatible_dims_error(lhs: usize, rhs: usize, message: String) -> OperationError {
+    OperationError::from_kind_and_message(
+        OperationErrorKind::IncompatibleDimensions {
+            a_cols: lhs,
+            b_rows: rhs,
+        },
+        message,
+    )
+}
+
+/// The shape of `op(A)`, i.e. the shape of `A` with the dimensions swapped for the transposed
+/// operation.
+fn spmm_op_shape<T>(op: &Op<&CsrMatrix<T>>) -> (usize, usize) {
+    match op {
+        Op::NoOp(a) => (a.nrows(), a.ncols()),
+        Op::Transpose(a) => (a.ncols(), a.nrows()),
+    }
+}
+
+fn check_spmm_dims(
+    c_shape: (usize, usize),
+    a_shape: (usize, usize),
+    b_shape: (usize, usize),
+) -> Result<(), OperationError> {
+    if c_shape.0 != a_shape.0 {
+        return Err(incompatible_dims_error(
+            c_shape.0,
+            a_shape.0,
+            format!(
+                "C.nrows() != op(A).nrows() (C: {}x{}, op(A): {}x{}).",
+                c_shape.0, c_shape.1, a_shape.0, a_shape.1
+            ),
+        ));
+    }
+    if c_shape.1 != b_shape.1 {
+        return Err(incompatible_dims_error(
+            c_shape.1,
+            b_shape.1,
+            format!(
+                "C.ncols() != op(B).ncols() (C: {}x{}, op(B): {}x{}).",
+                c_shape.0, c_shape.1, b_shape.0, b_shape.1
+            ),
+        ));
+    }
+    if a_shape.1 != b_shape.0 {
+        return Err(incompatible_dims_error(
+            a_shape.1,
+            b_shape.0,
+            format!(
+                "op(A).ncols() != op(B).nrows() (op(A): {}x{}, op(B): {}x{}).",
+                a_shape.0, a_shape.1, b_shape.0, b_shape.1
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Sparse-sparse matrix multiplication `C <- beta * C + alpha * op(A) * op(B)`, returning an
+/// error instead of panicking when the dimensions are incompatible.
+///
+/// This is the non-panicking counterpart to [`spmm_csr_prealloc`], intended for validating
+/// untrusted inputs uniformly through `Result`. The panicking version avoids the error
+/// construction and remains preferable when the dimensions are known to be consistent.
+///
+/// # Errors
+///
+/// Returns an error with kind
+/// [`IncompatibleDimensions`](OperationErrorKind::IncompatibleDimensions) if the dimensions
+/// of the matrices are not compatible with the expression, with the actual shapes recorded
+/// in the error message. Otherwise errors as [`spmm_csr_prealloc`].
+pub fn try_spmm_csr_prealloc<T>(
+    beta: T,
+    c: &mut CsrMatrix<T>,
+    alpha: T,
+    a: Op<&CsrMatrix<T>>,
+    b: Op<&CsrMatrix<T>>,
+) -> Result<(), OperationError>
+where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    check_spmm_dims(
+        (c.nrows(), c.ncols()),
+        spmm_op_shape(&a),
+        spmm_op_shape(&b),
+    )?;
+    spmm_csr_prealloc(beta, c, alpha, a, b)
+}
+
+/// Sparse matrix addition `C <- beta * C + alpha * op(A)`, returning an error instead of
+/// panicking when the dimensions are incompatible.
+///
+/// This is the non-panicking counterpart to [`spadd_csr_prealloc`]; see
+/// [`try_spmm_csr_prealloc`] for the rationale.
+///
+/// # Errors
+///
+/// Returns an error with kind
+/// [`IncompatibleDimensions`](OperationErrorKind::IncompatibleDimensions) if the dimensions
+/// of `c` and `op(a)` differ, with the actual shapes recorded in the error message.
+/// Otherwise errors as [`spadd_csr_prealloc`].
+pub fn try_spadd_csr_prealloc<T>(
+    beta: T,
+    c: &mut CsrMatrix<T>,
+    alpha: T,
+    a: Op<&CsrMatrix<T>>,
+) -> Result<(), OperationError>
+where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    let a_shape = spmm_op_shape(&a);
+    if (c.nrows(), c.ncols()) != a_shape {
+        return Err(incompatible_dims_error(
+            c.nrows(),
+            a_shape.0,
+            format!(
+                "C and op(A) must have the same dimensions (C: {}x{}, op(A): {}x{}).",
+                c.nrows(),
+                c.ncols(),
+                a_shape.0,
+                a_shape.1
+            ),
+        ));
+    }
+    spadd_csr_prealloc(beta, c, alpha, a)
+}
+
+/// Sparse-dense matrix-matrix multiplication `C <- beta * C + alpha * op(A) * op(B)`,
+/// returning an error instead of panicking when the dimensions are incompatible.
+///
+/// This is the non-panicking counterpart to [`spmm_csr_dense`]; see
+/// [`try_spmm_csr_prealloc`] for the rationale.
+///
+/// # Errors
+///
+/// Returns an error with kind
+/// [`IncompatibleDimensions`](OperationErrorKind::IncompatibleDimensions) if the dimensions
+/// of the matrices are not compatible with the expression, with the actual shapes recorded
+/// in the error message.
+pub fn try_spmm_csr_dense<'a, T>(
+    beta: T,
+    c: impl Into<DMatrixSliceMut<'a, T, Dynamic, Dynamic>>,
+    alpha: T,
+    a: Op<&CsrMatrix<T>>,
+    b: Op<impl Into<DMatrixSlice<'a, T, Dynamic, Dynamic>>>,
+) -> Result<(), OperationError>
+where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    let c = c.into();
+    let b = b.convert();
+    let b_shape = match &b {
+        Op::NoOp(b) => (b.nrows(), b.ncols()),
+        Op::Transpose(b) => (b.ncols(), b.nrows()),
+    };
+    check_spmm_dims((c.nrows(), c.ncols()), spmm_op_shape(&a), b_shape)?;
+    spmm_csr_dense(beta, c, alpha, a, b);
+    Ok(())
+}
+
+/// Computes the Galerkin triple product `R * A * P`.
+///
+/// This is the coarsening operation of multigrid methods, where `P` is the prolongation
+/// operator, `A` the fine-grid matrix and `R` the restriction operator, which is typically
+/// `P^T`. The product is computed as `(R * (A * P))` with a single preallocated intermediate
+/// for `A * P`, avoiding the duplicate pattern computations and allocations incurred by
+/// chaining two general `spmm` calls through the multiplication operators.
+///
+/// The dimensions must satisfy `r.ncols() == a.nrows()` and `a.ncols() == p.nrows()`; the
+/// result has dimensions `r.nrows() x p.ncols()`.
+///
+/// # Errors
+///
+/// Returns an error with kind
+/// [`IncompatibleDimensions`](OperationErrorKind::IncompatibleDimensions) if the dimensions
+/// of the matrices are not compatible with the product, with the actual shapes recorded in
+/// the error message.
+pub fn galerkin_product<T>(
+    r: &CsrMatrix<T>,
+    a: &CsrMatrix<T>,
+    p: &CsrMatrix<T>,
+) -> Result<CsrMatrix<T>, OperationError>
+where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    if r.ncols() != a.nrows() {
+        return Err(incompatible_dims_error(
+            r.ncols(),
+            a.nrows(),
+            format!(
+                "R.ncols() != A.nrows() (R: {}x{}, A: {}x{}).",
+                r.nrows(),
+                r.ncols(),
+                a.nrows(),
+                a.ncols()
+            ),
+        ));
+    }
+    if a.ncols() != p.nrows() {
+        return Err(incompatible_dims_error(
+            a.ncols(),
+            p.nrows(),
+            format!(
+                "A.ncols() != P.nrows() (A: {}x{}, P: {}x{}).",
+                a.nrows(),
+                a.ncols(),
+                p.nrows(),
+                p.ncols()
+            ),
+        ));
+    }
+
+    let ap_pattern = spmm_csr_pattern(a.pattern(), p.pattern());
+    let ap_values = vec![T::zero(); ap_pattern.nnz()];
+    let mut ap = CsrMatrix::try_from_pattern_and_values(ap_pattern, ap_values)
+        .expect("Internal error: Pattern and values must be compatible by construction");
+    spmm_csr_prealloc(T::zero(), &mut ap, T::one(), Op::NoOp(a), Op::NoOp(p))
+        .expect("Internal error: The product pattern must accommodate A * P");
+
+    let rap_pattern = spmm_csr_pattern(r.pattern(), ap.pattern());
+    let rap_values = vec![T::zero(); rap_pattern.nnz()];
+    let mut rap = CsrMatrix::try_from_pattern_and_values(rap_pattern, rap_values)
+        .expect("Internal error: Pattern and values must be compatible by construction");
+    spmm_csr_prealloc(T::zero(), &mut rap, T::one(), Op::NoOp(r), Op::NoOp(&ap))
+        .expect("Internal error: The product pattern must accommodate R * (A * P)");
+
+    Ok(rap)
+}
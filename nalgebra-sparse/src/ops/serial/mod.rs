@@ -56,13 +56,31 @@ mod cs;
 mod csc;
 mod csr;
 mod pattern;
+mod semiring;
 
 pub use csc::*;
 pub use csr::*;
 pub use pattern::*;
+pub use semiring::*;
 use std::fmt;
 use std::fmt::Formatter;
 
+/// Runtime-measured work counters for a sparse-sparse matrix multiplication.
+///
+/// Returned by [`spmm_csr_prealloc_with_stats`], this records the work actually performed by
+/// the kernel, as opposed to an a-priori estimate. The ratio of `search_steps` to
+/// `multiply_adds` indicates whether the linear search that places each contribution in the
+/// output pattern dominates the arithmetic, in which case the unchecked (Gustavson-style)
+/// path is likely to be significantly faster.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpmmStats {
+    /// The number of scalar multiply-add operations performed.
+    pub multiply_adds: usize,
+    /// The number of comparison steps performed by the linear search in the entry-placement
+    /// loop.
+    pub search_steps: usize,
+}
+
 /// A description of the error that occurred during an arithmetic operation.
 #[derive(Clone, Debug)]
 pub struct OperationError {
@@ -83,10 +101,18 @@ pub enum OperationErrorKind {
 
     /// Indicates that a matrix is singular when it is expected to be invertible.
     Singular,
+
+    /// Indicates that the dimensions of the operands are incompatible for the operation.
+    IncompatibleDimensions {
+        /// The number of columns of the left operand.
+        a_cols: usize,
+        /// The number of rows of the right operand.
+        b_rows: usize,
+    },
 }
 
 impl OperationError {
-    fn from_kind_and_message(error_type: OperationErrorKind, message: String) -> Self {
+    pub(crate) fn from_kind_and_message(error_type: OperationErrorKind, message: String) -> Self {
         Self {
             error_kind: error_type,
             message,
@@ -116,6 +142,9 @@ impl fmt::Display for OperationError {
             OperationErrorKind::Singular => {
                 write!(f, "Singular")?;
             }
+            OperationErrorKind::IncompatibleDimensions { a_cols, b_rows } => {
+                write!(f, "IncompatibleDimensions ({} != {})", a_cols, b_rows)?;
+            }
         }
         write!(f, " Message: {}", self.message)
     }
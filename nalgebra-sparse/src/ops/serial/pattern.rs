@@ -117,6 +117,61 @@ pub fn spmm_csr_pattern(a: &SparsityPattern, b: &SparsityPattern) -> SparsityPat
         .expect("Internal error: Invalid pattern during matrix multiplication pattern construction")
 }
 
+/// Gram matrix pattern construction, `C <- A^T * A`.
+///
+/// Interpreting `a` as the CSR pattern of a matrix `A`, this builds the pattern of the Gram
+/// matrix `A^T A` without computing any values: each row index set of `A` contributes the
+/// outer product of the set with itself, and the result is the union of these contributions.
+/// This makes it possible to preallocate the Gram matrix once and refill its values across
+/// iterations where the pattern of `A` is fixed but its values change, e.g. with
+/// [`CsrMatrix::gram`](crate::csr::CsrMatrix::gram) in normal-equation solvers.
+///
+/// The result is square with dimension `a.minor_dim()`, has sorted minor indices within each
+/// lane, and is symmetric as a pattern, since `A^T A` is symmetric.
+pub fn gram_pattern(a: &SparsityPattern) -> SparsityPattern {
+    let n = a.minor_dim();
+
+    // The lane for column i of A is the union of the row index sets of all rows that
+    // contain i, so we first need the transposed adjacency
+    let mut rows_containing_col: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (r, j) in a.entries() {
+        rows_containing_col[j].push(r);
+    }
+
+    let mut offsets = Vec::with_capacity(n + 1);
+    let mut indices = Vec::new();
+    offsets.push(0);
+
+    // Dense scatter vector marking the minor indices already present in the current lane,
+    // as in `spmm_csr_pattern`
+    let mut visited = vec![false; n];
+
+    for i in 0..n {
+        let c_lane_i_offset = *offsets.last().unwrap();
+        for &r in &rows_containing_col[i] {
+            for &j in a.lane(r) {
+                let have_visited_j = &mut visited[j];
+                if !*have_visited_j {
+                    indices.push(j);
+                    *have_visited_j = true;
+                }
+            }
+        }
+
+        let c_lane_i = &mut indices[c_lane_i_offset..];
+        c_lane_i.sort_unstable();
+
+        for j in c_lane_i {
+            visited[*j] = false;
+        }
+
+        offsets.push(indices.len());
+    }
+
+    SparsityPattern::try_from_offsets_and_indices(n, n, offsets, indices)
+        .expect("Internal error: Invalid pattern during Gram pattern construction")
+}
+
 /// Iterate over the union of the two sets represented by sorted slices
 /// (with unique elements)
 fn iterate_union<'a>(
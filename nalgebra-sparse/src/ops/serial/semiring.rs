@@ -0,0 +1,206 @@
+use crate::csr::CsrMatrix;
+use crate::ops::serial::{OperationError, OperationErrorKind};
+use nalgebra::{ClosedAdd, ClosedMul, Scalar};
+use num_traits::{One, Zero};
+
+/// A semiring over the scalar type `T`, used to generalize sparse matrix multiplication.
+///
+/// A semiring consists of an addition operation `⊕` with identity [`zero`](Self::zero) and a
+/// multiplication operation `⊗` with identity [`one`](Self::one), where `⊕` is associative and
+/// commutative, `⊗` is associative and distributes over `⊕`, and `zero` annihilates under `⊗`.
+/// Unlike a ring, no additive inverse is required, which admits structures such as the
+/// tropical semirings where `⊕` is `min` or `max`.
+///
+/// Implementors are typically zero-sized marker types that select the semiring at the type
+/// level, in the style of GraphBLAS: the same scalar type can be multiplied over different
+/// semirings by passing different markers to [`spmm_csr_semiring`]. The standard arithmetic
+/// instance is provided by [`StandardRing`], and the tropical instances by [`MinPlus`] and
+/// [`MaxPlus`].
+pub trait Semiring<T> {
+    /// The identity of the semiring addition, which also annihilates under the
+    /// semiring multiplication.
+    fn zero() -> T;
+
+    /// The identity of the semiring multiplication.
+    fn one() -> T;
+
+    /// The semiring addition, `x ⊕ y`.
+    fn add(x: &T, y: &T) -> T;
+
+    /// The semiring multiplication, `x ⊗ y`.
+    fn mul(x: &T, y: &T) -> T;
+}
+
+/// The standard arithmetic semiring `(+, *)`.
+///
+/// With this instance, [`spmm_csr_semiring`] computes the ordinary matrix product and agrees
+/// with [`spmm_csr_prealloc`](super::spmm_csr_prealloc) called with `beta = 0` and
+/// `alpha = 1`.
+#[derive(Copy, Clone, Debug)]
+pub struct StandardRing;
+
+impl<T> Semiring<T> for StandardRing
+where
+    T: Scalar + ClosedAdd + ClosedMul + Zero + One,
+{
+    #[inline]
+    fn zero() -> T {
+        T::zero()
+    }
+
+    #[inline]
+    fn one() -> T {
+        T::one()
+    }
+
+    #[inline]
+    fn add(x: &T, y: &T) -> T {
+        x.clone() + y.clone()
+    }
+
+    #[inline]
+    fn mul(x: &T, y: &T) -> T {
+        x.clone() * y.clone()
+    }
+}
+
+/// The min-plus (tropical) semiring `(min, +)`.
+///
+/// Addition is `min` with identity `+∞`, and multiplication is ordinary addition with
+/// identity `0`. Matrix multiplication over this semiring computes shortest path lengths:
+/// entry `(i, j)` of `A ⊗.⊕ B` is the length of the shortest two-leg path from `i` to `j`
+/// through any intermediate node, so repeated squaring of an adjacency matrix yields
+/// all-pairs shortest paths.
+#[derive(Copy, Clone, Debug)]
+pub struct MinPlus;
+
+/// The max-plus (tropical) semiring `(max, +)`.
+///
+/// Addition is `max` with identity `-∞`, and multiplication is ordinary addition with
+/// identity `0`. This semiring arises in scheduling and critical-path problems, where entry
+/// `(i, j)` of a product accumulates the longest two-leg path from `i` to `j`.
+#[derive(Copy, Clone, Debug)]
+pub struct MaxPlus;
+
+macro_rules! impl_tropical_semiring {
+    ($($t:ty),*) => {$(
+        impl Semiring<$t> for MinPlus {
+            #[inline]
+            fn zero() -> $t {
+                <$t>::INFINITY
+            }
+
+            #[inline]
+            fn one() -> $t {
+                0.0
+            }
+
+            #[inline]
+            fn add(x: &$t, y: &$t) -> $t {
+                x.min(*y)
+            }
+
+            #[inline]
+            fn mul(x: &$t, y: &$t) -> $t {
+                x + y
+            }
+        }
+
+        impl Semiring<$t> for MaxPlus {
+            #[inline]
+            fn zero() -> $t {
+                <$t>::NEG_INFINITY
+            }
+
+            #[inline]
+            fn one() -> $t {
+                0.0
+            }
+
+            #[inline]
+            fn add(x: &$t, y: &$t) -> $t {
+                x.max(*y)
+            }
+
+            #[inline]
+            fn mul(x: &$t, y: &$t) -> $t {
+                x + y
+            }
+        }
+    )*}
+}
+
+impl_tropical_semiring!(f32, f64);
+
+fn spmm_semiring_unexpected_entry() -> OperationError {
+    OperationError::from_kind_and_message(
+        OperationErrorKind::InvalidPattern,
+        String::from("Found unexpected entry that is not present in `c`."),
+    )
+}
+
+/// Sparse-sparse matrix multiplication over a semiring, `C <- A ⊗.⊕ B`.
+///
+/// Computes `c_ij = ⊕_k (a_ik ⊗ b_kj)` with the addition and multiplication of the semiring
+/// `S`, where the reduction over `k` ranges over the stored entries of `A` and `B`. With
+/// [`StandardRing`] this is the ordinary matrix product; with [`MinPlus`] or [`MaxPlus`] it
+/// performs a step of all-pairs shortest or longest path computation.
+///
+/// The sparsity pattern of `C` must be able to hold the pattern of the product, e.g. as
+/// computed by [`spmm_csr_pattern`](super::spmm_csr_pattern). All stored entries of `C` are
+/// overwritten; entries of the pattern that receive no contribution are set to
+/// [`S::zero()`](Semiring::zero), which for the tropical semirings is the appropriate
+/// infinity rather than the number zero. Note that explicitly stored zeros in `A` and `B`
+/// are not skipped: over a tropical semiring a stored `0.0` is an edge of weight zero, not
+/// an absent edge.
+///
+/// # Errors
+///
+/// If the pattern of `C` is not able to hold the result of the operation, an error is
+/// returned.
+///
+/// # Panics
+///
+/// Panics if the dimensions of the matrices involved are not compatible with the expression.
+pub fn spmm_csr_semiring<S, T>(
+    c: &mut CsrMatrix<T>,
+    a: &CsrMatrix<T>,
+    b: &CsrMatrix<T>,
+) -> Result<(), OperationError>
+where
+    T: Scalar,
+    S: Semiring<T>,
+{
+    assert_eq!(c.nrows(), a.nrows(), "C.nrows() != A.nrows()");
+    assert_eq!(c.ncols(), b.ncols(), "C.ncols() != B.ncols()");
+    assert_eq!(a.ncols(), b.nrows(), "A.ncols() != B.nrows()");
+
+    let (c, a, b) = (&mut c.cs, &a.cs, &b.cs);
+    for i in 0..c.pattern().major_dim() {
+        let a_lane_i = a.get_lane(i).unwrap();
+        let mut c_lane_i = c.get_lane_mut(i).unwrap();
+        for c_ij in c_lane_i.values_mut() {
+            *c_ij = S::zero();
+        }
+
+        for (&k, a_ik) in a_lane_i.minor_indices().iter().zip(a_lane_i.values()) {
+            let b_lane_k = b.get_lane(k).unwrap();
+            let (mut c_lane_i_cols, mut c_lane_i_values) = c_lane_i.indices_and_values_mut();
+            for (j, b_kj) in b_lane_k.minor_indices().iter().zip(b_lane_k.values()) {
+                // Determine the location in C to combine the value into
+                let (c_local_idx, _) = c_lane_i_cols
+                    .iter()
+                    .enumerate()
+                    .find(|(_, c_col)| *c_col == j)
+                    .ok_or_else(spmm_semiring_unexpected_entry)?;
+
+                c_lane_i_values[c_local_idx] =
+                    S::add(&c_lane_i_values[c_local_idx], &S::mul(a_ik, b_kj));
+                c_lane_i_cols = &c_lane_i_cols[c_local_idx..];
+                c_lane_i_values = &mut c_lane_i_values[c_local_idx..];
+            }
+        }
+    }
+
+    Ok(())
+}
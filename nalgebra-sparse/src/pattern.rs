@@ -4,9 +4,12 @@
 mod pattern_serde;
 
 use crate::cs::transpose_cs;
+use crate::csr::CsrMatrix;
 use crate::SparseFormatError;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
 
 /// A representation of the sparsity pattern of a CSR or CSC matrix.
 ///
@@ -43,7 +46,7 @@ use std::fmt;
 /// The invariants and relationship between `major_offsets` and `minor_indices` remain the same
 /// as for `row_offsets` and `col_indices` in the [CSR](`crate::csr::CsrMatrix`) format
 /// specification.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 // TODO: Make SparsityPattern parametrized by index type
 // (need a solid abstraction for index types though)
 pub struct SparsityPattern {
@@ -63,6 +66,9 @@ impl SparsityPattern {
     }
 
     /// The offsets for the major dimension.
+    ///
+    /// For a CSR matrix, these are the row offsets, while for a CSC matrix they are the
+    /// column offsets.
     #[inline]
     #[must_use]
     pub fn major_offsets(&self) -> &[usize] {
@@ -70,6 +76,9 @@ impl SparsityPattern {
     }
 
     /// The indices for the minor dimension.
+    ///
+    /// For a CSR matrix, these are the column indices, while for a CSC matrix they are the
+    /// row indices.
     #[inline]
     #[must_use]
     pub fn minor_indices(&self) -> &[usize] {
@@ -77,6 +86,10 @@ impl SparsityPattern {
     }
 
     /// The number of major lanes in the pattern.
+    ///
+    /// For a CSR matrix, this is the number of rows, while for a CSC matrix it is the number
+    /// of columns. Together with [`minor_dim`](Self::minor_dim), this makes it possible to
+    /// write format-generic code against `SparsityPattern` without format-specific branches.
     #[inline]
     #[must_use]
     pub fn major_dim(&self) -> usize {
@@ -85,6 +98,9 @@ impl SparsityPattern {
     }
 
     /// The number of minor lanes in the pattern.
+    ///
+    /// For a CSR matrix, this is the number of columns, while for a CSC matrix it is the
+    /// number of rows.
     #[inline]
     #[must_use]
     pub fn minor_dim(&self) -> usize {
@@ -135,10 +151,29 @@ impl SparsityPattern {
             return Err(InvalidOffsetArrayLength);
         }
 
+        let pattern = Self {
+            major_offsets,
+            minor_indices,
+            minor_dim,
+        };
+        pattern.check_invariants()?;
+        Ok(pattern)
+    }
+
+    /// Verifies that the pattern satisfies the invariants of the format.
+    ///
+    /// This is always upheld by patterns constructed through checked paths, but may be violated
+    /// by e.g. incorrect use of [`from_offset_and_indices_unchecked`
+    /// ](Self::from_offset_and_indices_unchecked). Checking the invariants catches corruption
+    /// early, instead of producing silently wrong results from binary searches over
+    /// unsorted indices.
+    pub fn check_invariants(&self) -> Result<(), SparsityPatternFormatError> {
+        use SparsityPatternFormatError::*;
+
         // Check that the first and last offsets conform to the specification
         {
-            let first_offset_ok = *major_offsets.first().unwrap() == 0;
-            let last_offset_ok = *major_offsets.last().unwrap() == minor_indices.len();
+            let first_offset_ok = *self.major_offsets.first().unwrap() == 0;
+            let last_offset_ok = *self.major_offsets.last().unwrap() == self.minor_indices.len();
             if !first_offset_ok || !last_offset_ok {
                 return Err(InvalidOffsetFirstLast);
             }
@@ -148,16 +183,16 @@ impl SparsityPattern {
         // minor indices within a lane are sorted, unique. In addition, each minor index
         // must be in bounds with respect to the minor dimension.
         {
-            for lane_idx in 0..major_dim {
-                let range_start = major_offsets[lane_idx];
-                let range_end = major_offsets[lane_idx + 1];
+            for lane_idx in 0..self.major_dim() {
+                let range_start = self.major_offsets[lane_idx];
+                let range_end = self.major_offsets[lane_idx + 1];
 
                 // Test that major offsets are monotonically increasing
                 if range_start > range_end {
                     return Err(NonmonotonicOffsets);
                 }
 
-                let minor_indices = &minor_indices[range_start..range_end];
+                let minor_indices = &self.minor_indices[range_start..range_end];
 
                 // We test for in-bounds, uniqueness and monotonicity at the same time
                 // to ensure that we only visit each minor index once
@@ -165,7 +200,7 @@ impl SparsityPattern {
                 let mut prev = None;
 
                 while let Some(next) = iter.next().copied() {
-                    if next >= minor_dim {
+                    if next >= self.minor_dim {
                         return Err(MinorIndexOutOfBounds);
                     }
 
@@ -181,11 +216,7 @@ impl SparsityPattern {
             }
         }
 
-        Ok(Self {
-            major_offsets,
-            minor_indices,
-            minor_dim,
-        })
+        Ok(())
     }
 
     /// Try to construct a sparsity pattern from the given dimensions, major offsets
@@ -210,11 +241,16 @@ impl SparsityPattern {
             assert!(first_offset_ok && last_offset_ok);
         }
 
-        Self {
+        let pattern = Self {
             major_offsets,
             minor_indices,
             minor_dim,
-        }
+        };
+        debug_assert!(
+            pattern.check_invariants().is_ok(),
+            "Format invariants violated in unchecked constructor"
+        );
+        pattern
     }
 
     /// An iterator over the explicitly stored "non-zero" entries (i, j).
@@ -265,6 +301,24 @@ impl SparsityPattern {
         (self.major_offsets, self.minor_indices)
     }
 
+    /// The number of bytes of memory used by the offset and index arrays of the pattern.
+    ///
+    /// This accounts for the *capacities* of the backing arrays rather than their lengths, so
+    /// that over-allocation - e.g. left over from incremental construction - is visible. See
+    /// also [`SparsityPattern::shrink_to_fit`].
+    #[must_use]
+    pub fn memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+        self.major_offsets.capacity() * size_of::<usize>()
+            + self.minor_indices.capacity() * size_of::<usize>()
+    }
+
+    /// Shrinks the capacity of the backing offset and index arrays to their lengths.
+    pub fn shrink_to_fit(&mut self) {
+        self.major_offsets.shrink_to_fit();
+        self.minor_indices.shrink_to_fit();
+    }
+
     /// Computes the transpose of the sparsity pattern.
     ///
     /// This is analogous to matrix transposition, i.e. an entry `(i, j)` becomes `(j, i)` in the
@@ -289,6 +343,277 @@ impl SparsityPattern {
         )
         .expect("Internal error: Transpose should never fail.")
     }
+
+    /// Computes the number of non-zeros in the union of `self` and `other` without
+    /// materializing the union pattern itself.
+    ///
+    /// This is the counting counterpart to
+    /// [`spadd_pattern`](crate::ops::serial::spadd_pattern): the returned count is exactly the
+    /// `nnz()` of the pattern that `spadd_pattern` would produce. It can be used to e.g. size
+    /// buffers before a sparse addition, or to decide whether the sum fits in a preallocated
+    /// matrix. The cost is a single merge pass over both patterns.
+    ///
+    /// Panics
+    /// ------
+    ///
+    /// Panics if the patterns do not have the same major and minor dimensions.
+    #[must_use]
+    pub fn union_nnz(&self, other: &SparsityPattern) -> usize {
+        assert_eq!(
+            self.major_dim(),
+            other.major_dim(),
+            "The patterns must have the same major dimension."
+        );
+        assert_eq!(
+            self.minor_dim(),
+            other.minor_dim(),
+            "The patterns must have the same minor dimension."
+        );
+
+        let mut count = 0;
+        for major in 0..self.major_dim() {
+            let lane_a = self.lane(major);
+            let lane_b = other.lane(major);
+            let (mut i, mut j) = (0, 0);
+            while i < lane_a.len() && j < lane_b.len() {
+                if lane_a[i] <= lane_b[j] {
+                    if lane_a[i] == lane_b[j] {
+                        j += 1;
+                    }
+                    i += 1;
+                } else {
+                    j += 1;
+                }
+                count += 1;
+            }
+            count += (lane_a.len() - i) + (lane_b.len() - j);
+        }
+        count
+    }
+
+    /// Computes the connected components of the pattern, interpreted as the adjacency
+    /// structure of an undirected graph.
+    ///
+    /// Each of the `major_dim` nodes is assigned a component label in `0 .. count`, and the
+    /// number of components together with the per-node labels is returned. Labels are assigned
+    /// in order of the lowest-numbered node in each component, so that node `0` always belongs
+    /// to component `0`. This is useful for detecting reducible systems before factorization,
+    /// which can then be solved block-by-block.
+    ///
+    /// The pattern is assumed to be structurally symmetric, i.e. an entry `(i, j)` implies the
+    /// presence of `(j, i)`. For an asymmetric pattern, each entry is still treated as an
+    /// undirected edge, so that the result corresponds to the connected components of the
+    /// symmetrized pattern. Explicit diagonal entries have no effect on the result.
+    ///
+    /// Panics
+    /// ------
+    ///
+    /// Panics if the pattern is not square.
+    #[must_use]
+    pub fn connected_components(&self) -> (usize, Vec<usize>) {
+        assert_eq!(
+            self.major_dim(),
+            self.minor_dim(),
+            "Connected components are only defined for square patterns."
+        );
+        let n = self.major_dim();
+
+        // Union-find with path halving and union by size. Using union-find rather than BFS
+        // lets us handle asymmetric patterns without materializing the transpose.
+        let mut parent: Vec<usize> = (0..n).collect();
+        let mut size = vec![1usize; n];
+
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for (i, j) in self.entries() {
+            let root_i = find(&mut parent, i);
+            let root_j = find(&mut parent, j);
+            if root_i != root_j {
+                if size[root_i] < size[root_j] {
+                    parent[root_i] = root_j;
+                    size[root_j] += size[root_i];
+                } else {
+                    parent[root_j] = root_i;
+                    size[root_i] += size[root_j];
+                }
+            }
+        }
+
+        // Relabel roots consecutively in order of first occurrence
+        let mut labels = vec![usize::MAX; n];
+        let mut count = 0;
+        for i in 0..n {
+            let root = find(&mut parent, i);
+            if labels[root] == usize::MAX {
+                labels[root] = count;
+                count += 1;
+            }
+            labels[i] = labels[root];
+        }
+
+        (count, labels)
+    }
+
+    /// Groups the major lanes (rows, for a CSR pattern) by identical minor index sets.
+    ///
+    /// Returns groups of lane indices such that all lanes within a group have exactly the
+    /// same set of minor indices. Groups are ordered by their first member, and the lane
+    /// indices within each group are in increasing order; concatenating the groups therefore
+    /// yields a permutation of `0..major_dim()` that clusters structurally identical lanes
+    /// together. This reveals block structure - for example, the rows belonging to the
+    /// components of a vector-valued unknown typically share a pattern - and can drive a
+    /// reordering towards block-banded form in block-structured solvers.
+    ///
+    /// Each lane's index slice is hashed once for grouping, so the expected cost is
+    /// `O(nnz)`.
+    #[must_use]
+    pub fn group_identical_rows(&self) -> Vec<Vec<usize>> {
+        let mut group_of_lane: HashMap<&[usize], usize> = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for i in 0..self.major_dim() {
+            let next_group = groups.len();
+            let group_idx = *group_of_lane.entry(self.lane(i)).or_insert(next_group);
+            if group_idx == next_group {
+                groups.push(Vec::new());
+            }
+            groups[group_idx].push(i);
+        }
+        groups
+    }
+
+    /// Compares the entries of two patterns and reports their differences.
+    ///
+    /// Classifies every entry of the two patterns as present only in `self`, only in `other`,
+    /// or in both. This is invaluable for debugging why two matrices that should share a
+    /// pattern do not - for example when
+    /// [`spadd_csr_prealloc`](crate::ops::serial::spadd_csr_prealloc) reports an unexpected
+    /// entry - since it pinpoints the exact offending `(major, minor)` coordinates.
+    ///
+    /// The dimensions of the patterns are not required to match; only the entry sets are
+    /// compared.
+    #[must_use]
+    pub fn diff(&self, other: &SparsityPattern) -> PatternDiff {
+        let mut diff = PatternDiff {
+            only_in_a: Vec::new(),
+            only_in_b: Vec::new(),
+            in_both: Vec::new(),
+        };
+        for i in 0..self.major_dim().max(other.major_dim()) {
+            let lane_a = if i < self.major_dim() { self.lane(i) } else { &[] };
+            let lane_b = if i < other.major_dim() {
+                other.lane(i)
+            } else {
+                &[]
+            };
+            // Merge the two sorted lanes, classifying each index as we go
+            let (mut a, mut b) = (lane_a.iter().peekable(), lane_b.iter().peekable());
+            loop {
+                match (a.peek(), b.peek()) {
+                    (Some(&&ja), Some(&&jb)) if ja == jb => {
+                        diff.in_both.push((i, ja));
+                        a.next();
+                        b.next();
+                    }
+                    (Some(&&ja), Some(&&jb)) if ja < jb => {
+                        diff.only_in_a.push((i, ja));
+                        a.next();
+                    }
+                    (Some(_), Some(&&jb)) => {
+                        diff.only_in_b.push((i, jb));
+                        b.next();
+                    }
+                    (Some(&&ja), None) => {
+                        diff.only_in_a.push((i, ja));
+                        a.next();
+                    }
+                    (None, Some(&&jb)) => {
+                        diff.only_in_b.push((i, jb));
+                        b.next();
+                    }
+                    (None, None) => break,
+                }
+            }
+        }
+        diff
+    }
+}
+
+/// The difference between the entry sets of two sparsity patterns, as computed by
+/// [`SparsityPattern::diff`].
+///
+/// All entries are `(major, minor)` coordinates in increasing lexicographic order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternDiff {
+    /// The entries present only in the first pattern.
+    pub only_in_a: Vec<(usize, usize)>,
+    /// The entries present only in the second pattern.
+    pub only_in_b: Vec<(usize, usize)>,
+    /// The entries present in both patterns.
+    pub in_both: Vec<(usize, usize)>,
+}
+
+/// An interner that canonicalizes equal sparsity patterns into shared instances.
+///
+/// Applications that assemble many matrices independently often end up with thousands of
+/// matrices that happen to share one of a few distinct patterns, each carrying its own copy
+/// of the index arrays. The interner deduplicates these: [`intern`](PatternInterner::intern)
+/// replaces the pattern handle of a matrix with a canonical shared one whenever an equal
+/// pattern has been seen before, so that all such matrices refer to a single allocation.
+/// Besides reducing memory use, this makes the pattern references of equal-pattern matrices
+/// pointer-identical, which lets operations like
+/// [`spadd_csr_prealloc`](crate::ops::serial::spadd_csr_prealloc) take their equal-pattern
+/// fast path without comparing the index arrays.
+///
+/// Interning never changes the value of a matrix, only which allocation backs its pattern.
+#[derive(Debug, Default)]
+pub struct PatternInterner {
+    canonical: HashSet<Arc<SparsityPattern>>,
+}
+
+impl PatternInterner {
+    /// Creates an interner with no registered patterns.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of distinct patterns registered in the interner.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.canonical.len()
+    }
+
+    /// Returns `true` if no patterns have been registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.canonical.is_empty()
+    }
+
+    /// Returns the canonical handle for the given pattern.
+    ///
+    /// If an equal pattern has been interned before, its handle is returned and the given
+    /// one is dropped; otherwise the given handle becomes the canonical one.
+    pub fn intern_pattern(&mut self, pattern: Arc<SparsityPattern>) -> Arc<SparsityPattern> {
+        if let Some(canonical) = self.canonical.get(&pattern) {
+            return Arc::clone(canonical);
+        }
+        self.canonical.insert(Arc::clone(&pattern));
+        pattern
+    }
+
+    /// Replaces the pattern handle of the matrix with the canonical one.
+    ///
+    /// After interning, all matrices whose patterns compare equal share a single pattern
+    /// allocation. The matrix itself is unchanged.
+    pub fn intern<T>(&mut self, matrix: &mut CsrMatrix<T>) {
+        let canonical = self.intern_pattern(Arc::clone(matrix.cs.pattern_arc()));
+        matrix.cs.replace_pattern_arc(canonical);
+    }
 }
 
 /// Error type for `SparsityPattern` format errors.
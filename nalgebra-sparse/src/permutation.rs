@@ -0,0 +1,98 @@
+//! Helpers for working with permutations of matrices and vectors.
+//!
+//! The routines in this module use the same convention as the ordering routines in
+//! [`factorization`](crate::factorization), such as
+//! [`approximate_minimum_degree`](crate::factorization::approximate_minimum_degree):
+//! a permutation is a slice `p` of length `n` containing each index in `0..n` exactly once,
+//! where **`p[i]` is the old index of new position `i`**. Applying `p` to a vector `x`
+//! therefore yields the vector with entries `x[p[0]], x[p[1]], ...`.
+//!
+//! A typical use is solving a reordered system: given a permutation `p` that reorders a
+//! symmetric matrix `A` into `B` with `B[i, j] = A[p[i], p[j]]` (i.e. `B = P A P^T`), one
+//! solves `B y = apply_permutation(p, b)` and recovers the solution of the original system
+//! as `x = apply_inverse_permutation(p, &y)`.
+
+use nalgebra::{DVector, Scalar};
+use num_traits::Zero;
+
+/// Asserts that `p` is a genuine permutation of `0..p.len()`.
+fn assert_is_permutation(p: &[usize]) {
+    let mut seen = vec![false; p.len()];
+    for &i in p {
+        assert!(
+            i < p.len() && !seen[i],
+            "Input is not a permutation: index {} is out of bounds or occurs more than once.",
+            i
+        );
+        seen[i] = true;
+    }
+}
+
+/// Computes the inverse of a permutation.
+///
+/// The result `ip` satisfies `ip[p[i]] == i` for all `i`, so that applying `ip` undoes the
+/// reordering performed by applying `p`.
+///
+/// Panics
+/// ------
+/// Panics if `p` is not a permutation of `0..p.len()`.
+#[must_use]
+pub fn invert_permutation(p: &[usize]) -> Vec<usize> {
+    assert_is_permutation(p);
+    let mut inverse = vec![0; p.len()];
+    for (i, &old) in p.iter().enumerate() {
+        inverse[old] = i;
+    }
+    inverse
+}
+
+/// Applies a permutation to a vector.
+///
+/// Entry `i` of the result is `v[p[i]]`, following the convention that `p[i]` is the old
+/// index of new position `i`.
+///
+/// Panics
+/// ------
+/// Panics if `p` is not a permutation of `0..p.len()`, or if the length of `v` does not
+/// match the length of `p`.
+#[must_use]
+pub fn apply_permutation<T>(p: &[usize], v: &DVector<T>) -> DVector<T>
+where
+    T: Scalar,
+{
+    assert_is_permutation(p);
+    assert_eq!(
+        v.nrows(),
+        p.len(),
+        "The vector length must match the permutation length."
+    );
+    DVector::from_fn(p.len(), |i, _| v[p[i]].clone())
+}
+
+/// Applies the inverse of a permutation to a vector.
+///
+/// Entry `p[i]` of the result is `v[i]`, i.e. this undoes [`apply_permutation`] without
+/// explicitly forming the inverse permutation. Use this to recover the solution of an
+/// original system from the solution of a reordered one.
+///
+/// Panics
+/// ------
+/// Panics if `p` is not a permutation of `0..p.len()`, or if the length of `v` does not
+/// match the length of `p`.
+#[must_use]
+pub fn apply_inverse_permutation<T>(p: &[usize], v: &DVector<T>) -> DVector<T>
+where
+    T: Scalar + Zero,
+{
+    assert_is_permutation(p);
+    assert_eq!(
+        v.nrows(),
+        p.len(),
+        "The vector length must match the permutation length."
+    );
+    let mut result = DVector::zeros(p.len());
+    for (i, &old) in p.iter().enumerate() {
+        result[old] = v[i].clone();
+    }
+    result
+}
@@ -0,0 +1,128 @@
+//! An implementation of a sparse vector with explicitly stored entries.
+use crate::{SparseFormatError, SparseFormatErrorKind};
+
+/// A sparse vector with explicitly stored non-zero entries.
+///
+/// The vector stores its explicit entries as a pair of sorted index and value arrays,
+/// exactly like a single lane of a CSR or CSC matrix. It is primarily intended for
+/// algorithms that manipulate matrix rows or columns as first-class objects; see
+/// [`CsrMatrix::into_rows`](crate::csr::CsrMatrix::into_rows) and
+/// [`CsrMatrix::from_rows`](crate::csr::CsrMatrix::from_rows) for decomposing a matrix into
+/// owned sparse rows and reassembling it.
+///
+/// As elsewhere in this crate, an explicitly stored entry may hold the value zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseVector<T> {
+    dim: usize,
+    indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T> SparseVector<T> {
+    /// Creates a sparse vector of the given dimension with no explicitly stored entries.
+    pub fn zeros(dim: usize) -> Self {
+        Self {
+            dim,
+            indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Try to construct a sparse vector from raw index and value arrays.
+    ///
+    /// The indices must be sorted, unique and in bounds with respect to the dimension of
+    /// the vector, and there must be one value per index. If this is not the case, an error
+    /// is returned to indicate the failure.
+    pub fn try_from_parts(
+        dim: usize,
+        indices: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<Self, SparseFormatError> {
+        if indices.len() != values.len() {
+            return Err(SparseFormatError::from_kind_and_msg(
+                SparseFormatErrorKind::InvalidStructure,
+                "Number of indices and values must be the same.",
+            ));
+        }
+        if !indices.windows(2).all(|w| w[0] < w[1]) {
+            return Err(SparseFormatError::from_kind_and_msg(
+                SparseFormatErrorKind::InvalidStructure,
+                "Indices must be sorted and unique.",
+            ));
+        }
+        if indices.last().map_or(false, |&i| i >= dim) {
+            return Err(SparseFormatError::from_kind_and_msg(
+                SparseFormatErrorKind::IndexOutOfBounds,
+                "An index is out of bounds with respect to the vector dimension.",
+            ));
+        }
+        Ok(Self {
+            dim,
+            indices,
+            values,
+        })
+    }
+
+    /// Constructs a sparse vector from data that is assumed to be valid.
+    pub(crate) fn from_parts_unchecked(dim: usize, indices: Vec<usize>, values: Vec<T>) -> Self {
+        debug_assert!(indices.len() == values.len());
+        debug_assert!(indices.windows(2).all(|w| w[0] < w[1]));
+        debug_assert!(indices.last().map_or(true, |&i| i < dim));
+        Self {
+            dim,
+            indices,
+            values,
+        }
+    }
+
+    /// The dimension of the vector.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.dim
+    }
+
+    /// Returns `true` if the vector has dimension zero.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.dim == 0
+    }
+
+    /// The number of explicitly stored entries in the vector.
+    #[inline]
+    #[must_use]
+    pub fn nnz(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// The indices of the explicitly stored entries.
+    #[inline]
+    #[must_use]
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// The values of the explicitly stored entries.
+    #[inline]
+    #[must_use]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Mutable access to the values of the explicitly stored entries.
+    #[inline]
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    /// An iterator over the explicitly stored entries as `(index, value)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.indices.iter().copied().zip(self.values.iter())
+    }
+
+    /// Returns the underlying index and value arrays.
+    pub fn disassemble(self) -> (Vec<usize>, Vec<T>) {
+        (self.indices, self.values)
+    }
+}
@@ -0,0 +1,173 @@
+//! An implementation of a symmetric sparse matrix format storing only one triangle.
+use crate::csr::CsrMatrix;
+use crate::{SparseFormatError, SparseFormatErrorKind};
+
+use nalgebra::{ClosedAdd, ClosedMul, ComplexField, DVector, Scalar};
+use num_traits::Zero;
+
+/// A symmetric sparse matrix that stores only the lower triangle, including the diagonal.
+///
+/// A symmetric matrix stored in general CSR format wastes half its index and value storage,
+/// since every off-diagonal entry `(i, j)` is mirrored by an equal entry `(j, i)`. This type
+/// stores only the entries with `j <= i` and treats the remaining entries as implied by
+/// symmetry: [`spmv`](SymCsrMatrix::spmv) accounts for the mirrored entries automatically,
+/// and [`to_csr`](SymCsrMatrix::to_csr) expands the matrix back to full CSR storage when a
+/// general kernel is needed.
+///
+/// A matrix that stores its upper triangle can be converted by transposing it first, which
+/// for a symmetric matrix leaves its value unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymCsrMatrix<T> {
+    // The lower triangle (j <= i) in ordinary CSR format; the invariant that no entry lies
+    // above the diagonal is upheld by all constructors
+    lower: CsrMatrix<T>,
+}
+
+impl<T> SymCsrMatrix<T> {
+    /// The number of rows of the (full, symmetric) matrix.
+    #[must_use]
+    pub fn nrows(&self) -> usize {
+        self.lower.nrows()
+    }
+
+    /// The number of columns of the (full, symmetric) matrix.
+    #[must_use]
+    pub fn ncols(&self) -> usize {
+        self.lower.ncols()
+    }
+
+    /// The number of explicitly stored entries, i.e. the entries of the lower triangle.
+    ///
+    /// Note that the number of structurally non-zero entries of the full matrix is larger,
+    /// since every stored off-diagonal entry represents two entries of the full matrix.
+    #[must_use]
+    pub fn nnz(&self) -> usize {
+        self.lower.nnz()
+    }
+
+    /// A reference to the stored lower triangle as an ordinary CSR matrix.
+    #[must_use]
+    pub fn lower(&self) -> &CsrMatrix<T> {
+        &self.lower
+    }
+
+    /// Converts the matrix into its stored lower triangle.
+    #[must_use]
+    pub fn into_lower(self) -> CsrMatrix<T> {
+        self.lower
+    }
+
+    /// Constructs a symmetric matrix directly from its lower triangle.
+    ///
+    /// The given matrix must be square and must not store any entry above the diagonal;
+    /// the entries above the diagonal of the symmetric matrix are implied by the stored ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with kind
+    /// [`InvalidStructure`](SparseFormatErrorKind::InvalidStructure) if the matrix is not
+    /// square or stores an entry above the diagonal.
+    pub fn try_from_lower_triangle(lower: CsrMatrix<T>) -> Result<Self, SparseFormatError> {
+        if lower.nrows() != lower.ncols() {
+            return Err(SparseFormatError::from_kind_and_msg(
+                SparseFormatErrorKind::InvalidStructure,
+                "A symmetric matrix must be square.",
+            ));
+        }
+        for (i, row) in lower.row_iter().enumerate() {
+            if row.col_indices().last().map_or(false, |&j| j > i) {
+                return Err(SparseFormatError::from_kind_and_msg(
+                    SparseFormatErrorKind::InvalidStructure,
+                    "The lower triangle must not store entries above the diagonal.",
+                ));
+            }
+        }
+        Ok(Self { lower })
+    }
+}
+
+impl<T: Scalar> SymCsrMatrix<T> {
+    /// Extracts the lower triangle of a general CSR matrix as a symmetric matrix.
+    ///
+    /// The matrix is first verified to be numerically symmetric within the given combined
+    /// absolute and relative tolerance, as by
+    /// [`CsrMatrix::is_symmetric_within`]; the entries above the diagonal are then discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with kind
+    /// [`InvalidStructure`](SparseFormatErrorKind::InvalidStructure) if the matrix is not
+    /// square, or not symmetric within the given tolerance.
+    pub fn from_csr(
+        matrix: &CsrMatrix<T>,
+        abs_tol: T::RealField,
+        rel_tol: T::RealField,
+    ) -> Result<Self, SparseFormatError>
+    where
+        T: ComplexField,
+    {
+        if matrix.nrows() != matrix.ncols() {
+            return Err(SparseFormatError::from_kind_and_msg(
+                SparseFormatErrorKind::InvalidStructure,
+                "A symmetric matrix must be square.",
+            ));
+        }
+        if !matrix.is_symmetric_within(abs_tol, rel_tol) {
+            return Err(SparseFormatError::from_kind_and_msg(
+                SparseFormatErrorKind::InvalidStructure,
+                "Matrix is not symmetric within the given tolerance.",
+            ));
+        }
+        Ok(Self {
+            lower: matrix.lower_triangle(),
+        })
+    }
+
+    /// Expands the matrix to full (general) CSR storage.
+    ///
+    /// Every stored off-diagonal entry `(i, j)` produces the mirrored entry `(j, i)` with the
+    /// same value in the result.
+    #[must_use]
+    pub fn to_csr(&self) -> CsrMatrix<T>
+    where
+        T: ClosedAdd,
+    {
+        let triplets = self.lower.triplet_iter().flat_map(|(i, j, v)| {
+            let mirrored = if i != j {
+                Some((j, i, v.clone()))
+            } else {
+                None
+            };
+            std::iter::once((i, j, v.clone())).chain(mirrored)
+        });
+        CsrMatrix::from_triplet_iter_summed(self.nrows(), self.ncols(), triplets)
+    }
+
+    /// Sparse matrix-vector multiplication `y <- beta * y + alpha * A * x` with the full
+    /// symmetric matrix `A = self`.
+    ///
+    /// Each stored off-diagonal entry contributes to two output positions, accounting for
+    /// its mirrored counterpart; no expansion to full storage takes place.
+    ///
+    /// Panics
+    /// ------
+    /// Panics if the lengths of `x` and `y` are not equal to the dimension of the matrix.
+    pub fn spmv(&self, beta: T, y: &mut DVector<T>, alpha: T, x: &DVector<T>)
+    where
+        T: ClosedAdd + ClosedMul + Zero,
+    {
+        assert_eq!(y.nrows(), self.nrows(), "y.nrows() != A.nrows()");
+        assert_eq!(x.nrows(), self.ncols(), "x.nrows() != A.ncols()");
+
+        for y_i in y.iter_mut() {
+            *y_i = beta.clone() * y_i.clone();
+        }
+        for (i, j, v) in self.lower.triplet_iter() {
+            let gamma = alpha.clone() * v.clone();
+            y[i] += gamma.clone() * x[j].clone();
+            if i != j {
+                y[j] += gamma * x[i].clone();
+            }
+        }
+    }
+}
@@ -10,7 +10,7 @@ pub fn apply_permutation<T: Clone>(out_slice: &mut [T], in_slice: &[T], permutat
     }
 }
 
-/// computes permutation by using provided indices as keys
+/// computes a stable permutation by using provided indices as keys
 #[inline]
 pub fn compute_sort_permutation(permutation: &mut [usize], indices: &[usize]) {
     assert_eq!(permutation.len(), indices.len());
@@ -19,8 +19,10 @@ pub fn compute_sort_permutation(permutation: &mut [usize], indices: &[usize]) {
         *p = i;
     }
 
-    // Compute permutation needed to bring minor indices into sorted order
+    // Compute permutation needed to bring minor indices into sorted order.
     // Note: Using sort_unstable here avoids internal allocations, which is crucial since
-    // each lane might have a small number of elements
-    permutation.sort_unstable_by_key(|idx| indices[*idx]);
+    // each lane might have a small number of elements. Ties are broken by the original
+    // position, which makes the sort stable: entries with equal indices keep their
+    // relative order.
+    permutation.sort_unstable_by_key(|idx| (indices[*idx], *idx));
 }
@@ -114,4 +114,48 @@ fn test_cholesky(a: Matrix5<f64>) {
     let l = DMatrix::from_iterator(l.nrows(), l.ncols(), l.iter().cloned());
     let cs_l_mat = DMatrix::from(&cs_l);
     assert_matrix_eq!(l, cs_l_mat, comp = abs, tol = 1e-12);
-}
\ No newline at end of file
+}
+#[test]
+fn amd_ordering_reduces_cholesky_fill() {
+    use nalgebra_sparse::factorization::{approximate_minimum_degree, CscSymbolicCholesky};
+
+    // The five-point Laplacian on a 2D grid, a standard example where the natural ordering
+    // produces significant fill
+    let grid = 7;
+    let n = grid * grid;
+    let mut dense = DMatrix::zeros(n, n);
+    for x in 0..grid {
+        for y in 0..grid {
+            let i = x * grid + y;
+            dense[(i, i)] = 4.0;
+            if x + 1 < grid {
+                dense[(i, i + grid)] = -1.0;
+                dense[(i + grid, i)] = -1.0;
+            }
+            if y + 1 < grid {
+                dense[(i, i + 1)] = -1.0;
+                dense[(i + 1, i)] = -1.0;
+            }
+        }
+    }
+    let a = CscMatrix::from(&dense);
+
+    let perm = approximate_minimum_degree(a.pattern());
+
+    // The result is a permutation of 0..n
+    let mut sorted = perm.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..n).collect::<Vec<_>>());
+
+    // Permute the matrix symmetrically and compare the fill of the Cholesky factors
+    let permuted_dense = DMatrix::from_fn(n, n, |i, j| dense[(perm[i], perm[j])]);
+    let permuted = CscMatrix::from(&permuted_dense);
+
+    let fill_natural = CscSymbolicCholesky::factor(a.pattern().clone()).l_pattern().nnz();
+    let fill_amd = CscSymbolicCholesky::factor(permuted.pattern().clone()).l_pattern().nnz();
+    assert!(fill_amd < fill_natural,
+            "AMD fill {} should be smaller than natural fill {}", fill_amd, fill_natural);
+
+    // The permuted matrix still factors numerically
+    CscCholesky::factor(&permuted).unwrap();
+}
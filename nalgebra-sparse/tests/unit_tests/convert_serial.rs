@@ -2,10 +2,11 @@ use crate::common::csc_strategy;
 use nalgebra::proptest::matrix;
 use nalgebra::DMatrix;
 use nalgebra_sparse::convert::serial::{
-    convert_coo_csc, convert_coo_csr, convert_coo_dense, convert_csc_coo, convert_csc_csr,
+    convert_coo_csc, convert_coo_csr, convert_coo_csr_with_options, convert_coo_dense, convert_csc_coo, convert_csc_csr,
     convert_csc_dense, convert_csr_coo, convert_csr_csc, convert_csr_dense, convert_dense_coo,
     convert_dense_csc, convert_dense_csr,
 };
+use nalgebra_sparse::convert::serial::CooToCsrOptions;
 use nalgebra_sparse::coo::CooMatrix;
 use nalgebra_sparse::csc::CscMatrix;
 use nalgebra_sparse::csr::CsrMatrix;
@@ -450,3 +451,151 @@ proptest! {
         prop_assert_eq!(&csc, &CscMatrix::from(&CsrMatrix::from(&csc)));
     }
 }
+
+#[test]
+fn test_convert_coo_cs_sums_duplicates_in_insertion_order() {
+    // The values are chosen so that floating-point rounding makes the sum depend on the
+    // order of summation: only the insertion order yields exactly 0.0
+    let values = vec![1e16, 1.0, -1e16];
+    let coo =
+        CooMatrix::try_from_triplets(2, 2, vec![1, 1, 1], vec![0, 0, 0], values).unwrap();
+
+    let csr = convert_coo_csr(&coo);
+    assert_eq!(csr.values(), &[0.0]);
+
+    let csc = convert_coo_csc(&coo);
+    assert_eq!(csc.values(), &[0.0]);
+}
+
+proptest! {
+    #[test]
+    fn coo_csr_coo_roundtrip_preserves_content(coo in coo_strategy()) {
+        let csr = CsrMatrix::from(&coo);
+        let roundtrip = csr.to_coo();
+
+        // The round trip sums duplicates, but otherwise preserves the content of the matrix
+        prop_assert_eq!(DMatrix::from(&roundtrip), DMatrix::from(&coo));
+        prop_assert_eq!(roundtrip.nnz(), csr.nnz());
+
+        // The triplets of the CSR conversion are produced in row-major order
+        let triplets: Vec<_> = roundtrip.triplet_iter().map(|(i, j, _)| (i, j)).collect();
+        let mut sorted = triplets.clone();
+        sorted.sort_unstable();
+        prop_assert_eq!(triplets, sorted);
+    }
+
+    #[test]
+    fn csc_to_coo_triplets_are_column_major(csc in csc_strategy()) {
+        let coo = csc.to_coo();
+        prop_assert_eq!(DMatrix::from(&coo), DMatrix::from(&csc));
+
+        let triplets: Vec<_> = coo.triplet_iter().map(|(i, j, _)| (j, i)).collect();
+        let mut sorted = triplets.clone();
+        sorted.sort_unstable();
+        prop_assert_eq!(triplets, sorted);
+    }
+}
+
+#[test]
+fn test_convert_coo_csr_sorted_input() {
+    // Triplets already in row-major order take the sort-free fast path; the result must be
+    // identical to the one produced for the same triplets in shuffled order
+    let mut sorted = CooMatrix::new(4, 5);
+    sorted.push(0, 1, 2);
+    sorted.push(0, 3, 1);
+    // Duplicates are adjacent in sorted input and must still be summed
+    sorted.push(0, 3, 4);
+    sorted.push(2, 0, 3);
+    sorted.push(2, 4, -2);
+    sorted.push(3, 2, 7);
+
+    let mut shuffled = CooMatrix::new(4, 5);
+    shuffled.push(2, 4, -2);
+    shuffled.push(0, 3, 1);
+    shuffled.push(3, 2, 7);
+    shuffled.push(0, 1, 2);
+    shuffled.push(2, 0, 3);
+    shuffled.push(0, 3, 4);
+
+    let csr_sorted = convert_coo_csr(&sorted);
+    let csr_shuffled = convert_coo_csr(&shuffled);
+    assert_eq!(csr_sorted, csr_shuffled);
+    assert_eq!(csr_sorted.row_offsets(), &[0, 2, 2, 4, 5]);
+    assert_eq!(csr_sorted.col_indices(), &[1, 3, 0, 4, 2]);
+    assert_eq!(csr_sorted.values(), &[2, 5, 3, -2, 7]);
+
+    // Empty matrices also take the fast path
+    let empty = CooMatrix::<i32>::new(3, 3);
+    assert_eq!(convert_coo_csr(&empty), CsrMatrix::zeros(3, 3));
+}
+
+proptest! {
+    #[test]
+    fn coo_csr_agrees_between_sorted_and_shuffled_triplets(coo in coo_strategy()) {
+        // Sorting the triplets beforehand must not change the result of the conversion
+        let mut triplets: Vec<_> = coo
+            .triplet_iter()
+            .map(|(i, j, v)| (i, j, *v))
+            .collect();
+        triplets.sort_by_key(|&(i, j, _)| (i, j));
+
+        let mut sorted = CooMatrix::new(coo.nrows(), coo.ncols());
+        for (i, j, v) in triplets {
+            sorted.push(i, j, v);
+        }
+
+        prop_assert_eq!(convert_coo_csr(&sorted), convert_coo_csr(&coo));
+    }
+}
+
+#[test]
+fn test_convert_coo_csr_with_options() {
+    let mut coo = CooMatrix::new(3, 4);
+    coo.push(0, 1, 2);
+    coo.push(2, 0, 0);
+    // Duplicates that sum to zero
+    coo.push(1, 3, 5);
+    coo.push(1, 3, -5);
+    coo.push(2, 2, 4);
+
+    // The default options match the plain conversion
+    let default = convert_coo_csr_with_options(&coo, CooToCsrOptions::default());
+    assert_eq!(default, convert_coo_csr(&coo));
+    assert_eq!(default.nnz(), 4);
+
+    // Dropping zeros removes both the explicit zero and the duplicates that cancel
+    let dropped = convert_coo_csr_with_options(
+        &coo,
+        CooToCsrOptions {
+            drop_zeros: true,
+            ..CooToCsrOptions::default()
+        },
+    );
+    assert_eq!(dropped.row_offsets(), &[0, 1, 1, 2]);
+    assert_eq!(dropped.col_indices(), &[1, 2]);
+    assert_eq!(dropped.values(), &[2, 4]);
+
+    // Without summation, the first occurrence of a duplicated index pair wins
+    let first = convert_coo_csr_with_options(
+        &coo,
+        CooToCsrOptions {
+            sum_duplicates: false,
+            ..CooToCsrOptions::default()
+        },
+    );
+    assert_eq!(first.row_offsets(), &[0, 1, 2, 4]);
+    assert_eq!(first.col_indices(), &[1, 3, 0, 2]);
+    assert_eq!(first.values(), &[2, 5, 0, 4]);
+}
+
+proptest! {
+    #[test]
+    fn coo_csr_with_default_options_agrees_with_plain_conversion(
+        coo in coo_with_duplicates(-5..5, 0..=6usize, 0..=6usize, 40, 2)
+    ) {
+        prop_assert_eq!(
+            convert_coo_csr_with_options(&coo, CooToCsrOptions::default()),
+            convert_coo_csr(&coo)
+        );
+    }
+}
@@ -367,3 +367,64 @@ fn coo_push_matrix_out_of_bounds_entries() {
         assert_panics!(CooMatrix::new(3, 3).push_matrix(2, 2, &inserted));
     }
 }
+
+#[test]
+fn coo_from_iter() {
+    let triplets = vec![(0, 0, 1), (2, 2, 2), (0, 0, 3), (1, 2, 4)];
+    let coo = CooMatrix::from_iter(3, 3, triplets.clone().into_iter());
+
+    assert_eq!(coo.nrows(), 3);
+    assert_eq!(coo.ncols(), 3);
+    assert_eq!(coo.nnz(), 4);
+    let collected: Vec<_> = coo.triplet_iter().map(|(i, j, v)| (i, j, *v)).collect();
+    assert_eq!(collected, triplets);
+
+    // An empty iterator gives a zero matrix
+    assert_eq!(CooMatrix::<i32>::from_iter(2, 2, std::iter::empty()), CooMatrix::new(2, 2));
+
+    // Out of bounds indices cause a panic as soon as they are consumed
+    assert_panics!(CooMatrix::from_iter(3, 3, vec![(3, 0, 1)].into_iter()));
+    assert_panics!(CooMatrix::from_iter(3, 3, vec![(0, 3, 1)].into_iter()));
+}
+
+#[cfg(feature = "provenance")]
+#[test]
+fn coo_entry_provenance() {
+    let mut coo = CooMatrix::new(3, 3);
+    // Two tagged contributions and one untagged contribution to the same entry
+    coo.push_tagged(1, 1, 1.0, 10);
+    coo.push(0, 2, 2.0);
+    coo.push_tagged(1, 1, -1.0, 20);
+    coo.push(1, 1, 4.0);
+    coo.push_tagged(2, 0, 3.0, 30);
+
+    let provenance = coo.entry_provenance();
+    assert_eq!(
+        provenance,
+        vec![
+            ((0, 2), vec![]),
+            ((1, 1), vec![10, 20]),
+            ((2, 0), vec![30]),
+        ]
+    );
+}
+
+#[test]
+fn coo_try_from_triplets_names_offending_triplet() {
+    // The row index of triplet 2 is out of bounds
+    let err =
+        CooMatrix::try_from_triplets(3, 4, vec![0, 1, 5, 2], vec![0, 1, 2, 3], vec![1, 2, 3, 4])
+            .unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::IndexOutOfBounds);
+    assert!(err.to_string().contains("Triplet 2 = (5, 2, _)"));
+    assert!(err.to_string().contains("row index"));
+
+    // The col index of triplet 1 is out of bounds, and validation short-circuits on it
+    // even though triplet 3 is also invalid
+    let err =
+        CooMatrix::try_from_triplets(3, 4, vec![0, 1, 2, 9], vec![0, 7, 2, 8], vec![1, 2, 3, 4])
+            .unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::IndexOutOfBounds);
+    assert!(err.to_string().contains("Triplet 1 = (1, 7, _)"));
+    assert!(err.to_string().contains("col index"));
+}
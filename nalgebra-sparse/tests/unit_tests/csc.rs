@@ -1,5 +1,7 @@
-use nalgebra::DMatrix;
+use matrixcompare::assert_matrix_eq;
+use nalgebra::{DMatrix, DVector};
 use nalgebra_sparse::csc::CscMatrix;
+use nalgebra_sparse::ops::serial::OperationErrorKind;
 use nalgebra_sparse::{SparseEntry, SparseEntryMut, SparseFormatErrorKind};
 
 use proptest::prelude::*;
@@ -721,3 +723,146 @@ proptest! {
         prop_assert_eq!(DMatrix::from(&csc), DMatrix::identity(n, n));
     }
 }
+
+#[test]
+fn csc_abs() {
+    let csc = CscMatrix::try_from_csc_data(
+        3,
+        2,
+        vec![0, 2, 3],
+        vec![0, 2, 1],
+        vec![-3.0, 2.0, -0.5],
+    )
+    .unwrap();
+    let abs = csc.abs();
+
+    assert_eq!(abs.pattern(), csc.pattern());
+    assert_eq!(DMatrix::from(&abs), DMatrix::from(&csc).abs());
+}
+
+#[test]
+fn csc_try_from_csc_data_errors_name_offending_column() {
+    // Out-of-bounds row index in column 1
+    let err = CscMatrix::try_from_csc_data(3, 2, vec![0, 1, 2], vec![0, 5], vec![1, 2]).unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::IndexOutOfBounds);
+    assert!(err.to_string().contains("column 1"));
+
+    // Duplicate row index in column 0
+    let err =
+        CscMatrix::try_from_csc_data(3, 2, vec![0, 2, 2], vec![1, 1], vec![1, 2]).unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::DuplicateEntry);
+    assert!(err.to_string().contains("column 0"));
+
+    // Unsorted row indices in column 0
+    let err =
+        CscMatrix::try_from_csc_data(3, 2, vec![0, 2, 2], vec![2, 0], vec![1, 2]).unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::InvalidStructure);
+    assert!(err.to_string().contains("column 0"));
+
+    // The same data is accepted by the unsorted constructor, which sorts during construction
+    let csc = CscMatrix::try_from_unsorted_csc_data(3, 2, vec![0, 2, 2], vec![2, 0], vec![1, 2])
+        .unwrap();
+    assert_eq!(csc.row_indices(), &[0, 2]);
+    assert_eq!(csc.values(), &[2, 1]);
+}
+
+#[test]
+fn csc_empty_cols() {
+    // Column 1 is structurally empty; column 2 stores only explicit zeros
+    let csc =
+        CscMatrix::try_from_csc_data(3, 4, vec![0, 2, 2, 4, 5], vec![0, 1, 0, 2, 1], vec![
+            1.0, 2.0, 0.0, 1e-12, 3.0,
+        ])
+        .unwrap();
+
+    assert_eq!(csc.empty_cols(), vec![1]);
+    assert!(csc.has_empty_col());
+
+    // Numerical emptiness also reports columns of explicit (near-)zeros
+    assert_eq!(csc.numerically_zero_cols(1e-10), vec![1, 2]);
+    assert_eq!(csc.numerically_zero_cols(0.0), vec![1]);
+
+    let full = CscMatrix::from(&DMatrix::from_element(2, 2, 1.0));
+    assert_eq!(full.empty_cols(), Vec::<usize>::new());
+    assert!(!full.has_empty_col());
+}
+
+#[test]
+fn csc_solve_lower_triangular_vector() {
+    #[rustfmt::skip]
+    let l = DMatrix::from_row_slice(3, 3, &[
+        2.0, 0.0, 0.0,
+        1.0, 4.0, 0.0,
+        3.0, 5.0, 8.0,
+    ]);
+    let csc = CscMatrix::from(&l);
+
+    // Non-unit diagonal: compare against the dense solve
+    let b = DVector::from_column_slice(&[2.0, 6.0, 19.0]);
+    let mut x = b.clone();
+    csc.solve_lower_triangular_vector(&mut x, false).unwrap();
+    let x_dense = l.clone().lu().solve(&b).unwrap();
+    assert_matrix_eq!(x, x_dense, comp = abs, tol = 1e-12);
+
+    // Unit diagonal: stored diagonal entries are ignored
+    let mut x = b.clone();
+    csc.solve_lower_triangular_vector(&mut x, true).unwrap();
+    let mut l_unit = l.clone();
+    l_unit.fill_diagonal(1.0);
+    let x_dense = l_unit.lu().solve(&b).unwrap();
+    assert_matrix_eq!(x, x_dense, comp = abs, tol = 1e-12);
+
+    // Entries above the diagonal are ignored
+    let mut l_upper = l.clone();
+    l_upper[(0, 2)] = 7.0;
+    let csc_upper = CscMatrix::from(&l_upper);
+    let mut x = b.clone();
+    csc_upper.solve_lower_triangular_vector(&mut x, false).unwrap();
+    let x_dense = l.lu().solve(&b).unwrap();
+    assert_matrix_eq!(x, x_dense, comp = abs, tol = 1e-12);
+
+    // A missing or zero diagonal entry is an error when unit_diagonal is false
+    let singular =
+        CscMatrix::try_from_csc_data(2, 2, vec![0, 1, 1], vec![0], vec![1.0]).unwrap();
+    let result = singular.solve_lower_triangular_vector(&mut DVector::zeros(2), false);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        OperationErrorKind::Singular
+    ));
+
+    // ... but unit_diagonal treats it as 1
+    let mut x = DVector::from_column_slice(&[1.0, 2.0]);
+    singular
+        .solve_lower_triangular_vector(&mut x, true)
+        .unwrap();
+    assert_matrix_eq!(x, DVector::from_column_slice(&[1.0, 2.0]), comp = abs, tol = 1e-12);
+}
+
+#[test]
+fn csc_col_iter_is_double_ended_and_exact_size() {
+    let a = CscMatrix::try_from_csc_data(4, 3, vec![0, 2, 2, 5], vec![0, 3, 1, 2, 3], vec![
+        1, 2, 3, 4, 5,
+    ])
+    .unwrap();
+
+    assert_eq!(a.col_iter().len(), 3);
+
+    let reversed: Vec<_> = a
+        .col_iter()
+        .rev()
+        .map(|col| (col.row_indices().to_vec(), col.values().to_vec()))
+        .collect();
+    assert_eq!(reversed, vec![
+        (vec![1, 2, 3], vec![3, 4, 5]),
+        (vec![], vec![]),
+        (vec![0, 3], vec![1, 2]),
+    ]);
+
+    let mut a = a;
+    for mut col in a.col_iter_mut().rev() {
+        for v in col.values_mut() {
+            *v += 1;
+        }
+    }
+    assert_eq!(a.values(), &[2, 3, 4, 5, 6]);
+}
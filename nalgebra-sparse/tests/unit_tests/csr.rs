@@ -1,7 +1,10 @@
-use nalgebra::DMatrix;
-use nalgebra_sparse::csr::CsrMatrix;
+use nalgebra::{DMatrix, DVector};
+use nalgebra_sparse::csr::{CsrMatrix, SweepDirection, ZeroRowPolicy};
+use nalgebra_sparse::ops::serial::OperationErrorKind;
+use nalgebra_sparse::pattern::SparsityPattern;
 use nalgebra_sparse::{SparseEntry, SparseEntryMut, SparseFormatErrorKind};
 
+use matrixcompare::assert_matrix_eq;
 use proptest::prelude::*;
 use proptest::sample::subsequence;
 
@@ -11,6 +14,7 @@ use crate::assert_panics;
 use crate::common::csr_strategy;
 
 use std::collections::HashSet;
+use std::sync::Arc;
 
 #[test]
 fn csr_matrix_valid_data() {
@@ -396,6 +400,55 @@ fn csr_matrix_try_from_unsorted_invalid_csr_data() {
     }
 }
 
+#[test]
+fn csr_matrix_from_csr_data_unsorted() {
+    {
+        // Unsorted columns without duplicates agree with the strict constructors
+        let valid_data: ValidCsDataExamples = ValidCsDataExamples::new();
+
+        let (offsets, indices, values) = valid_data.valid_unsorted_cs_data;
+        let csr = CsrMatrix::from_csr_data_unsorted(4, 5, offsets, indices, values).unwrap();
+
+        let (offsets2, indices2, values2) = valid_data.valid_cs_data;
+        let expected_csr = CsrMatrix::try_from_csr_data(4, 5, offsets2, indices2, values2).unwrap();
+
+        assert_eq!(csr, expected_csr);
+    }
+
+    {
+        // Duplicate entries within a row are summed in order of appearance
+        let offsets = vec![0, 4, 4, 6];
+        let indices = vec![3, 1, 3, 1, 2, 2];
+        let values = vec![10, 1, 20, 2, 5, 7];
+        let csr = CsrMatrix::from_csr_data_unsorted(3, 6, offsets, indices, values).unwrap();
+
+        let expected_csr =
+            CsrMatrix::try_from_csr_data(3, 6, vec![0, 2, 2, 3], vec![1, 3, 2], vec![3, 30, 12])
+                .unwrap();
+        assert_eq!(csr, expected_csr);
+    }
+
+    {
+        // Structural errors are still reported
+        let invalid_data: InvalidCsDataExamples = InvalidCsDataExamples::new();
+
+        let (offsets, indices, values) = invalid_data.nonmonotonic_offsets;
+        let matrix = CsrMatrix::from_csr_data_unsorted(3, 6, offsets, indices, values);
+        assert_eq!(
+            matrix.unwrap_err().kind(),
+            &SparseFormatErrorKind::InvalidStructure
+        );
+
+        let invalid_data: InvalidCsDataExamples = InvalidCsDataExamples::new();
+        let (offsets, indices, values) = invalid_data.minor_index_out_of_bounds;
+        let matrix = CsrMatrix::from_csr_data_unsorted(3, 6, offsets, indices, values);
+        assert_eq!(
+            matrix.unwrap_err().kind(),
+            &SparseFormatErrorKind::IndexOutOfBounds
+        );
+    }
+}
+
 #[test]
 fn csr_disassemble_avoids_clone_when_owned() {
     // Test that disassemble avoids cloning the sparsity pattern when it holds the sole reference
@@ -715,3 +768,1753 @@ proptest! {
         prop_assert_eq!(DMatrix::from(&csr), DMatrix::identity(n, n));
     }
 }
+
+#[test]
+fn csr_row_dot() {
+    // Matrix:
+    // [1, 0, 2, 0]
+    // [0, 0, 0, 0]
+    // [0, 3, 0, 4]
+    let csr = CsrMatrix::try_from_csr_data(
+        3,
+        4,
+        vec![0, 2, 2, 4],
+        vec![0, 2, 1, 3],
+        vec![1, 2, 3, 4],
+    )
+    .unwrap();
+    let x = DVector::from_column_slice(&[1, 2, 3, 4]);
+
+    assert_eq!(csr.row_dot(0, &x), 7);
+    assert_eq!(csr.row_dot(1, &x), 0);
+    assert_eq!(csr.row_dot(2, &x), 22);
+
+    // Each row dot corresponds to an entry of the full matrix-vector product
+    let product = DMatrix::from(&csr) * &x;
+    for i in 0..csr.nrows() {
+        assert_eq!(csr.row_dot(i, &x), product[i]);
+    }
+
+    assert_panics!(csr.row_dot(3, &x));
+    assert_panics!(csr.row_dot(0, &DVector::from_column_slice(&[1, 2, 3])));
+}
+
+#[test]
+fn csr_from_triplet_iter_summed() {
+    // Duplicate entries (0, 0) and unsorted triplets within each row
+    let triplets = vec![(2, 1, 5), (0, 3, 2), (0, 0, 1), (1, 1, 3), (0, 0, 4)];
+    let csr = CsrMatrix::from_triplet_iter_summed(3, 4, triplets.into_iter());
+
+    let expected = DMatrix::from_row_slice(3, 4, &[5, 0, 0, 2, 0, 3, 0, 0, 0, 5, 0, 0]);
+    assert_eq!(DMatrix::from(&csr), expected);
+    assert_eq!(csr.nnz(), 4);
+
+    // An empty iterator gives a zero matrix
+    let empty = CsrMatrix::<i32>::from_triplet_iter_summed(2, 3, std::iter::empty());
+    assert_eq!(empty, CsrMatrix::zeros(2, 3));
+
+    // Out of bounds indices cause a panic
+    assert_panics!(CsrMatrix::from_triplet_iter_summed(3, 4, vec![(3, 0, 1)].into_iter()));
+    assert_panics!(CsrMatrix::from_triplet_iter_summed(3, 4, vec![(0, 4, 1)].into_iter()));
+}
+
+#[test]
+fn csr_check_invariants() {
+    let csr = CsrMatrix::try_from_csr_data(
+        3,
+        4,
+        vec![0, 2, 2, 4],
+        vec![0, 2, 1, 3],
+        vec![1, 2, 3, 4],
+    )
+    .unwrap();
+    assert!(csr.check_invariants().is_ok());
+    assert!(CsrMatrix::<i32>::zeros(3, 4).check_invariants().is_ok());
+}
+
+#[test]
+fn csr_dense_lu_solve() {
+    let a = CsrMatrix::try_from_csr_data(
+        3,
+        3,
+        vec![0, 2, 3, 5],
+        vec![0, 2, 1, 0, 2],
+        vec![4.0, 1.0, 2.0, 1.0, 3.0],
+    )
+    .unwrap();
+    let b = DMatrix::from_column_slice(3, 2, &[6.0, 4.0, 7.0, 1.0, 2.0, 4.0]);
+
+    let x = a.dense_lu_solve(&b).unwrap();
+    assert_matrix_eq!(DMatrix::from(&a) * x, b, comp = abs, tol = 1e-12);
+
+    // Singular matrices are rejected
+    let singular = CsrMatrix::<f64>::zeros(3, 3);
+    assert!(singular.dense_lu_solve(&b).is_none());
+
+    // Non-square systems and incompatible right-hand sides are rejected
+    let rectangular = CsrMatrix::<f64>::zeros(3, 4);
+    assert!(rectangular.dense_lu_solve(&b).is_none());
+    assert!(a.dense_lu_solve(&DMatrix::zeros(4, 2)).is_none());
+}
+
+#[test]
+fn csr_symmetrize_pattern() {
+    // Matrix:
+    // [1, 2, 0]
+    // [0, 0, 3]
+    // [0, 0, 0]
+    let csr = CsrMatrix::try_from_csr_data(3, 3, vec![0, 2, 3, 3], vec![0, 1, 2], vec![1, 2, 3])
+        .unwrap();
+    let symmetrized = csr.symmetrize_pattern();
+
+    // The pattern is structurally symmetric, and the new entries are explicit zeros,
+    // so the matrix itself is unchanged
+    assert_eq!(symmetrized.pattern(), &symmetrized.pattern().transpose());
+    assert_eq!(DMatrix::from(&symmetrized), DMatrix::from(&csr));
+    assert_eq!(symmetrized.nnz(), 5);
+
+    // A matrix with a symmetric pattern is returned as-is
+    let symmetric = CsrMatrix::<i32>::identity(3);
+    assert_eq!(symmetric.symmetrize_pattern(), symmetric);
+
+    assert_panics!(CsrMatrix::<i32>::zeros(2, 3).symmetrize_pattern());
+}
+
+#[test]
+fn csr_abs() {
+    use nalgebra::Complex;
+
+    let csr = CsrMatrix::try_from_csr_data(
+        2,
+        3,
+        vec![0, 2, 3],
+        vec![0, 2, 1],
+        vec![-3.0, 2.0, -0.5],
+    )
+    .unwrap();
+    let abs = csr.abs();
+
+    assert_eq!(abs.pattern(), csr.pattern());
+    assert_eq!(DMatrix::from(&abs), DMatrix::from(&csr).abs());
+
+    // For complex input, the magnitude is the modulus
+    let complex = CsrMatrix::try_from_csr_data(
+        1,
+        2,
+        vec![0, 2],
+        vec![0, 1],
+        vec![Complex::new(3.0, 4.0), Complex::new(0.0, -2.0)],
+    )
+    .unwrap();
+    assert_eq!(complex.abs().values(), &[5.0, 2.0]);
+}
+
+#[test]
+fn csr_add_outer_product_in_pattern() {
+    let mut csr = CsrMatrix::try_from_csr_data(
+        3,
+        4,
+        vec![0, 2, 2, 4],
+        vec![0, 2, 1, 3],
+        vec![1, 2, 3, 4],
+    )
+    .unwrap();
+    let pattern = csr.pattern().clone();
+    let x = DVector::from_column_slice(&[1, 2, 3]);
+    let y = DVector::from_column_slice(&[4, 5, 6, 7]);
+    let alpha = 2;
+
+    csr.add_outer_product_in_pattern(alpha, &x, &y);
+
+    // The pattern is unchanged and each stored entry receives alpha * x[i] * y[j],
+    // while off-pattern contributions are dropped
+    assert_eq!(csr.pattern(), &pattern);
+    let mask = CsrMatrix::try_from_pattern_and_values(pattern, vec![1; 4]).unwrap();
+    let expected = DMatrix::from_row_slice(3, 4, &[1, 0, 2, 0, 0, 0, 0, 0, 0, 3, 0, 4])
+        + (&x * y.transpose() * alpha).zip_map(&DMatrix::from(&mask), |v, m| v * m);
+    assert_eq!(DMatrix::from(&csr), expected);
+
+    assert_panics!(csr.clone().add_outer_product_in_pattern(1, &y, &y));
+    assert_panics!(csr.clone().add_outer_product_in_pattern(1, &x, &x));
+}
+
+#[test]
+fn csr_spy() {
+    // 4x4 matrix with entries on the diagonal, downsampled to a 2x2 grid
+    let csr = CsrMatrix::try_from_csr_data(
+        4,
+        4,
+        vec![0, 1, 2, 3, 4],
+        vec![0, 1, 2, 3],
+        vec![1, 1, 1, 1],
+    )
+    .unwrap();
+    let spy = csr.spy(2, 2);
+    assert_eq!(spy, DMatrix::from_row_slice(2, 2, &[true, false, false, true]));
+
+    // Non-divisible dimensions: a 3x5 matrix bucketed into 2x2.
+    // Rows 0 map to bucket 0, rows 1..3 to bucket 1 (floor(i * 2 / 3)),
+    // cols 0..3 map to bucket 0, cols 3..5 to bucket 1 (floor(j * 2 / 5))
+    let csr = CsrMatrix::try_from_csr_data(3, 5, vec![0, 1, 1, 2], vec![2, 3], vec![1, 1])
+        .unwrap();
+    let spy = csr.spy(2, 2);
+    assert_eq!(spy, DMatrix::from_row_slice(2, 2, &[true, false, false, true]));
+
+    // A matrix smaller than the grid produces the exact sparsity mask
+    let spy = csr.spy(4, 6);
+    let mut expected = DMatrix::from_element(4, 6, false);
+    expected[(0, 2)] = true;
+    expected[(2, 3)] = true;
+    assert_eq!(spy, expected);
+
+    // The grid must be non-empty
+    assert_panics!(csr.spy(0, 2));
+    assert_panics!(csr.spy(2, 0));
+}
+
+#[test]
+fn csr_generic_index_interop() {
+    // Construction from i32-based raw data, as produced by e.g. 32-bit FFI APIs
+    let csr = CsrMatrix::try_from_csr_data_generic_indices(
+        3,
+        4,
+        vec![0i32, 2, 2, 4],
+        vec![0i32, 2, 1, 3],
+        vec![1, 2, 3, 4],
+    )
+    .unwrap();
+    assert_eq!(csr.row_offsets(), &[0, 2, 2, 4]);
+    assert_eq!(csr.col_indices(), &[0, 2, 1, 3]);
+
+    // Negative indices are rejected before validation
+    let result = CsrMatrix::try_from_csr_data_generic_indices(
+        3,
+        4,
+        vec![0i32, -2, 2, 4],
+        vec![0i32, 2, 1, 3],
+        vec![1, 2, 3, 4],
+    );
+    assert_eq!(
+        result.unwrap_err().kind(),
+        &SparseFormatErrorKind::IndexOutOfBounds
+    );
+
+    // Round-trip through u32 index arrays
+    let (offsets, indices, values) = csr.csr_data_with_index_type::<u32>().unwrap();
+    assert_eq!(offsets, vec![0u32, 2, 2, 4]);
+    assert_eq!(indices, vec![0u32, 2, 1, 3]);
+    assert_eq!(values, csr.values());
+
+    // Conversion fails if the index type cannot represent the data
+    let wide = CsrMatrix::<i32>::zeros(300, 300);
+    assert!(wide.csr_data_with_index_type::<u8>().is_some());
+    let dense = DMatrix::from_element(1, 300, 1);
+    let wide = CsrMatrix::from(&dense);
+    assert!(wide.csr_data_with_index_type::<u8>().is_none());
+}
+
+#[test]
+fn csr_graph_laplacian() {
+    // Undirected graph on 4 nodes with edges (0, 1), (0, 2) and (1, 2); node 3 is isolated
+    #[rustfmt::skip]
+    let w_dense = DMatrix::from_row_slice(4, 4, &[
+        0.0, 2.0, 1.0, 0.0,
+        2.0, 0.0, 3.0, 0.0,
+        1.0, 3.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0,
+    ]);
+    let w = CsrMatrix::from(&w_dense);
+    let degrees = [3.0f64, 5.0, 4.0, 0.0];
+
+    let laplacian = w.graph_laplacian(false);
+    #[rustfmt::skip]
+    let expected = DMatrix::from_row_slice(4, 4, &[
+         3.0, -2.0, -1.0,  0.0,
+        -2.0,  5.0, -3.0,  0.0,
+        -1.0, -3.0,  4.0,  0.0,
+         0.0,  0.0,  0.0,  0.0,
+    ]);
+    assert_matrix_eq!(laplacian, expected);
+    // The diagonal is explicitly stored even for the isolated node
+    assert_eq!(laplacian.nnz(), w.nnz() + 4);
+
+    let normalized = w.graph_laplacian(true);
+    let expected_normalized = DMatrix::from_fn(4, 4, |i, j| {
+        let scale = if degrees[i] == 0.0 || degrees[j] == 0.0 {
+            0.0
+        } else {
+            1.0 / (degrees[i] * degrees[j]).sqrt()
+        };
+        let identity = if i == j && degrees[i] != 0.0 { 1.0 } else { 0.0 };
+        identity - scale * w_dense[(i, j)]
+    });
+    assert_matrix_eq!(normalized, expected_normalized, comp = abs, tol = 1e-14);
+}
+
+#[test]
+fn csr_mul_diagonal_left() {
+    #[rustfmt::skip]
+    let a_dense = DMatrix::from_row_slice(3, 4, &[
+        1, 0, 2, 0,
+        0, 3, 0, 0,
+        4, 0, 0, 5,
+    ]);
+    let a = CsrMatrix::from(&a_dense);
+
+    // Diagonal scaling matrix with no explicit entry for row 1, which scales it by zero
+    let d = CsrMatrix::try_from_csr_data(3, 3, vec![0, 1, 1, 2], vec![0, 2], vec![2, 3]).unwrap();
+
+    let scaled = a.mul_diagonal_left(&d);
+    assert_eq!(scaled.pattern(), a.pattern());
+    #[rustfmt::skip]
+    let expected = DMatrix::from_row_slice(3, 4, &[
+        2, 0, 4, 0,
+        0, 0, 0, 0,
+        12, 0, 0, 15,
+    ]);
+    assert_matrix_eq!(scaled, expected);
+
+    // A scaling matrix with off-diagonal entries is rejected
+    let not_diagonal =
+        CsrMatrix::try_from_csr_data(3, 3, vec![0, 2, 2, 2], vec![0, 1], vec![1, 1]).unwrap();
+    assert_panics!(a.mul_diagonal_left(&not_diagonal));
+    // As are incompatible dimensions
+    let wrong_dim = CsrMatrix::identity(4);
+    assert_panics!(a.mul_diagonal_left(&wrong_dim));
+}
+
+proptest! {
+    #[test]
+    fn csr_gram_agrees_with_transpose_multiply(csr in csr_strategy()) {
+        let gram = csr.gram(false);
+        let expected = csr.transpose() * &csr;
+        let gram_dense = DMatrix::from(&gram);
+        let expected_dense = DMatrix::from(&expected);
+        prop_assert_eq!(&gram_dense, &expected_dense);
+
+        // The upper triangular variant stores exactly the entries on or above the diagonal
+        let upper = csr.gram(true);
+        prop_assert!(upper.triplet_iter().all(|(i, j, _)| j >= i));
+        let upper_dense = DMatrix::from(&upper);
+        for i in 0..upper_dense.nrows() {
+            for j in i..upper_dense.ncols() {
+                prop_assert_eq!(upper_dense[(i, j)], expected_dense[(i, j)]);
+            }
+        }
+    }
+}
+
+#[test]
+fn csr_normalize_rows() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(4, 3, &[
+        1.0,  0.0, 3.0,
+        0.0,  0.0, 0.0,
+        2.0, -2.0, 0.0,
+        0.0,  5.0, 0.0,
+    ]);
+
+    // Row 2 sums to zero and is left unchanged under the LeaveUnchanged policy
+    let mut unchanged = CsrMatrix::from(&dense);
+    unchanged.normalize_rows(ZeroRowPolicy::LeaveUnchanged);
+    #[rustfmt::skip]
+    let expected = DMatrix::from_row_slice(4, 3, &[
+        0.25,  0.0, 0.75,
+        0.0,   0.0, 0.0,
+        2.0,  -2.0, 0.0,
+        0.0,   1.0, 0.0,
+    ]);
+    assert_matrix_eq!(unchanged, expected, comp = abs, tol = 1e-14);
+
+    // Under the Uniform policy the stored entries of row 2 are set to 1 / nnz; the empty
+    // row 1 remains empty
+    let mut uniform = CsrMatrix::from(&dense);
+    uniform.normalize_rows(ZeroRowPolicy::Uniform);
+    #[rustfmt::skip]
+    let expected = DMatrix::from_row_slice(4, 3, &[
+        0.25, 0.0, 0.75,
+        0.0,  0.0, 0.0,
+        0.5,  0.5, 0.0,
+        0.0,  1.0, 0.0,
+    ]);
+    assert_matrix_eq!(uniform, expected, comp = abs, tol = 1e-14);
+}
+
+#[test]
+fn csr_try_from_shared_pattern_and_values() {
+    let pattern = Arc::new(
+        SparsityPattern::try_from_offsets_and_indices(2, 3, vec![0, 1, 3], vec![1, 0, 2]).unwrap(),
+    );
+
+    // The same shared pattern can be paired with several value arrays
+    let a = CsrMatrix::try_from_shared_pattern_and_values(Arc::clone(&pattern), vec![1, 2, 3])
+        .unwrap();
+    let b = CsrMatrix::try_from_shared_pattern_and_values(Arc::clone(&pattern), vec![4, 5, 6])
+        .unwrap();
+    assert_eq!(a.pattern(), b.pattern());
+    assert_eq!(a.values(), &[1, 2, 3]);
+    assert_eq!(b.values(), &[4, 5, 6]);
+
+    // A mismatch between the number of values and the pattern nnz is rejected
+    let result =
+        CsrMatrix::try_from_shared_pattern_and_values(Arc::clone(&pattern), vec![1, 2]);
+    assert_eq!(
+        result.unwrap_err().kind(),
+        &SparseFormatErrorKind::InvalidStructure
+    );
+
+    // A uniquely owned pattern is moved out of the Arc rather than cloned
+    let unique = CsrMatrix::try_from_shared_pattern_and_values(pattern, vec![7, 8, 9]).unwrap();
+    assert_eq!(unique.values(), &[7, 8, 9]);
+}
+
+#[test]
+fn csr_recip() {
+    let csr =
+        CsrMatrix::try_from_csr_data(2, 3, vec![0, 2, 3], vec![0, 2, 1], vec![2.0, 4.0, 0.5])
+            .unwrap();
+    let recip = csr.recip();
+    assert_eq!(recip.pattern(), csr.pattern());
+    assert_eq!(recip.values(), &[0.5, 0.25, 2.0]);
+
+    // For a diagonal matrix, recip yields the inverse
+    let d = CsrMatrix::try_from_csr_data(2, 2, vec![0, 1, 2], vec![0, 1], vec![2.0, 4.0]).unwrap();
+    let prod = d.recip() * &d;
+    assert_matrix_eq!(prod, DMatrix::identity(2, 2), comp = abs, tol = 1e-14);
+
+    // An explicitly stored zero produces an infinite value for floats
+    let with_zero =
+        CsrMatrix::try_from_csr_data(1, 1, vec![0, 1], vec![0], vec![0.0f64]).unwrap();
+    assert!(with_zero.recip().values()[0].is_infinite());
+}
+
+proptest! {
+    #[test]
+    fn csr_row_chunks_by_nnz_covers_all_rows(
+        csr in csr_strategy(),
+        num_chunks in 1..10usize)
+    {
+        let chunks = csr.row_chunks_by_nnz(num_chunks);
+        prop_assert_eq!(chunks.len(), num_chunks);
+
+        // The chunks are contiguous, in order, and cover all rows exactly once
+        let mut next_row = 0;
+        for chunk in &chunks {
+            prop_assert_eq!(chunk.start, next_row);
+            next_row = chunk.end;
+        }
+        prop_assert_eq!(next_row, csr.nrows());
+
+        // No chunk exceeds the ideal chunk size by more than the largest single row,
+        // since rows are indivisible
+        let max_row_nnz = csr.row_iter().map(|row| row.nnz()).max().unwrap_or(0);
+        let ideal = csr.nnz() / num_chunks;
+        for chunk in &chunks {
+            let chunk_nnz: usize = chunk.clone().map(|i| csr.row(i).nnz()).sum();
+            prop_assert!(chunk_nnz <= ideal + max_row_nnz);
+        }
+    }
+}
+
+#[test]
+fn csr_bandwidth() {
+    // No stored entries
+    let empty = CsrMatrix::<i32>::zeros(3, 3);
+    assert_eq!(empty.bandwidth(), (0, 0));
+    assert_eq!(empty.total_bandwidth(), 1);
+
+    // Purely diagonal
+    let identity = CsrMatrix::<i32>::identity(4);
+    assert_eq!(identity.bandwidth(), (0, 0));
+    assert_eq!(identity.total_bandwidth(), 1);
+
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(4, 4, &[
+        1, 1, 0, 0,
+        0, 1, 0, 0,
+        1, 0, 1, 1,
+        0, 0, 1, 1,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+    assert_eq!(csr.bandwidth(), (2, 1));
+    assert_eq!(csr.total_bandwidth(), 4);
+
+    // Rectangular matrix with only super-diagonal entries
+    let upper = CsrMatrix::try_from_csr_data(2, 4, vec![0, 1, 2], vec![3, 2], vec![1, 1]).unwrap();
+    assert_eq!(upper.bandwidth(), (0, 3));
+}
+
+#[test]
+fn csr_from_fn() {
+    // A 1D Laplace stencil, specified with unsorted and duplicated coordinates
+    let mut coords = Vec::new();
+    for i in 0..4 {
+        coords.push((i, i));
+        if i > 0 {
+            coords.push((i, i - 1));
+        }
+        if i + 1 < 4 {
+            coords.push((i, i + 1));
+        }
+    }
+    coords.reverse();
+    coords.push((2, 2));
+    let csr = CsrMatrix::from_fn(4, 4, &coords, |i, j| if i == j { 2 } else { -1 });
+
+    #[rustfmt::skip]
+    let expected = DMatrix::from_row_slice(4, 4, &[
+         2, -1,  0,  0,
+        -1,  2, -1,  0,
+         0, -1,  2, -1,
+         0,  0, -1,  2,
+    ]);
+    assert_matrix_eq!(csr, expected);
+    assert_eq!(csr.nnz(), 10);
+
+    // Out-of-bounds coordinates are rejected
+    assert_panics!(CsrMatrix::from_fn(2, 2, &[(0, 2)], |_, _| 1));
+}
+
+#[test]
+fn csr_row_argmax() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(4, 3, &[
+        -3.0, -1.0, -2.0,
+         0.0,  0.0,  0.0,
+         5.0,  2.0,  5.0,
+         0.0,  4.0,  0.0,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+
+    // Row 1 stores no entries; in row 0 the largest *stored* value is -1 even though the
+    // implicit zeros would be larger; ties in row 2 resolve to the lowest column index
+    assert_eq!(
+        csr.row_argmax(),
+        vec![Some(1), None, Some(0), Some(1)]
+    );
+    assert_eq!(
+        csr.row_max_values(),
+        vec![Some(-1.0), None, Some(5.0), Some(4.0)]
+    );
+
+    // NaN values are skipped
+    let with_nan =
+        CsrMatrix::try_from_csr_data(1, 2, vec![0, 2], vec![0, 1], vec![f64::NAN, 1.0]).unwrap();
+    assert_eq!(with_nan.row_argmax(), vec![Some(1)]);
+}
+
+#[test]
+fn csr_diagonal_scaled_norm() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(3, 3, &[
+        4.0, 1.0, 0.0,
+        1.0, 9.0, 2.0,
+        0.0, 2.0, 16.0,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+    let r = DVector::from_column_slice(&[2.0, 3.0, 4.0]);
+
+    // ||D^{-1/2} r||^2 = 4/4 + 9/9 + 16/16 = 3
+    let norm = csr.diagonal_scaled_norm(&r);
+    assert!((norm - 3.0f64.sqrt()).abs() < 1e-14);
+
+    // Rows with a missing or zero diagonal are skipped
+    #[rustfmt::skip]
+    let partial = DMatrix::from_row_slice(3, 3, &[
+        4.0, 1.0, 0.0,
+        1.0, 0.0, 2.0,
+        0.0, 2.0, 0.0,
+    ]);
+    let csr = CsrMatrix::from(&partial);
+    assert!((csr.diagonal_scaled_norm(&r) - 1.0).abs() < 1e-14);
+
+    assert_panics!(CsrMatrix::<f64>::zeros(2, 3).diagonal_scaled_norm(&DVector::zeros(2)));
+    assert_panics!(CsrMatrix::<f64>::zeros(3, 3).diagonal_scaled_norm(&DVector::zeros(2)));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn csr_random_spd() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let a = CsrMatrix::random_spd(20, 0.2, &mut rng);
+    assert_eq!(a.nrows(), 20);
+    assert_eq!(a.ncols(), 20);
+
+    // Symmetry and positive definiteness (via Cholesky)
+    let dense = DMatrix::from(&a);
+    assert_eq!(dense, dense.transpose());
+    assert!(dense.clone().cholesky().is_some());
+
+    // All diagonal entries are explicitly stored and positive
+    for i in 0..20 {
+        assert!(a.get_entry(i, i).unwrap().into_value() >= 1.0);
+    }
+
+    // Same seed yields the same matrix
+    let mut rng = StdRng::seed_from_u64(42);
+    let b = CsrMatrix::random_spd(20, 0.2, &mut rng);
+    assert_eq!(a, b);
+
+    assert_panics!(CsrMatrix::random_spd(5, 1.5, &mut StdRng::seed_from_u64(0)));
+}
+
+#[test]
+fn csr_checked_mul() {
+    let a = CsrMatrix::from(&DMatrix::from_row_slice(2, 3, &[1, 0, 2, 0, 3, 0]));
+    let b = CsrMatrix::from(&DMatrix::from_row_slice(3, 2, &[1, 2, 0, 1, 1, 0]));
+
+    let product = a.checked_mul(&b).unwrap();
+    assert_eq!(product, &a * &b);
+
+    let err = b.checked_mul(&b).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        OperationErrorKind::IncompatibleDimensions {
+            a_cols: 2,
+            b_rows: 3
+        }
+    ));
+}
+
+#[test]
+fn csr_row_nnz_histogram() {
+    // Rows with 2, 0, 3 and 3 entries; max_nnz = 3
+    let csr = CsrMatrix::try_from_csr_data(
+        4,
+        4,
+        vec![0, 2, 2, 5, 8],
+        vec![0, 1, 0, 1, 2, 1, 2, 3],
+        vec![1; 8],
+    )
+    .unwrap();
+
+    // Two bins over [0, 3]: nnz in {0, 1} and nnz in {2, 3}
+    assert_eq!(csr.row_nnz_histogram(2), vec![1, 3]);
+    // One bin per possible count
+    assert_eq!(csr.row_nnz_histogram(4), vec![1, 0, 1, 2]);
+
+    // An empty matrix puts every row in the first bucket
+    assert_eq!(CsrMatrix::<i32>::zeros(3, 3).row_nnz_histogram(2), vec![3, 0]);
+
+    assert_panics!(csr.row_nnz_histogram(0));
+}
+
+#[test]
+fn csr_set_diagonal_and_add_to_diagonal() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(3, 3, &[
+        1, 2, 0,
+        0, 3, 4,
+        5, 0, 6,
+    ]);
+    let mut csr = CsrMatrix::from(&dense);
+
+    csr.set_diagonal(&DVector::from_column_slice(&[7, 8, 9])).unwrap();
+    #[rustfmt::skip]
+    let expected = DMatrix::from_row_slice(3, 3, &[
+        7, 2, 0,
+        0, 8, 4,
+        5, 0, 9,
+    ]);
+    assert_eq!(DMatrix::from(&csr), expected);
+
+    csr.add_to_diagonal(&DVector::from_column_slice(&[1, 1, 1]));
+    assert_eq!(
+        DMatrix::from(&csr),
+        expected + DMatrix::identity(3, 3)
+    );
+
+    // A structurally absent diagonal entry leaves the matrix unmodified
+    #[rustfmt::skip]
+    let partial_dense = DMatrix::from_row_slice(2, 2, &[
+        1, 2,
+        3, 0,
+    ]);
+    let mut partial = CsrMatrix::from(&partial_dense);
+    let err = partial
+        .set_diagonal(&DVector::from_column_slice(&[5, 5]))
+        .unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::InvalidStructure);
+    assert_eq!(DMatrix::from(&partial), partial_dense);
+
+    assert_panics!({
+        let mut partial = CsrMatrix::from(&partial_dense);
+        partial.add_to_diagonal(&DVector::from_column_slice(&[5, 5]))
+    });
+    assert_panics!({
+        let mut csr = CsrMatrix::<i32>::identity(3);
+        csr.set_diagonal(&DVector::from_column_slice(&[1, 2])).unwrap()
+    });
+}
+
+#[test]
+fn csr_rows_view() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(4, 3, &[
+        1, 0, 2,
+        0, 3, 0,
+        4, 5, 0,
+        0, 0, 6,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+
+    let view = csr.rows(1..3);
+    assert_eq!(view.nrows(), 2);
+    assert_eq!(view.ncols(), 3);
+    assert_eq!(view.nnz(), 3);
+
+    let rows: Vec<_> = view.row_iter().collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].col_indices(), &[1]);
+    assert_eq!(rows[0].values(), &[3]);
+    assert_eq!(rows[1].col_indices(), &[0, 1]);
+    assert_eq!(rows[1].values(), &[4, 5]);
+
+    let x = DVector::from_column_slice(&[1, 2, 3]);
+    assert_eq!(view.spmv(&x), DVector::from_column_slice(&[6, 14]));
+
+    // An empty view is allowed
+    assert_eq!(csr.rows(2..2).nnz(), 0);
+
+    assert_panics!(csr.rows(2..5));
+    assert_panics!(view.spmv(&DVector::from_column_slice(&[1, 2])));
+}
+
+#[test]
+fn csr_empty_rows() {
+    // Row 1 is structurally empty; row 2 stores only explicit zeros
+    let csr =
+        CsrMatrix::try_from_csr_data(4, 3, vec![0, 2, 2, 4, 5], vec![0, 1, 0, 2, 1], vec![
+            1.0, 2.0, 0.0, 1e-12, 3.0,
+        ])
+        .unwrap();
+
+    assert_eq!(csr.empty_rows(), vec![1]);
+    assert!(csr.has_empty_row());
+
+    // Numerical emptiness also reports rows of explicit (near-)zeros
+    assert_eq!(csr.numerically_zero_rows(1e-10), vec![1, 2]);
+    assert_eq!(csr.numerically_zero_rows(0.0), vec![1]);
+
+    let full = CsrMatrix::from(&DMatrix::from_element(2, 2, 1.0));
+    assert_eq!(full.empty_rows(), Vec::<usize>::new());
+    assert!(!full.has_empty_row());
+}
+
+#[test]
+fn csr_cast() {
+    let csr = CsrMatrix::try_from_csr_data(2, 3, vec![0, 2, 3], vec![0, 2, 1], vec![
+        1.000000123456789f64,
+        -2.5,
+        1e30,
+    ])
+    .unwrap();
+
+    // The pattern is preserved exactly, only the values are converted
+    let single = csr.to_f32();
+    assert_eq!(single.pattern(), csr.pattern());
+
+    // Round-trip through f32 loses at most the relative f32 rounding error
+    let round_trip = single.to_f64();
+    assert_eq!(round_trip.pattern(), csr.pattern());
+    for (v, w) in csr.values().iter().zip(round_trip.values()) {
+        assert!(((v - w) / v).abs() <= f32::EPSILON as f64);
+    }
+
+    // Integer casts work as well
+    let ints = CsrMatrix::try_from_csr_data(1, 2, vec![0, 2], vec![0, 1], vec![3i32, -4]).unwrap();
+    assert_eq!(ints.cast::<i64>().values(), &[3i64, -4]);
+
+    // Values that cannot be represented in the target type panic
+    assert_panics!(ints.cast::<u32>());
+}
+
+#[test]
+fn csr_diagonal() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(3, 4, &[
+        1, 2, 0, 0,
+        0, 3, 4, 0,
+        5, 0, 6, 7,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+
+    assert_eq!(csr.diagonal(0), DVector::from_column_slice(&[1, 3, 6]));
+    assert_eq!(csr.diagonal(1), DVector::from_column_slice(&[2, 4, 7]));
+    assert_eq!(csr.diagonal(2), DVector::from_column_slice(&[0, 0]));
+    assert_eq!(csr.diagonal(3), DVector::from_column_slice(&[0]));
+    assert_eq!(csr.diagonal(-1), DVector::from_column_slice(&[0, 0]));
+    assert_eq!(csr.diagonal(-2), DVector::from_column_slice(&[5]));
+
+    assert_panics!(csr.diagonal(4));
+    assert_panics!(csr.diagonal(-3));
+}
+
+#[test]
+fn csr_insert_entry() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(3, 3, &[
+        1, 0, 2,
+        0, 0, 0,
+        3, 0, 4,
+    ]);
+    let mut csr = CsrMatrix::from(&dense);
+
+    // Overwriting an existing entry does not change the pattern
+    csr.insert_entry(0, 2, 9).unwrap();
+    assert_eq!(csr.nnz(), 4);
+    assert_eq!(csr.index_entry(0, 2), SparseEntry::NonZero(&9));
+
+    // Inserting new entries grows the pattern and keeps columns sorted
+    csr.insert_entry(1, 1, 5).unwrap();
+    csr.insert_entry(0, 1, 6).unwrap();
+    csr.insert_entry(2, 0, 7).unwrap();
+    assert_eq!(csr.nnz(), 6);
+    csr.check_invariants().unwrap();
+
+    #[rustfmt::skip]
+    let expected = DMatrix::from_row_slice(3, 3, &[
+        1, 6, 9,
+        0, 5, 0,
+        7, 0, 4,
+    ]);
+    assert_eq!(DMatrix::from(&csr), expected);
+
+    assert_eq!(
+        csr.insert_entry(3, 0, 1).unwrap_err().kind(),
+        &SparseFormatErrorKind::IndexOutOfBounds
+    );
+    assert_eq!(
+        csr.insert_entry(0, 3, 1).unwrap_err().kind(),
+        &SparseFormatErrorKind::IndexOutOfBounds
+    );
+}
+
+#[test]
+fn csr_density_and_memory_bytes() {
+    let mut csr =
+        CsrMatrix::try_from_csr_data(2, 4, vec![0, 2, 4], vec![0, 1, 1, 3], vec![1, 2, 3, 4])
+            .unwrap();
+    assert_eq!(csr.density(), 0.5);
+    assert_eq!(CsrMatrix::<i32>::zeros(0, 3).density(), 0.0);
+
+    // After shrinking, the footprint reflects exactly the lengths of the arrays
+    csr.shrink_to_fit();
+    let usize_size = std::mem::size_of::<usize>();
+    let expected = 3 * usize_size + 4 * usize_size + 4 * std::mem::size_of::<i32>();
+    assert_eq!(csr.memory_bytes(), expected);
+
+    // Inserting an entry may over-allocate; memory_bytes reports capacity, not length
+    csr.insert_entry(0, 2, 5).unwrap();
+    assert!(csr.memory_bytes() >= 3 * usize_size + 5 * usize_size + 5 * std::mem::size_of::<i32>());
+}
+
+#[test]
+fn csr_fold_entries() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(2, 3, &[
+        1, 0, -2,
+        0, 3, 0,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+
+    let sum_of_squares = csr.fold_entries(0, |acc, _, _, v| acc + v * v);
+    assert_eq!(sum_of_squares, 14);
+
+    let count_above_two = csr.fold_entries(0, |acc, _, _, v| acc + usize::from(*v > 2));
+    assert_eq!(count_above_two, 1);
+
+    // Entries are visited in row-major order
+    let order = csr.fold_entries(Vec::new(), |mut acc, i, j, _| {
+        acc.push((i, j));
+        acc
+    });
+    assert_eq!(order, vec![(0, 0), (0, 2), (1, 1)]);
+}
+
+#[test]
+fn csr_compress() {
+    let a = CsrMatrix::try_from_csr_data(3, 3, vec![0, 2, 3, 5], vec![0, 2, 1, 0, 2], vec![
+        1, 0, 2, 0, 3,
+    ])
+    .unwrap();
+
+    let compressed = a.compress();
+    assert_eq!(compressed.nrows(), 3);
+    assert_eq!(compressed.ncols(), 3);
+    assert_eq!(compressed.row_offsets(), &[0, 1, 2, 3]);
+    assert_eq!(compressed.col_indices(), &[0, 1, 2]);
+    assert_eq!(compressed.values(), &[1, 2, 3]);
+
+    // Subtracting a matrix from itself cancels every entry; compressing yields
+    // an empty pattern
+    let diff = &a - &a;
+    assert_eq!(diff.nnz(), a.nnz());
+    let mut diff = diff;
+    diff.compress_in_place();
+    assert_eq!(diff.nnz(), 0);
+    assert_eq!(diff.row_offsets(), &[0, 0, 0, 0]);
+    assert_eq!(DMatrix::from(&diff), DMatrix::zeros(3, 3));
+}
+
+#[test]
+fn csr_can_add_and_can_mul() {
+    let a = CsrMatrix::<i32>::zeros(2, 3);
+    let b = CsrMatrix::<i32>::zeros(3, 4);
+
+    assert!(a.can_add(&a));
+    assert!(!a.can_add(&b));
+
+    assert!(a.can_mul(&b));
+    assert!(!b.can_mul(&a));
+    assert!(!a.can_mul(&a));
+
+    // Transpose-aware variant follows the spmm dimension logic
+    assert!(a.can_mul_transposed(false, &b, false));
+    assert!(!a.can_mul_transposed(true, &b, false));
+    assert!(a.can_mul_transposed(true, &a, false));
+    assert!(a.can_mul_transposed(false, &a, true));
+    assert!(!a.can_mul_transposed(true, &a, true));
+}
+
+#[test]
+fn csr_get_value() {
+    let a = CsrMatrix::try_from_csr_data(2, 3, vec![0, 2, 3], vec![0, 2, 1], vec![1, 2, 3])
+        .unwrap();
+
+    assert_eq!(a.get_value(0, 0), 1);
+    assert_eq!(a.get_value(0, 2), 2);
+    assert_eq!(a.get_value(1, 1), 3);
+    // Implicit zeros are returned by value
+    assert_eq!(a.get_value(0, 1), 0);
+    assert_eq!(a.get_value(1, 0), 0);
+
+    assert_panics!(a.get_value(2, 0));
+    assert_panics!(a.get_value(0, 3));
+}
+
+#[test]
+fn csr_max_and_min_abs_entry() {
+    let a = CsrMatrix::try_from_csr_data(2, 3, vec![0, 2, 4], vec![0, 2, 0, 1], vec![
+        -4.0, 1.0, 2.0, 4.0,
+    ])
+    .unwrap();
+
+    // Magnitudes are compared; ties resolve to the first entry in row-major order
+    assert_eq!(a.max_abs_entry(), Some((0, 0, 4.0)));
+    assert_eq!(a.min_abs_entry(), Some((0, 2, 1.0)));
+
+    let empty = CsrMatrix::<f64>::zeros(3, 3);
+    assert_eq!(empty.max_abs_entry(), None);
+    assert_eq!(empty.min_abs_entry(), None);
+}
+
+#[test]
+fn csr_try_invert_diagonal() {
+    let d = CsrMatrix::try_from_csr_data(3, 3, vec![0, 1, 2, 3], vec![0, 1, 2], vec![
+        2.0, 4.0, 0.5,
+    ])
+    .unwrap();
+    let inv = d.try_invert_diagonal().unwrap();
+    assert_eq!(inv.pattern(), d.pattern());
+    assert_eq!(inv.values(), &[0.5, 0.25, 2.0]);
+    assert_eq!(DMatrix::from(&(&d * &inv)), DMatrix::identity(3, 3));
+
+    // Not square
+    assert!(CsrMatrix::<f64>::zeros(2, 3).try_invert_diagonal().is_none());
+    // Off-diagonal entry
+    let off = CsrMatrix::try_from_csr_data(2, 2, vec![0, 1, 2], vec![1, 1], vec![1.0, 1.0])
+        .unwrap();
+    assert!(off.try_invert_diagonal().is_none());
+    // Missing diagonal entry
+    let missing =
+        CsrMatrix::try_from_csr_data(2, 2, vec![0, 1, 1], vec![0], vec![1.0]).unwrap();
+    assert!(missing.try_invert_diagonal().is_none());
+    // Explicit zero on the diagonal
+    let singular =
+        CsrMatrix::try_from_csr_data(2, 2, vec![0, 1, 2], vec![0, 1], vec![1.0, 0.0]).unwrap();
+    assert!(singular.try_invert_diagonal().is_none());
+}
+
+#[test]
+fn csr_row_iter_is_double_ended_and_exact_size() {
+    let a = CsrMatrix::try_from_csr_data(3, 4, vec![0, 2, 2, 5], vec![0, 3, 1, 2, 3], vec![
+        1, 2, 3, 4, 5,
+    ])
+    .unwrap();
+
+    assert_eq!(a.row_iter().len(), 3);
+
+    // Reverse iteration visits rows from last to first with correct contents
+    let reversed: Vec<_> = a
+        .row_iter()
+        .rev()
+        .map(|row| (row.col_indices().to_vec(), row.values().to_vec()))
+        .collect();
+    assert_eq!(reversed, vec![
+        (vec![1, 2, 3], vec![3, 4, 5]),
+        (vec![], vec![]),
+        (vec![0, 3], vec![1, 2]),
+    ]);
+
+    // Mixed front/back iteration stays consistent
+    let mut iter = a.row_iter();
+    assert_eq!(iter.next().unwrap().values(), &[1, 2]);
+    assert_eq!(iter.next_back().unwrap().values(), &[3, 4, 5]);
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next().unwrap().nnz(), 0);
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+
+    // The mutable iterator can also be reversed, e.g. for back substitution
+    let mut a = a;
+    for mut row in a.row_iter_mut().rev() {
+        for v in row.values_mut() {
+            *v *= 10;
+        }
+    }
+    assert_eq!(a.values(), &[10, 20, 30, 40, 50]);
+}
+
+#[test]
+fn csr_split_columns() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(3, 4, &[
+        1, 2, 0, 3,
+        0, 4, 5, 0,
+        6, 0, 0, 7,
+    ]);
+    let a = CsrMatrix::from(&dense);
+
+    let (selected, unselected) = a.split_columns(&[true, false, false, true]);
+    assert_eq!(selected.nrows(), 3);
+    assert_eq!(unselected.nrows(), 3);
+    assert_eq!(selected.ncols(), 2);
+    assert_eq!(unselected.ncols(), 2);
+
+    assert_eq!(DMatrix::from(&selected), dense.select_columns(&[0, 3]));
+    assert_eq!(DMatrix::from(&unselected), dense.select_columns(&[1, 2]));
+
+    // Degenerate masks put everything into one side
+    let (all, none) = a.split_columns(&[true; 4]);
+    assert_eq!(all, a);
+    assert_eq!(none, CsrMatrix::zeros(3, 0));
+
+    assert_panics!(a.split_columns(&[true, false]));
+}
+
+#[test]
+fn csr_has_full_diagonal_and_ensure_diagonal() {
+    // Missing diagonal entries at (1, 1) and (2, 2)
+    let a = CsrMatrix::try_from_csr_data(
+        3,
+        3,
+        vec![0, 2, 3, 4],
+        vec![0, 2, 0, 1],
+        vec![1, 2, 3, 4],
+    )
+    .unwrap();
+    assert!(!a.has_full_diagonal());
+
+    let b = a.ensure_diagonal();
+    assert!(b.has_full_diagonal());
+    // Values are unchanged, missing diagonal entries are explicit zeros
+    assert_eq!(DMatrix::from(&b), DMatrix::from(&a));
+    assert_eq!(b.nnz(), a.nnz() + 2);
+    assert_eq!(b.get_entry(1, 1).unwrap().into_value(), 0);
+    assert_eq!(b.get_entry(2, 2).unwrap().into_value(), 0);
+
+    // A matrix with a full diagonal is returned unchanged
+    assert!(b.ensure_diagonal() == b);
+
+    // An explicitly stored zero counts as present
+    let c = CsrMatrix::try_from_csr_data(1, 1, vec![0, 1], vec![0], vec![0]).unwrap();
+    assert!(c.has_full_diagonal());
+
+    // Rectangular matrices only consider diagonal positions up to min(nrows, ncols)
+    let rect = CsrMatrix::try_from_csr_data(2, 3, vec![0, 1, 2], vec![0, 1], vec![1, 2]).unwrap();
+    assert!(rect.has_full_diagonal());
+    let rect_missing =
+        CsrMatrix::try_from_csr_data(2, 3, vec![0, 1, 2], vec![0, 2], vec![1, 2]).unwrap();
+    assert!(!rect_missing.has_full_diagonal());
+    assert!(rect_missing.ensure_diagonal().has_full_diagonal());
+
+    // ensure_diagonal makes set_diagonal applicable
+    let mut b = b;
+    assert!(b.set_diagonal(&nalgebra::dvector![7, 8, 9]).is_ok());
+    assert_eq!(b.get_entry(2, 2).unwrap().into_value(), 9);
+}
+
+#[test]
+fn csr_from_stencil_1d() {
+    // The classic 1D Laplacian stencil produces the tridiagonal matrix directly
+    let laplacian = CsrMatrix::from_stencil_1d(4, &[-1, 0, 1], &[-1, 2, -1]);
+    let expected = DMatrix::from_row_slice(4, 4, &[
+        2, -1, 0, 0,
+        -1, 2, -1, 0,
+        0, -1, 2, -1,
+        0, 0, -1, 2,
+    ]);
+    assert_eq!(DMatrix::from(&laplacian), expected);
+    // Boundary rows are truncated, interior rows hold the full stencil
+    assert_eq!(laplacian.row(0).nnz(), 2);
+    assert_eq!(laplacian.row(1).nnz(), 3);
+    assert_eq!(laplacian.row(3).nnz(), 2);
+
+    // Unsorted offsets are permitted
+    let unsorted = CsrMatrix::from_stencil_1d(4, &[1, -1, 0], &[-1, -1, 2]);
+    assert_eq!(unsorted, laplacian);
+
+    // A wide upwind-style stencil with offsets beyond the immediate neighbors
+    let upwind = CsrMatrix::from_stencil_1d(5, &[-2, 0], &[1.0, -1.0]);
+    assert_eq!(upwind.row(0).nnz(), 1);
+    assert_eq!(upwind.row(1).nnz(), 1);
+    assert_eq!(upwind.row(2).nnz(), 2);
+    assert_eq!(upwind.get_entry(2, 0).unwrap().into_value(), 1.0);
+
+    // Degenerate cases
+    let empty = CsrMatrix::<f64>::from_stencil_1d(0, &[-1, 0, 1], &[1.0, 1.0, 1.0]);
+    assert_eq!(empty.nrows(), 0);
+    assert_eq!(empty.nnz(), 0);
+
+    assert_panics!(CsrMatrix::from_stencil_1d(3, &[-1, 0], &[1.0]));
+    assert_panics!(CsrMatrix::from_stencil_1d(3, &[0, 0], &[1.0, 2.0]));
+}
+
+#[test]
+fn csr_into_rows_and_from_rows_roundtrip() {
+    let a = CsrMatrix::try_from_csr_data(
+        3,
+        4,
+        vec![0, 2, 2, 5],
+        vec![0, 3, 1, 2, 3],
+        vec![1, 2, 3, 4, 5],
+    )
+    .unwrap();
+
+    let rows = a.clone().into_rows();
+    assert_eq!(rows.len(), 3);
+    assert!(rows.iter().all(|row| row.len() == 4));
+    assert_eq!(rows[0].indices(), &[0, 3]);
+    assert_eq!(rows[0].values(), &[1, 2]);
+    assert_eq!(rows[1].nnz(), 0);
+    assert_eq!(rows[2].indices(), &[1, 2, 3]);
+    assert_eq!(rows[2].values(), &[3, 4, 5]);
+
+    // Reassembling the unmodified rows reproduces the matrix
+    let reassembled = CsrMatrix::from_rows(rows.clone(), 4).unwrap();
+    assert_eq!(reassembled, a);
+
+    // Rows can be reordered and filtered as independent objects
+    let reordered = CsrMatrix::from_rows(vec![rows[2].clone(), rows[0].clone()], 4).unwrap();
+    assert_eq!(reordered.nrows(), 2);
+    assert_eq!(reordered.row(0).col_indices(), &[1, 2, 3]);
+    assert_eq!(reordered.row(1).col_indices(), &[0, 3]);
+
+    // A row of mismatched dimension is rejected
+    let bad_row = nalgebra_sparse::SparseVector::try_from_parts(3, vec![1], vec![1]).unwrap();
+    let result = CsrMatrix::from_rows(vec![bad_row], 4);
+    assert_eq!(
+        result.unwrap_err().kind(),
+        &SparseFormatErrorKind::InvalidStructure
+    );
+
+    // Empty input produces an empty matrix
+    let empty = CsrMatrix::<i32>::from_rows(Vec::new(), 4).unwrap();
+    assert_eq!(empty.nrows(), 0);
+    assert_eq!(empty.ncols(), 4);
+}
+
+#[test]
+fn sparse_vector_try_from_parts_validates_input() {
+    use nalgebra_sparse::SparseVector;
+
+    let v = SparseVector::try_from_parts(5, vec![0, 2, 4], vec![1, 2, 3]).unwrap();
+    assert_eq!(v.len(), 5);
+    assert_eq!(v.nnz(), 3);
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![(0, &1), (2, &2), (4, &3)]);
+
+    // Mismatched lengths
+    assert!(SparseVector::try_from_parts(5, vec![0, 1], vec![1]).is_err());
+    // Unsorted indices
+    assert!(SparseVector::try_from_parts(5, vec![2, 0], vec![1, 2]).is_err());
+    // Duplicate indices
+    assert!(SparseVector::try_from_parts(5, vec![1, 1], vec![1, 2]).is_err());
+    // Out of bounds
+    assert_eq!(
+        SparseVector::try_from_parts(5, vec![0, 5], vec![1, 2])
+            .unwrap_err()
+            .kind(),
+        &SparseFormatErrorKind::IndexOutOfBounds
+    );
+}
+
+#[test]
+fn csr_conjugate() {
+    use nalgebra::Complex;
+
+    let a = CsrMatrix::try_from_csr_data(
+        2,
+        2,
+        vec![0, 2, 3],
+        vec![0, 1, 1],
+        vec![
+            Complex::new(1.0, 2.0),
+            Complex::new(0.0, -3.0),
+            Complex::new(4.0, 0.0),
+        ],
+    )
+    .unwrap();
+
+    let conj = a.conjugate();
+    assert_eq!(conj.pattern(), a.pattern());
+    assert_eq!(
+        conj.values(),
+        &[
+            Complex::new(1.0, -2.0),
+            Complex::new(0.0, 3.0),
+            Complex::new(4.0, 0.0),
+        ]
+    );
+
+    // Conjugating twice round-trips
+    assert_eq!(conj.conjugate(), a);
+
+    // The in-place variant agrees with the allocating one
+    let mut b = a.clone();
+    b.conjugate_in_place();
+    assert_eq!(b, conj);
+
+    // For real scalars, conjugation is a no-op copy
+    let real = CsrMatrix::try_from_csr_data(2, 2, vec![0, 1, 2], vec![0, 1], vec![1.0, -2.0])
+        .unwrap();
+    assert_eq!(real.conjugate(), real);
+
+    // The conjugate transpose of a Hermitian matrix is the matrix itself
+    let hermitian = CsrMatrix::try_from_csr_data(
+        2,
+        2,
+        vec![0, 2, 4],
+        vec![0, 1, 0, 1],
+        vec![
+            Complex::new(2.0, 0.0),
+            Complex::new(1.0, 1.0),
+            Complex::new(1.0, -1.0),
+            Complex::new(3.0, 0.0),
+        ],
+    )
+    .unwrap();
+    assert_eq!(hermitian.transpose().conjugate(), hermitian);
+}
+
+#[test]
+fn csr_is_symmetric_within() {
+    #[rustfmt::skip]
+    let symmetric = CsrMatrix::try_from_csr_data(
+        3, 3,
+        vec![0, 2, 4, 5],
+        vec![0, 1, 0, 1, 2],
+        vec![2.0, 1.0, 1.0, 3.0, 4.0],
+    ).unwrap();
+    assert!(symmetric.is_symmetric_within(0.0, 0.0));
+
+    // Small perturbations pass with an absolute tolerance, but not without one
+    let mut perturbed = symmetric.clone();
+    if let SparseEntryMut::NonZero(v) = perturbed.index_entry_mut(1, 0) {
+        *v += 1e-12;
+    }
+    assert!(!perturbed.is_symmetric_within(0.0, 0.0));
+    assert!(perturbed.is_symmetric_within(1e-10, 0.0));
+
+    // A relative tolerance scales with the magnitude of the entries
+    #[rustfmt::skip]
+    let large = CsrMatrix::try_from_csr_data(
+        2, 2,
+        vec![0, 2, 4],
+        vec![0, 1, 0, 1],
+        vec![1.0, 1e10, 1e10 + 1.0, 1.0],
+    ).unwrap();
+    assert!(!large.is_symmetric_within(1e-8, 0.0));
+    assert!(large.is_symmetric_within(0.0, 1e-9));
+
+    // Structural asymmetry fails regardless of tolerance, even for zero values
+    #[rustfmt::skip]
+    let structural = CsrMatrix::try_from_csr_data(
+        2, 2,
+        vec![0, 2, 3],
+        vec![0, 1, 1],
+        vec![1.0, 0.0, 1.0],
+    ).unwrap();
+    assert!(!structural.is_symmetric_within(1e10, 1e10));
+
+    // Non-square matrices are rejected
+    let rect = CsrMatrix::<f64>::zeros(2, 3);
+    assert_panics!(rect.is_symmetric_within(0.0, 0.0));
+}
+
+#[test]
+fn csr_map_diagonal() {
+    #[rustfmt::skip]
+    let mut a = CsrMatrix::try_from_csr_data(
+        3, 3,
+        vec![0, 2, 4, 5],
+        vec![0, 2, 1, 2, 2],
+        vec![1.0, 2.0, -3.0, 4.0, 5.0],
+    ).unwrap();
+
+    // Tikhonov shift of the diagonal
+    a.map_diagonal(|d| d + 10.0).unwrap();
+    assert_eq!(a.values(), &[11.0, 2.0, 7.0, 4.0, 15.0]);
+
+    // Clamping the diagonal from below
+    a.map_diagonal(|d: f64| d.max(10.0)).unwrap();
+    assert_eq!(a.values(), &[11.0, 2.0, 10.0, 4.0, 15.0]);
+
+    // A missing diagonal entry is reported, and the matrix is left unmodified...
+    #[rustfmt::skip]
+    let mut missing = CsrMatrix::try_from_csr_data(
+        2, 2,
+        vec![0, 1, 2],
+        vec![0, 0],
+        vec![1.0, 2.0],
+    ).unwrap();
+    let err = missing.map_diagonal(|d| d + 1.0).unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::InvalidStructure);
+    assert_eq!(missing.values(), &[1.0, 2.0]);
+
+    // ... unless the diagonal is first made structurally present
+    let mut ensured = missing.ensure_diagonal();
+    ensured.map_diagonal(|d| d + 1.0).unwrap();
+    assert_eq!(ensured.get_entry(1, 1).unwrap().into_value(), 1.0);
+
+    // Non-square matrices are rejected
+    assert_panics!(CsrMatrix::<f64>::zeros(2, 3).map_diagonal(|d| d));
+}
+
+#[test]
+fn csr_gather_rows_dense() {
+    #[rustfmt::skip]
+    let a = CsrMatrix::try_from_csr_data(
+        3, 4,
+        vec![0, 2, 3, 5],
+        vec![0, 2, 3, 1, 2],
+        vec![1, 2, 3, 4, 5],
+    ).unwrap();
+
+    // Rows may be repeated and listed in any order
+    let tile = a.gather_rows_dense(&[2, 0, 2]);
+    #[rustfmt::skip]
+    let expected = DMatrix::from_row_slice(3, 4, &[
+        0, 4, 5, 0,
+        1, 0, 2, 0,
+        0, 4, 5, 0,
+    ]);
+    assert_eq!(tile, expected);
+
+    // An empty selection produces an empty matrix with the full number of columns
+    let empty = a.gather_rows_dense(&[]);
+    assert_eq!(empty.nrows(), 0);
+    assert_eq!(empty.ncols(), 4);
+
+    // Out-of-bounds row indices are rejected
+    assert_panics!(a.gather_rows_dense(&[0, 3]));
+}
+
+#[test]
+fn csr_estimate_spectral_condition() {
+    // For a diagonal matrix the condition number is the ratio of the extremal entries
+    #[rustfmt::skip]
+    let diagonal = CsrMatrix::try_from_csr_data(
+        3, 3,
+        vec![0, 1, 2, 3],
+        vec![0, 1, 2],
+        vec![100.0_f64, 4.0, 2.0],
+    ).unwrap();
+    let estimate = diagonal.estimate_spectral_condition(50).unwrap();
+    assert!((estimate - 50.0).abs() < 1e-6);
+
+    // The identity is perfectly conditioned
+    let identity = CsrMatrix::<f64>::identity(4);
+    let estimate = identity.estimate_spectral_condition(10).unwrap();
+    assert!((estimate - 1.0).abs() < 1e-12);
+
+    // Indefinite matrices fail the Cholesky factorization
+    #[rustfmt::skip]
+    let indefinite = CsrMatrix::try_from_csr_data(
+        2, 2,
+        vec![0, 1, 2],
+        vec![0, 1],
+        vec![1.0_f64, -1.0],
+    ).unwrap();
+    assert!(indefinite.estimate_spectral_condition(10).is_none());
+
+    // Non-square and empty matrices are rejected
+    assert!(CsrMatrix::<f64>::zeros(2, 3).estimate_spectral_condition(10).is_none());
+    assert!(CsrMatrix::<f64>::zeros(0, 0).estimate_spectral_condition(10).is_none());
+}
+
+proptest! {
+    #[test]
+    fn csr_gram_transpose_agrees_with_multiply_transpose(csr in csr_strategy()) {
+        let gram = csr.gram_transpose(false);
+        let expected = &csr * csr.transpose();
+        let gram_dense = DMatrix::from(&gram);
+        let expected_dense = DMatrix::from(&expected);
+        prop_assert_eq!(&gram_dense, &expected_dense);
+
+        // The upper triangular variant stores exactly the entries on or above the diagonal
+        let upper = csr.gram_transpose(true);
+        prop_assert!(upper.triplet_iter().all(|(i, j, _)| j >= i));
+        let upper_dense = DMatrix::from(&upper);
+        for i in 0..upper_dense.nrows() {
+            for j in i..upper_dense.ncols() {
+                prop_assert_eq!(upper_dense[(i, j)], expected_dense[(i, j)]);
+            }
+        }
+    }
+}
+
+#[test]
+fn csr_spmv_axpy_and_transpose_variant() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(2, 3, &[
+        1.0, 0.0, 3.0,
+        2.0, -1.0, 0.0,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+
+    let x = DVector::from_column_slice(&[1.0, 2.0, -1.0]);
+    let mut y = DVector::from_column_slice(&[5.0, -3.0]);
+    let expected = &y + &dense * &x * 2.0;
+    csr.spmv_axpy(2.0, &x, &mut y);
+    assert_eq!(y, expected);
+
+    let xt = DVector::from_column_slice(&[1.0, -2.0]);
+    let mut yt = DVector::from_column_slice(&[0.5, 1.0, -1.0]);
+    let expected_t = &yt + dense.transpose() * &xt * 3.0;
+    csr.spmv_transpose_axpy(3.0, &xt, &mut yt);
+    assert_eq!(yt, expected_t);
+
+    // Mismatched dimensions panic
+    assert_panics!({
+        let mut y = DVector::from_column_slice(&[0.0; 3]);
+        CsrMatrix::<f64>::zeros(2, 3).spmv_axpy(1.0, &DVector::from_column_slice(&[0.0; 3]), &mut y);
+    });
+    assert_panics!({
+        let mut y = DVector::from_column_slice(&[0.0; 2]);
+        CsrMatrix::<f64>::zeros(2, 3)
+            .spmv_transpose_axpy(1.0, &DVector::from_column_slice(&[0.0; 2]), &mut y);
+    });
+}
+
+#[test]
+fn csr_detect_block_size() {
+    // A 6x6 matrix built from fully populated 2x2 blocks
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(6, 6, &[
+        1.0, 2.0, 0.0, 0.0, 0.0, 0.0,
+        3.0, 4.0, 0.0, 0.0, 0.0, 0.0,
+        0.0, 0.0, 5.0, 6.0, 0.0, 0.0,
+        0.0, 0.0, 7.0, 8.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0, 9.0, 1.0,
+        0.0, 0.0, 0.0, 0.0, 2.0, 3.0,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+
+    // The largest aligning candidate wins; 3 and 6 do not align, 2 does
+    assert_eq!(csr.detect_block_size(&[6, 3, 2]), Some(2));
+    assert_eq!(csr.detect_block_size(&[3, 6]), None);
+    // A block size of 1 trivially aligns
+    assert_eq!(csr.detect_block_size(&[1]), Some(1));
+    // Candidates that do not divide the dimensions are skipped
+    assert_eq!(csr.detect_block_size(&[4, 5]), None);
+    // Candidate order does not matter
+    assert_eq!(csr.detect_block_size(&[2, 3, 6]), Some(2));
+
+    // A partially populated block breaks alignment
+    let mut partial = dense;
+    partial[(0, 1)] = 0.0;
+    let partial = CsrMatrix::from(&partial);
+    assert_eq!(partial.detect_block_size(&[2]), None);
+
+    // An empty matrix aligns to any block size that divides its dimensions
+    assert_eq!(CsrMatrix::<f64>::zeros(6, 6).detect_block_size(&[2, 3]), Some(3));
+}
+
+#[test]
+fn csr_swap_rows_and_columns() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(3, 4, &[
+        1.0, 0.0, 2.0, 0.0,
+        0.0, 3.0, 0.0, 0.0,
+        4.0, 0.0, 5.0, 6.0,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+
+    let mut swapped = csr.clone();
+    swapped.swap_rows(0, 2);
+    let mut expected = dense.clone();
+    expected.swap_rows(0, 2);
+    assert_eq!(DMatrix::from(&swapped), expected);
+
+    // Swapping back restores the original matrix, and a self-swap is a no-op
+    swapped.swap_rows(2, 0);
+    swapped.swap_rows(1, 1);
+    assert_eq!(swapped, csr);
+
+    let mut swapped = csr.clone();
+    swapped.swap_columns(1, 3);
+    let mut expected = dense.clone();
+    expected.swap_columns(1, 3);
+    assert_eq!(DMatrix::from(&swapped), expected);
+
+    swapped.swap_columns(3, 1);
+    swapped.swap_columns(2, 2);
+    assert_eq!(swapped, csr);
+
+    // Out-of-bounds indices panic
+    assert_panics!(CsrMatrix::<f64>::zeros(3, 4).swap_rows(0, 3));
+    assert_panics!(CsrMatrix::<f64>::zeros(3, 4).swap_columns(4, 0));
+}
+
+#[test]
+fn csr_mul_sparse_vector() {
+    use nalgebra_sparse::SparseVector;
+
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(4, 5, &[
+        1, 0, 2, 0, 0,
+        0, 3, 0, 0, 0,
+        0, 0, 0, 4, 5,
+        6, 0, -2, 0, 0,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+
+    let x = SparseVector::try_from_parts(5, vec![0, 2], vec![2, 1]).unwrap();
+    let y = csr.mul_sparse_vector(&x);
+    assert_eq!(y.len(), 4);
+    // Rows 1 and 2 have no overlap with the support of x and are not stored
+    assert_eq!(y.indices(), &[0, 3]);
+    assert_eq!(y.values(), &[4, 10]);
+
+    // Cancellation produces an explicitly stored zero, not a missing entry
+    let cancel = SparseVector::try_from_parts(5, vec![0, 2], vec![2, -1]).unwrap();
+    let y = csr.mul_sparse_vector(&cancel);
+    assert_eq!(y.indices(), &[0, 3]);
+    assert_eq!(y.values(), &[0, 14]);
+
+    // An empty vector yields an empty result
+    let empty = SparseVector::<i32>::zeros(5);
+    assert_eq!(csr.mul_sparse_vector(&empty).nnz(), 0);
+
+    // Mismatched dimensions panic
+    assert_panics!(CsrMatrix::<i32>::zeros(4, 5).mul_sparse_vector(&SparseVector::zeros(4)));
+}
+
+#[test]
+fn csr_from_mask() {
+    #[rustfmt::skip]
+    let mask = DMatrix::from_row_slice(3, 4, &[
+        true, false, true, false,
+        false, false, false, false,
+        false, true, true, true,
+    ]);
+    let csr = CsrMatrix::from_mask(&mask, |i, j| (10 * i + j) as i32);
+
+    assert_eq!(csr.nrows(), 3);
+    assert_eq!(csr.ncols(), 4);
+    assert_eq!(csr.row_offsets(), &[0, 2, 2, 5]);
+    assert_eq!(csr.col_indices(), &[0, 2, 1, 2, 3]);
+    assert_eq!(csr.values(), &[0, 2, 21, 22, 23]);
+
+    // An all-false mask yields a matrix with no stored entries, and the generator may
+    // produce explicit zeros without affecting the structure
+    let empty = DMatrix::from_element(2, 2, false);
+    assert_eq!(CsrMatrix::from_mask(&empty, |_, _| 1), CsrMatrix::zeros(2, 2));
+    let full = DMatrix::from_element(2, 2, true);
+    assert_eq!(CsrMatrix::from_mask(&full, |_, _| 0).nnz(), 4);
+}
+
+#[test]
+fn csr_norm_l1() {
+    #[rustfmt::skip]
+    let csr = CsrMatrix::from(&DMatrix::from_row_slice(2, 3, &[
+        1.0, -4.0, 2.0,
+        -3.0, 0.0, 5.0,
+    ]));
+    assert_eq!(csr.norm_l1(), 7.0);
+    assert_eq!(CsrMatrix::<f64>::zeros(3, 3).norm_l1(), 0.0);
+}
+
+#[test]
+fn csr_estimate_norm1_condition() {
+    use nalgebra_sparse::factorization::CscCholesky;
+    use nalgebra_sparse::CscMatrix;
+
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(2, 2, &[
+        4.0, 1.0,
+        1.0, 3.0,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+    let cholesky = CscCholesky::factor(&CscMatrix::from(&csr)).unwrap();
+
+    let estimate = csr.estimate_norm1_condition(&cholesky);
+    // Exact 1-norm condition number: ||A||_1 = 5, ||A^{-1}||_1 = 5/11
+    let exact = 25.0 / 11.0;
+    // Hager's estimate is a lower bound on the true condition number
+    assert!(estimate <= exact + 1e-12);
+    assert!(estimate >= 0.9 * exact);
+
+    // The identity is perfectly conditioned
+    let identity = CsrMatrix::<f64>::identity(4);
+    let cholesky = CscCholesky::factor(&CscMatrix::from(&identity)).unwrap();
+    assert!((identity.estimate_norm1_condition(&cholesky) - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn csr_sor_sweep_converges_to_solution() {
+    #[rustfmt::skip]
+    let a = CsrMatrix::from(&DMatrix::from_row_slice(2, 2, &[
+        4.0, 1.0,
+        1.0, 3.0,
+    ]));
+    let b = DVector::from_column_slice(&[1.0, 2.0]);
+    let exact = DVector::from_column_slice(&[1.0 / 11.0, 7.0 / 11.0]);
+
+    // Gauss-Seidel (omega = 1), forward sweeps
+    let mut x = DVector::zeros(2);
+    for _ in 0..50 {
+        a.sor_sweep(&b, &mut x, 1.0, SweepDirection::Forward);
+    }
+    assert!((&x - &exact).norm() < 1e-12);
+
+    // Over-relaxed backward sweeps converge to the same solution
+    let mut x = DVector::zeros(2);
+    for _ in 0..50 {
+        a.sor_sweep(&b, &mut x, 1.2, SweepDirection::Backward);
+    }
+    assert!((&x - &exact).norm() < 1e-12);
+
+    // The exact solution is a fixed point of the sweep
+    let mut x = exact.clone();
+    a.sor_sweep(&b, &mut x, 1.0, SweepDirection::Forward);
+    assert!((&x - &exact).norm() < 1e-14);
+
+    // A missing diagonal entry is rejected
+    assert_panics!({
+        let mut x = DVector::zeros(2);
+        let no_diag = CsrMatrix::from(&DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]));
+        no_diag.sor_sweep(&DVector::zeros(2), &mut x, 1.0, SweepDirection::Forward);
+    });
+    // As is a non-square matrix
+    assert_panics!({
+        let mut x = DVector::zeros(3);
+        CsrMatrix::<f64>::zeros(2, 3).sor_sweep(&DVector::zeros(2), &mut x, 1.0, SweepDirection::Forward);
+    });
+}
+
+#[test]
+fn csr_row_and_column_max_abs_and_ruiz_equilibration() {
+    use nalgebra_sparse::CscMatrix;
+
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(3, 3, &[
+        100.0, 0.0, -2.0,
+        0.0, 0.0, 0.0,
+        0.5, -0.01, 4.0,
+    ]);
+    let csr = CsrMatrix::from(&dense);
+
+    assert_eq!(
+        csr.row_max_abs(),
+        DVector::from_column_slice(&[100.0, 0.0, 4.0])
+    );
+    assert_eq!(
+        CscMatrix::from(&dense).column_max_abs(),
+        DVector::from_column_slice(&[100.0, 0.01, 4.0])
+    );
+
+    let (scaled, d_r, d_c) = csr.ruiz_equilibration(50);
+
+    // The scaled matrix satisfies scaled = diag(d_r) * A * diag(d_c)
+    let expected = DMatrix::from_diagonal(&d_r) * &dense * DMatrix::from_diagonal(&d_c);
+    assert!((DMatrix::from(&scaled) - expected).norm() < 1e-12);
+
+    // After equilibration, all non-empty rows and columns have maximum magnitude close to one
+    let col_max: DVector<f64> = CscMatrix::from(&scaled).column_max_abs();
+    for &max in scaled.row_max_abs().iter().chain(col_max.iter()) {
+        assert!(max == 0.0 || (max - 1.0).abs() < 1e-3);
+    }
+    // The empty row is left unscaled
+    assert_eq!(d_r[1], 1.0);
+
+    // Zero iterations return the matrix unchanged with identity scalings
+    let (unscaled, d_r, d_c) = csr.ruiz_equilibration(0);
+    assert_eq!(unscaled, csr);
+    assert!(d_r.iter().chain(d_c.iter()).all(|&d| d == 1.0));
+}
+
+#[test]
+fn csr_from_rows_data() {
+    let rows = vec![
+        (vec![0, 2], vec![1, 2]),
+        (vec![], vec![]),
+        (vec![1, 2, 3], vec![3, 4, 5]),
+    ];
+    let csr = CsrMatrix::from_rows_data(4, rows).unwrap();
+    assert_eq!(csr.nrows(), 3);
+    assert_eq!(csr.ncols(), 4);
+    assert_eq!(csr.row_offsets(), &[0, 2, 2, 5]);
+    assert_eq!(csr.col_indices(), &[0, 2, 1, 2, 3]);
+    assert_eq!(csr.values(), &[1, 2, 3, 4, 5]);
+
+    // No rows yields an empty matrix
+    assert_eq!(
+        CsrMatrix::<i32>::from_rows_data(4, Vec::new()).unwrap(),
+        CsrMatrix::zeros(0, 4)
+    );
+
+    // Mismatched per-row array lengths are rejected
+    let err = CsrMatrix::from_rows_data(4, vec![(vec![0], vec![1, 2])]).unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::InvalidStructure);
+    assert!(err.to_string().contains("Row 0"));
+
+    // Unsorted, duplicate or out-of-bounds column indices are rejected
+    let err = CsrMatrix::from_rows_data(4, vec![(vec![2, 0], vec![1, 2])]).unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::InvalidStructure);
+    let err = CsrMatrix::from_rows_data(4, vec![(vec![1, 1], vec![1, 2])]).unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::DuplicateEntry);
+    let err = CsrMatrix::from_rows_data(4, vec![(vec![4], vec![1])]).unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::IndexOutOfBounds);
+}
+
+#[test]
+fn csr_debug_dump() {
+    #[rustfmt::skip]
+    let csr = CsrMatrix::try_from_csr_data(
+        2, 3,
+        vec![0, 2, 3],
+        vec![0, 2, 1],
+        vec![1.0, 2.0, 3.0],
+    ).unwrap();
+
+    assert_eq!(
+        csr.debug_dump(),
+        "offsets=[0, 2, 3], indices=[0, 2, 1], values=[1.0, 2.0, 3.0], dims=(2, 3)"
+    );
+
+    // The dumped arrays reconstruct the matrix through try_from_csr_data
+    let roundtrip =
+        CsrMatrix::try_from_csr_data(2, 3, vec![0, 2, 3], vec![0, 2, 1], vec![1.0, 2.0, 3.0])
+            .unwrap();
+    assert_eq!(roundtrip, csr);
+
+    assert_eq!(
+        CsrMatrix::<i32>::zeros(1, 1).debug_dump(),
+        "offsets=[0, 0], indices=[], values=[], dims=(1, 1)"
+    );
+}
@@ -2,8 +2,10 @@ use matrixcompare::assert_matrix_eq;
 use nalgebra::matrix;
 use nalgebra::Complex;
 use nalgebra_sparse::io::{
-    load_coo_from_matrix_market_file, load_coo_from_matrix_market_str, save_to_matrix_market_file,
-    save_to_matrix_market_str,
+    load_coo_from_matrix_market_file, load_coo_from_matrix_market_str,
+    load_dense_from_matrix_market_str, matrix_market_entries, save_coo_to_matrix_market_str,
+    save_to_matrix_market_file, save_to_matrix_market_str, DataType, MatrixMarketErrorKind,
+    Sparsity, StorageScheme,
 };
 use nalgebra_sparse::proptest::coo_no_duplicates;
 use nalgebra_sparse::CooMatrix;
@@ -446,6 +448,47 @@ fn test_matrixmarket_write_complex() {
     assert_eq!(matrixmarket_str, expected);
 }
 
+#[test]
+fn test_matrixmarket_write_coo_general() {
+    // Duplicate triplets are written as-is, in insertion order
+    let mut coo_matrix = CooMatrix::new(2, 3);
+    coo_matrix.push(1, 2, 3);
+    coo_matrix.push(0, 0, 1);
+    coo_matrix.push(1, 2, 4);
+    let expected = r#"%%matrixmarket matrix coordinate integer general
+% matrixmarket file generated by nalgebra-sparse.
+2 3 3
+2 3 3
+1 1 1
+2 3 4
+"#;
+    let matrixmarket_str = save_coo_to_matrix_market_str(&coo_matrix, false);
+    assert_eq!(matrixmarket_str, expected);
+}
+
+#[test]
+fn test_matrixmarket_write_coo_symmetric() {
+    let mut coo_matrix = CooMatrix::new(3, 3);
+    coo_matrix.push(2, 0, 5);
+    coo_matrix.push(0, 0, 1);
+    // Strictly upper-triangle triplets are skipped, assumed to mirror the lower triangle
+    coo_matrix.push(0, 2, 5);
+    coo_matrix.push(1, 1, 2);
+    let expected = r#"%%matrixmarket matrix coordinate integer symmetric
+% matrixmarket file generated by nalgebra-sparse.
+3 3 3
+3 1 5
+1 1 1
+2 2 2
+"#;
+    let matrixmarket_str = save_coo_to_matrix_market_str(&coo_matrix, true);
+    assert_eq!(matrixmarket_str, expected);
+
+    // The loader expands the symmetric format back to the full matrix
+    let loaded: CooMatrix<i32> = load_coo_from_matrix_market_str(&matrixmarket_str).unwrap();
+    assert_matrix_eq!(loaded, coo_matrix);
+}
+
 proptest! {
     #[test]
     fn coo_matrix_market_roundtrip_str(coo in coo_no_duplicates(-10 ..= 10, 0 ..= 10, 0..= 10, 100)) {
@@ -466,3 +509,209 @@ proptest! {
         temp_dir.close().expect("Unable to delete temporary directory");
     }
 }
+
+#[test]
+#[rustfmt::skip]
+fn test_matrixmarket_load_dense_as_dmatrix() {
+    // A dense column vector, as typically used for right-hand sides
+    let rhs_str = r#"
+%%matrixmarket matrix array real general
+3 1
+1.0
+2.0
+3.0
+"#;
+    let rhs = load_dense_from_matrix_market_str::<f64>(rhs_str).unwrap();
+    let expected = matrix![
+        1.0;
+        2.0;
+        3.0;
+    ];
+    assert_matrix_eq!(rhs, expected);
+
+    // A general rectangular matrix; entries are stored in column-major order
+    let mat_str = r#"
+%%matrixmarket matrix array real general
+2 3
+1.0
+2.0
+3.0
+4.0
+5.0
+6.0
+"#;
+    let mat = load_dense_from_matrix_market_str::<f64>(mat_str).unwrap();
+    let expected = matrix![
+        1.0, 3.0, 5.0;
+        2.0, 4.0, 6.0;
+    ];
+    assert_matrix_eq!(mat, expected);
+
+    // A symmetric array stores only the lower triangle; the full matrix is reconstructed
+    let sym_str = r#"
+%%matrixmarket matrix array real symmetric
+2 2
+1.0
+2.0
+3.0
+"#;
+    let sym = load_dense_from_matrix_market_str::<f64>(sym_str).unwrap();
+    let expected = matrix![
+        1.0, 2.0;
+        2.0, 3.0;
+    ];
+    assert_matrix_eq!(sym, expected);
+
+    // Coordinate (sparse) data is rejected
+    let coo_str = r#"
+%%matrixmarket matrix coordinate real general
+2 2 1
+1 1 1.0
+"#;
+    let err = load_dense_from_matrix_market_str::<f64>(coo_str).unwrap_err();
+    assert_eq!(err.kind(), MatrixMarketErrorKind::TypeMismatch);
+}
+
+#[cfg(feature = "flate2")]
+#[test]
+fn test_matrixmarket_load_gz() {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use nalgebra_sparse::io::load_coo_from_matrix_market_gz;
+    use std::io::Write;
+
+    let mm_str = r#"
+%%matrixmarket matrix coordinate integer general
+5 4 2
+1 1 10
+2 3 5
+"#;
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("matrix.mtx.gz");
+    let mut encoder = GzEncoder::new(std::fs::File::create(&path).unwrap(), Compression::default());
+    encoder.write_all(mm_str.as_bytes()).unwrap();
+    encoder.finish().unwrap();
+
+    let matrix = load_coo_from_matrix_market_gz::<i32, _>(&path).unwrap();
+    let expected = load_coo_from_matrix_market_str::<i32>(mm_str).unwrap();
+    assert_eq!(matrix, expected);
+
+    // Data that is not valid gzip surfaces as an IO error
+    let bad_path = dir.path().join("not_gzip.mtx.gz");
+    std::fs::write(&bad_path, mm_str).unwrap();
+    let err = load_coo_from_matrix_market_gz::<i32, _>(&bad_path).unwrap_err();
+    assert!(matches!(err.kind(), MatrixMarketErrorKind::IOError(_)));
+}
+
+#[test]
+fn test_matrixmarket_entries_general() {
+    let file_str = r#"
+%%MatrixMarket matrix CoOrdinate real general
+% comments and blank lines are skipped
+    5 4 2
+1 1 10.0
+
+2 3 5.5
+"#
+    .trim_start();
+
+    let (header, entries) = matrix_market_entries::<f64, _>(file_str.as_bytes()).unwrap();
+    assert_eq!(header.nrows, 5);
+    assert_eq!(header.ncols, 4);
+    assert_eq!(header.nnz, 2);
+    assert_eq!(header.sparsity, Sparsity::Sparse);
+    assert_eq!(header.datatype, DataType::Real);
+    assert_eq!(header.storagescheme, StorageScheme::General);
+
+    let triplets: Vec<_> = entries.map(|entry| entry.unwrap()).collect();
+    assert_eq!(triplets, vec![(0, 0, 10.0), (1, 2, 5.5)]);
+
+    // Streaming must agree with the eager loader
+    let coo = load_coo_from_matrix_market_str::<f64>(file_str).unwrap();
+    let loaded: Vec<_> = coo.triplet_iter().map(|(i, j, v)| (i, j, *v)).collect();
+    assert_eq!(triplets, loaded);
+}
+
+#[test]
+fn test_matrixmarket_entries_symmetric_expansion() {
+    let file_str = r#"
+%%matrixmarket matrix coordinate integer symmetric
+3 3 3
+1 1 2
+2 1 3
+3 2 4
+"#
+    .trim_start();
+
+    let (header, entries) = matrix_market_entries::<i32, _>(file_str.as_bytes()).unwrap();
+    assert_eq!(header.nnz, 3);
+
+    // Mirrored entries are yielded directly after their stored counterparts, matching the
+    // expansion of the eager loader
+    let triplets: Vec<_> = entries.map(|entry| entry.unwrap()).collect();
+    assert_eq!(
+        triplets,
+        vec![(0, 0, 2), (1, 0, 3), (0, 1, 3), (2, 1, 4), (1, 2, 4)]
+    );
+    let coo = load_coo_from_matrix_market_str::<i32>(file_str).unwrap();
+    let loaded: Vec<_> = coo.triplet_iter().map(|(i, j, v)| (i, j, *v)).collect();
+    assert_eq!(triplets, loaded);
+}
+
+#[test]
+fn test_matrixmarket_entries_errors() {
+    // Invalid header
+    let err = matrix_market_entries::<f64, _>("%%not a header\n1 1 1\n".as_bytes())
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(err.kind(), MatrixMarketErrorKind::InvalidHeader);
+
+    // Dense (array) format cannot be streamed
+    let dense_str = "%%matrixmarket matrix array real general\n2 2\n1.0\n2.0\n3.0\n4.0\n";
+    let err = matrix_market_entries::<f64, _>(dense_str.as_bytes())
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(err.kind(), MatrixMarketErrorKind::TypeMismatch);
+
+    // Fewer entries than declared surface as an error at the end of iteration
+    let short_str = "%%matrixmarket matrix coordinate real general\n2 2 2\n1 1 1.0\n";
+    let (_, entries) = matrix_market_entries::<f64, _>(short_str.as_bytes()).unwrap();
+    let results: Vec<_> = entries.collect();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert_eq!(
+        results[1].as_ref().unwrap_err().kind(),
+        MatrixMarketErrorKind::EntryMismatch
+    );
+
+    // More entries than declared are rejected, and iteration stops after the error
+    let long_str = "%%matrixmarket matrix coordinate real general\n2 2 1\n1 1 1.0\n2 2 2.0\n";
+    let (_, entries) = matrix_market_entries::<f64, _>(long_str.as_bytes()).unwrap();
+    let results: Vec<_> = entries.collect();
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        results[1].as_ref().unwrap_err().kind(),
+        MatrixMarketErrorKind::EntryMismatch
+    );
+
+    // Out-of-bounds indices are caught during streaming
+    let oob_str = "%%matrixmarket matrix coordinate real general\n2 2 1\n3 1 1.0\n";
+    let (_, entries) = matrix_market_entries::<f64, _>(oob_str.as_bytes()).unwrap();
+    let results: Vec<_> = entries.collect();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(
+        results[0].as_ref().unwrap_err().kind(),
+        MatrixMarketErrorKind::SparseFormatError(_)
+    ));
+
+    // Malformed data lines are caught during streaming
+    let bad_str = "%%matrixmarket matrix coordinate real general\n2 2 1\n1 1 abc\n";
+    let (_, entries) = matrix_market_entries::<f64, _>(bad_str.as_bytes()).unwrap();
+    let results: Vec<_> = entries.collect();
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+        results[0].as_ref().unwrap_err().kind(),
+        MatrixMarketErrorKind::ParsingError
+    );
+}
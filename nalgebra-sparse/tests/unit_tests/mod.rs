@@ -6,5 +6,7 @@ mod csr;
 mod matrix_market;
 mod ops;
 mod pattern;
+mod permutation;
 mod proptest;
+mod sym_csr;
 mod test_data_examples;
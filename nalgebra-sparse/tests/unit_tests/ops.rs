@@ -5,9 +5,13 @@ use crate::common::{
 use nalgebra_sparse::csc::CscMatrix;
 use nalgebra_sparse::csr::CsrMatrix;
 use nalgebra_sparse::ops::serial::{
-    spadd_csc_prealloc, spadd_csr_prealloc, spadd_pattern, spmm_csc_dense, spmm_csc_prealloc,
-    spmm_csc_prealloc_unchecked, spmm_csr_dense, spmm_csr_pattern, spmm_csr_prealloc,
-    spmm_csr_prealloc_unchecked, spsolve_csc_lower_triangular,
+    galerkin_product, gram_pattern, spadd_csc_prealloc, spadd_csr_into_new, spadd_csr_prealloc, spadd_pattern, spmm_csc_dense,
+    spmm_csc_prealloc,
+    spmm_csc_prealloc_unchecked, spmm_csr_dense, spmm_csr_dense_blocked, spmm_csr_pattern,
+    spmm_csr_prealloc,
+    spmm_csr_masked, spmm_csr_prealloc_unchecked, spmm_csr_prealloc_with_stats, spmm_csr_semiring,
+    spmv_csr, spsolve_csc_lower_triangular, MinPlus, StandardRing,
+    try_spadd_csr_prealloc, try_spmm_csr_dense, try_spmm_csr_prealloc, OperationErrorKind,
 };
 use nalgebra_sparse::ops::Op;
 use nalgebra_sparse::pattern::SparsityPattern;
@@ -15,10 +19,14 @@ use nalgebra_sparse::proptest::{csc, csr, sparsity_pattern};
 
 use nalgebra::proptest::{matrix, vector};
 use nalgebra::{DMatrix, DMatrixSlice, DMatrixSliceMut, Scalar};
+#[cfg(feature = "simd-accel")]
+use nalgebra::DVector;
 
 use proptest::prelude::*;
 
 use matrixcompare::prop_assert_matrix_eq;
+#[cfg(feature = "simd-accel")]
+use matrixcompare::assert_matrix_eq;
 
 use std::panic::catch_unwind;
 
@@ -1280,3 +1288,793 @@ proptest! {
     }
 
 }
+
+proptest! {
+    #[test]
+    fn spmv_csr_agrees_with_dense_gemv(
+        (alpha, beta, (a, x, y)) in (
+            PROPTEST_I32_VALUE_STRATEGY,
+            PROPTEST_I32_VALUE_STRATEGY,
+            csr_strategy().prop_flat_map(|a| {
+                let ncols = a.ncols();
+                let nrows = a.nrows();
+                (Just(a),
+                 vector(PROPTEST_I32_VALUE_STRATEGY, ncols),
+                 vector(PROPTEST_I32_VALUE_STRATEGY, nrows))
+            }))
+    ) {
+        let mut spmv_result = y.clone();
+        spmv_csr(beta, &mut spmv_result, alpha, &a, &x);
+
+        // Compare with the dense equivalent y <- beta * y + alpha * A * x
+        let gemv_result = &y * beta + DMatrix::from(&a) * &x * alpha;
+
+        prop_assert_eq!(spmv_result, gemv_result);
+    }
+}
+
+proptest! {
+    #[test]
+    fn dense_mul_csr_agrees_with_dense_mul_dense(
+        (a, b) in csr_strategy().prop_flat_map(|b| {
+            let nrows = b.nrows();
+            (matrix(PROPTEST_I32_VALUE_STRATEGY, PROPTEST_MATRIX_DIM, nrows), Just(b))
+        })
+    ) {
+        let result = &a * &b;
+        let expected = &a * DMatrix::from(&b);
+        prop_assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn dense_mul_csc_agrees_with_dense_mul_dense(
+        (a, b) in csc_strategy().prop_flat_map(|b| {
+            let nrows = b.nrows();
+            (matrix(PROPTEST_I32_VALUE_STRATEGY, PROPTEST_MATRIX_DIM, nrows), Just(b))
+        })
+    ) {
+        let result = &a * &b;
+        let expected = &a * DMatrix::from(&b);
+        prop_assert_eq!(result, expected);
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn spmm_csr_prealloc_unchecked_panics_on_pattern_mismatch_in_debug() {
+    // The pattern of C is missing the entry (0, 1) produced by A * B
+    let a = CsrMatrix::try_from_csr_data(2, 2, vec![0, 1, 2], vec![0, 1], vec![1i32, 1]).unwrap();
+    let b = CsrMatrix::try_from_csr_data(2, 2, vec![0, 2, 2], vec![0, 1], vec![1i32, 1]).unwrap();
+    let c = CsrMatrix::try_from_csr_data(2, 2, vec![0, 1, 1], vec![0], vec![0i32]).unwrap();
+    let result = catch_unwind(|| {
+        let mut c = c.clone();
+        let _ = spmm_csr_prealloc_unchecked(0, &mut c, 1, Op::NoOp(&a), Op::NoOp(&b));
+    });
+    assert!(result.is_err());
+    // With a (superset) pattern that can hold the product, the kernel succeeds
+    let mut c = CsrMatrix::try_from_csr_data(2, 2, vec![0, 2, 2], vec![0, 1], vec![0i32, 0]).unwrap();
+    spmm_csr_prealloc_unchecked(0, &mut c, 1, Op::NoOp(&a), Op::NoOp(&b)).unwrap();
+    assert_eq!(c.values(), &[1, 1]);
+}
+
+proptest! {
+    #[test]
+    fn spmm_csr_masked_agrees_with_masked_dense_product(
+        a in csr(PROPTEST_I32_VALUE_STRATEGY, 5, 4, 16),
+        b in csr(PROPTEST_I32_VALUE_STRATEGY, 4, 6, 16),
+        mask in sparsity_pattern(5, 6, 20),
+        alpha in PROPTEST_I32_VALUE_STRATEGY)
+    {
+        let masked = spmm_csr_masked(&mask, alpha, Op::NoOp(&a), Op::NoOp(&b)).unwrap();
+        prop_assert_eq!(masked.pattern(), &mask);
+
+        // The stored entries agree with the full product at the masked positions, and the
+        // positions outside the mask are (implicitly) zero
+        let full = DMatrix::from(&a) * DMatrix::from(&b) * alpha;
+        let masked_dense = DMatrix::from(&masked);
+        for i in 0..masked_dense.nrows() {
+            for j in 0..masked_dense.ncols() {
+                let expected = if mask.lane(i).contains(&j) { full[(i, j)] } else { 0 };
+                prop_assert_eq!(masked_dense[(i, j)], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn spmm_csr_masked_transpose_agrees_with_no_op(
+        a in csr(PROPTEST_I32_VALUE_STRATEGY, 4, 5, 16),
+        b in csr(PROPTEST_I32_VALUE_STRATEGY, 6, 4, 16),
+        mask in sparsity_pattern(5, 6, 20))
+    {
+        let a_t = a.transpose();
+        let b_t = b.transpose();
+        let transposed =
+            spmm_csr_masked(&mask, 1, Op::Transpose(&a), Op::Transpose(&b)).unwrap();
+        let no_op = spmm_csr_masked(&mask, 1, Op::NoOp(&a_t), Op::NoOp(&b_t)).unwrap();
+        prop_assert_eq!(transposed, no_op);
+    }
+}
+
+proptest! {
+    #[test]
+    fn spadd_csr_prealloc_equal_patterns_agrees_with_dense(
+        (c, a, alpha, beta) in csr_strategy()
+            .prop_flat_map(|c| {
+                let a_values = vec![PROPTEST_I32_VALUE_STRATEGY; c.nnz()];
+                (Just(c), a_values, PROPTEST_I32_VALUE_STRATEGY, PROPTEST_I32_VALUE_STRATEGY)
+            })
+            .prop_map(|(c, a_values, alpha, beta)| {
+                // `a` shares an equal but pointer-distinct pattern with `c`, which exercises
+                // the structural equality fast path
+                let a = CsrMatrix::try_from_pattern_and_values(c.pattern().clone(), a_values)
+                    .unwrap();
+                (c, a, alpha, beta)
+            }))
+    {
+        let mut c_sparse = c.clone();
+        spadd_csr_prealloc(beta, &mut c_sparse, alpha, Op::NoOp(&a)).unwrap();
+
+        let mut c_dense = DMatrix::from(&c);
+        c_dense = c_dense * beta + DMatrix::from(&a) * alpha;
+        prop_assert_eq!(&DMatrix::from(&c_sparse), &c_dense);
+    }
+}
+
+#[cfg(feature = "simd-accel")]
+#[test]
+fn spmv_csr_simd_path_agrees_with_dense_gemv() {
+    // Wide rows whose lengths are not a multiple of the SIMD width, so that both the
+    // vectorized main loop and the scalar remainder loop are exercised.
+    let ncols = 70;
+    let dense_f64 = DMatrix::from_fn(3, ncols, |i, j| ((i + 2) * j) as f64 * 0.25 - 3.0);
+    let a = CsrMatrix::from(&dense_f64);
+    let x = DVector::from_fn(ncols, |i, _| i as f64 * 0.5 - 8.0);
+    let y = DVector::from_fn(3, |i, _| i as f64);
+
+    let mut result = y.clone();
+    spmv_csr(2.0, &mut result, 3.0, &a, &x);
+    let expected = &y * 2.0 + &dense_f64 * &x * 3.0;
+    assert_matrix_eq!(result, expected, comp = abs, tol = 1e-12);
+
+    let dense_f32 = dense_f64.map(|v| v as f32);
+    let a = CsrMatrix::from(&dense_f32);
+    let x = x.map(|v| v as f32);
+    let y = y.map(|v| v as f32);
+
+    let mut result = y.clone();
+    spmv_csr(2.0, &mut result, 3.0, &a, &x);
+    let expected = &y * 2.0 + &dense_f32 * &x * 3.0;
+    assert_matrix_eq!(result, expected, comp = abs, tol = 1e-2);
+}
+
+#[test]
+fn spmm_csr_dense_all_trans_combinations_with_strided_b() {
+    // A non-square sparse operand, so that transposing it changes the shape
+    let a = CsrMatrix::try_from_csr_data(3, 2, vec![0, 1, 3, 4], vec![1, 0, 1, 0], vec![
+        2, -3, 4, 5,
+    ])
+    .unwrap();
+    let a_dense = DMatrix::from(&a);
+    let (alpha, beta) = (3, 2);
+
+    // The dense operand is a view into the interior of a larger matrix, so that its column
+    // stride differs from the number of rows of the view itself
+    let b_large = DMatrix::from_fn(8, 10, |i, j| (i * 10 + j) as i32 - 35);
+
+    for &(trans_a, trans_b) in &[(false, false), (false, true), (true, false), (true, true)] {
+        let (c_rows, common_dim) = if trans_a {
+            (a.ncols(), a.nrows())
+        } else {
+            (a.nrows(), a.ncols())
+        };
+        let c_cols = 4;
+        let b_shape = if trans_b {
+            (c_cols, common_dim)
+        } else {
+            (common_dim, c_cols)
+        };
+        let b = b_large.slice((1, 1), b_shape);
+
+        let op_a_dense = if trans_a {
+            a_dense.transpose()
+        } else {
+            a_dense.clone()
+        };
+        let op_b_dense = if trans_b {
+            b.transpose()
+        } else {
+            b.clone_owned()
+        };
+
+        let mut c = DMatrix::from_fn(c_rows, c_cols, |i, j| (i + 2 * j) as i32);
+        let expected = &c * beta + op_a_dense * op_b_dense * alpha;
+
+        let op_a = if trans_a {
+            Op::Transpose(&a)
+        } else {
+            Op::NoOp(&a)
+        };
+        let op_b = if trans_b {
+            Op::Transpose(&b)
+        } else {
+            Op::NoOp(&b)
+        };
+        spmm_csr_dense(beta, &mut c, alpha, op_a, op_b);
+
+        assert_eq!(c, expected, "trans_a: {}, trans_b: {}", trans_a, trans_b);
+    }
+}
+
+#[test]
+fn spmm_csr_dense_beta_zero_ignores_nan_in_c() {
+    let a = CsrMatrix::try_from_csr_data(2, 3, vec![0, 2, 3], vec![0, 2, 1], vec![
+        1.0, 2.0, -3.0,
+    ])
+    .unwrap();
+    let a_dense = DMatrix::from(&a);
+    let b = DMatrix::from_fn(3, 2, |i, j| (i + 2 * j) as f64);
+
+    // With beta = 0, the prior contents of C must be ignored entirely, matching BLAS
+    // semantics - even when C is filled with NaN
+    let mut c = DMatrix::from_element(2, 2, f64::NAN);
+    spmm_csr_dense(0.0, &mut c, 2.0, Op::NoOp(&a), Op::NoOp(&b));
+    assert_eq!(c, &a_dense * &b * 2.0);
+
+    let mut c = DMatrix::from_element(3, 2, f64::NAN);
+    spmm_csr_dense(0.0, &mut c, 2.0, Op::Transpose(&a), Op::NoOp(&b.rows(0, 2).clone_owned()));
+    assert_eq!(c, a_dense.transpose() * b.rows(0, 2) * 2.0);
+}
+
+proptest! {
+    #[test]
+    fn csr_mul_csc_agrees_with_csr_mul_csr(
+        (a, b) in csr_strategy().prop_flat_map(|a| {
+            let ncols = a.ncols();
+            (Just(a), csr(PROPTEST_I32_VALUE_STRATEGY, ncols, PROPTEST_MATRIX_DIM, PROPTEST_MAX_NNZ))
+        })
+    ) {
+        let b_csc = CscMatrix::from(&b);
+
+        // All reference/value combinations of the mixed-format product
+        prop_assert_eq!(&a * &b_csc, &a * &b);
+        prop_assert_eq!(&a * b_csc.clone(), &a * &b);
+        prop_assert_eq!(a.clone() * &b_csc, &a * &b);
+        prop_assert_eq!(a.clone() * b_csc, &a * &b);
+    }
+}
+
+#[test]
+fn try_variants_return_incompatible_dimensions_errors() {
+    let a = CsrMatrix::from(&DMatrix::from_row_slice(2, 3, &[1, 0, 2, 0, 3, 0]));
+    let b = CsrMatrix::from(&DMatrix::from_row_slice(3, 2, &[1, 2, 0, 1, 1, 0]));
+
+    // Compatible dimensions agree with the panicking versions
+    let pattern = spmm_csr_pattern(a.pattern(), b.pattern());
+    let mut c = CsrMatrix::try_from_pattern_and_values(
+        pattern.clone(),
+        vec![0; pattern.nnz()],
+    )
+    .unwrap();
+    let mut c_try = c.clone();
+    spmm_csr_prealloc(0, &mut c, 1, Op::NoOp(&a), Op::NoOp(&b)).unwrap();
+    try_spmm_csr_prealloc(0, &mut c_try, 1, Op::NoOp(&a), Op::NoOp(&b)).unwrap();
+    assert_eq!(c, c_try);
+
+    // Incompatible dimensions produce an error that records the actual shapes
+    let mut c_bad = CsrMatrix::<i32>::zeros(2, 2);
+    let err = try_spmm_csr_prealloc(0, &mut c_bad, 1, Op::NoOp(&a), Op::NoOp(&a)).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        OperationErrorKind::IncompatibleDimensions { .. }
+    ));
+    assert!(err.message().contains("2x3"));
+
+    let err = try_spadd_csr_prealloc(0, &mut c_bad, 1, Op::NoOp(&a)).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        OperationErrorKind::IncompatibleDimensions { .. }
+    ));
+    assert!(err.message().contains("2x2") && err.message().contains("2x3"));
+
+    // Transposition is taken into account: op(A) here is 3x2
+    let mut c_t = a.transpose();
+    try_spadd_csr_prealloc(0, &mut c_t, 1, Op::Transpose(&a)).unwrap();
+
+    let mut c_dense = DMatrix::<i32>::zeros(2, 4);
+    let b_dense = DMatrix::<i32>::zeros(3, 4);
+    try_spmm_csr_dense(0, &mut c_dense, 1, Op::NoOp(&a), Op::NoOp(&b_dense)).unwrap();
+    let err = try_spmm_csr_dense(0, &mut c_dense, 1, Op::Transpose(&a), Op::NoOp(&b_dense))
+        .unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        OperationErrorKind::IncompatibleDimensions { .. }
+    ));
+    assert!(err.message().contains("3x2"));
+}
+
+#[test]
+fn spmm_csr_dense_blocked_agrees_with_unblocked() {
+    let a = CsrMatrix::from(&DMatrix::from_fn(7, 5, |i, j| {
+        if (i + 2 * j) % 3 == 0 {
+            (i * 5 + j) as i32 - 12
+        } else {
+            0
+        }
+    }));
+    let (alpha, beta) = (3, 2);
+
+    // Column counts below, at and above the tile width, including the many-RHS cases the
+    // blocked loop order is designed for
+    for &ncols in &[1, 7, 8, 32, 128] {
+        let b = DMatrix::from_fn(5, ncols, |i, j| (i * ncols + j) as i32 - 40);
+        let mut c = DMatrix::from_fn(7, ncols, |i, j| (i + 2 * j) as i32);
+        let mut c_blocked = c.clone();
+
+        spmm_csr_dense(beta, &mut c, alpha, Op::NoOp(&a), Op::NoOp(&b));
+        spmm_csr_dense_blocked(beta, &mut c_blocked, alpha, &a, &b);
+        assert_eq!(c_blocked, c, "ncols: {}", ncols);
+    }
+
+    // beta = 0 ignores prior contents of C, including NaN
+    let a = CsrMatrix::from(&DMatrix::from_fn(3, 4, |i, j| ((i + j) % 2) as f64));
+    let b = DMatrix::from_fn(4, 32, |i, j| (i * 32 + j) as f64 * 0.5);
+    let mut c = DMatrix::from_element(3, 32, f64::NAN);
+    spmm_csr_dense_blocked(0.0, &mut c, 2.0, &a, &b);
+    assert_eq!(c, DMatrix::from(&a) * &b * 2.0);
+}
+
+#[test]
+fn spadd_csr_into_new_combines_disjoint_patterns() {
+    let a_dense = DMatrix::from_row_slice(2, 3, &[1, 0, 2, 0, 3, 0]);
+    let b_dense = DMatrix::from_row_slice(2, 3, &[0, 4, 2, 5, 0, 6]);
+    let a = CsrMatrix::from(&a_dense);
+    let b = CsrMatrix::from(&b_dense);
+
+    let c = spadd_csr_into_new(2, &a, -1, &b).unwrap();
+    assert_eq!(DMatrix::from(&c), &a_dense * 2 - &b_dense);
+
+    // The pattern is the union of the operand patterns
+    assert_eq!(c.pattern(), &spadd_pattern(a.pattern(), b.pattern()));
+
+    // Mismatched dimensions produce an error instead of panicking
+    let err = spadd_csr_into_new(1, &a, 1, &CsrMatrix::zeros(3, 3)).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        OperationErrorKind::IncompatibleDimensions { .. }
+    ));
+    assert!(err.message().contains("2x3") && err.message().contains("3x3"));
+}
+
+#[test]
+fn spmm_csr_prealloc_with_stats_counts_work() {
+    let a = CsrMatrix::from(&DMatrix::from_row_slice(2, 3, &[1, 0, 2, 0, 3, 0]));
+    let b = CsrMatrix::from(&DMatrix::from_row_slice(3, 2, &[4, 0, 0, 5, 6, 7]));
+
+    let pattern = spmm_csr_pattern(a.pattern(), b.pattern());
+    let nnz = pattern.nnz();
+    let mut c = CsrMatrix::try_from_pattern_and_values(pattern, vec![0; nnz]).unwrap();
+    let mut c_ref = c.clone();
+
+    let stats = spmm_csr_prealloc_with_stats(0, &mut c, 1, Op::NoOp(&a), Op::NoOp(&b)).unwrap();
+    spmm_csr_prealloc(0, &mut c_ref, 1, Op::NoOp(&a), Op::NoOp(&b)).unwrap();
+    assert_eq!(c, c_ref);
+
+    // One multiply-add per scalar contribution, i.e. per compatible (a_ik, b_kj) pair
+    let expected_multiply_adds: usize = a
+        .triplet_iter()
+        .map(|(_, k, _)| b.row(k).nnz())
+        .sum();
+    assert_eq!(stats.multiply_adds, expected_multiply_adds);
+    // Every placement requires at least one comparison
+    assert!(stats.search_steps >= stats.multiply_adds);
+
+    // Transposed operands produce the same result and still count the work
+    let mut c2 = c_ref.clone();
+    let stats_t =
+        spmm_csr_prealloc_with_stats(0, &mut c2, 1, Op::Transpose(&a.transpose()), Op::NoOp(&b))
+            .unwrap();
+    assert_eq!(c2, c_ref);
+    assert_eq!(stats_t.multiply_adds, stats.multiply_adds);
+}
+
+#[test]
+fn ops_work_with_custom_dual_number_scalar() {
+    use num_traits::{One, Zero};
+
+    // A minimal dual number type: value + derivative. Zero/One/PartialEq follow the contract
+    // documented in the `ops` module: only the true identities compare equal to zero/one.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Dual {
+        re: f64,
+        eps: f64,
+    }
+
+    impl Dual {
+        fn new(re: f64, eps: f64) -> Self {
+            Self { re, eps }
+        }
+    }
+
+    impl std::ops::Add for Dual {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            Self::new(self.re + rhs.re, self.eps + rhs.eps)
+        }
+    }
+
+    impl std::ops::AddAssign for Dual {
+        fn add_assign(&mut self, rhs: Self) {
+            *self = self.clone() + rhs;
+        }
+    }
+
+    impl std::ops::Mul for Dual {
+        type Output = Self;
+        fn mul(self, rhs: Self) -> Self {
+            Self::new(self.re * rhs.re, self.re * rhs.eps + self.eps * rhs.re)
+        }
+    }
+
+    impl std::ops::MulAssign for Dual {
+        fn mul_assign(&mut self, rhs: Self) {
+            *self = self.clone() * rhs;
+        }
+    }
+
+    impl num_traits::Zero for Dual {
+        fn zero() -> Self {
+            Self::new(0.0, 0.0)
+        }
+        fn is_zero(&self) -> bool {
+            *self == Self::zero()
+        }
+    }
+
+    impl num_traits::One for Dual {
+        fn one() -> Self {
+            Self::new(1.0, 0.0)
+        }
+    }
+
+    // a = [[x, 0], [0, x]] with dx tracked in the dual part
+    let x = Dual::new(3.0, 1.0);
+    let a = CsrMatrix::try_from_csr_data(2, 2, vec![0, 1, 2], vec![0, 1], vec![
+        x.clone(),
+        x.clone(),
+    ])
+    .unwrap();
+
+    // a * a = [[x^2, 0], [0, x^2]]; d(x^2)/dx = 2x must propagate through the kernel
+    let mut c = a.clone();
+    spmm_csr_prealloc(
+        Dual::zero(),
+        &mut c,
+        Dual::one(),
+        Op::NoOp(&a),
+        Op::NoOp(&a),
+    )
+    .unwrap();
+    assert_eq!(c.values(), &[Dual::new(9.0, 6.0), Dual::new(9.0, 6.0)]);
+
+    // beta = one must leave the previous contents intact (fast path skips the scaling)
+    let mut c2 = c.clone();
+    spadd_csr_prealloc(Dual::one(), &mut c2, Dual::one(), Op::NoOp(&a)).unwrap();
+    assert_eq!(c2.values(), &[Dual::new(12.0, 7.0), Dual::new(12.0, 7.0)]);
+
+    // beta = zero must ignore prior contents entirely in the dense kernel
+    let b = DMatrix::from_element(2, 1, Dual::new(1.0, 0.0));
+    let mut c_dense = DMatrix::from_element(2, 1, Dual::new(f64::NAN, f64::NAN));
+    spmm_csr_dense(
+        Dual::zero(),
+        &mut c_dense,
+        Dual::one(),
+        Op::NoOp(&a),
+        Op::NoOp(&b),
+    );
+    assert_eq!(
+        c_dense,
+        DMatrix::from_element(2, 1, Dual::new(3.0, 1.0))
+    );
+}
+
+#[test]
+fn gram_pattern_matches_product_pattern() {
+    let a = CsrMatrix::from(&DMatrix::from_row_slice(3, 4, &[
+        1, 0, 2, 0,
+        0, 3, 0, 0,
+        4, 0, 0, 5,
+    ]));
+
+    let pattern = gram_pattern(a.pattern());
+    assert_eq!(pattern.major_dim(), 4);
+    assert_eq!(pattern.minor_dim(), 4);
+
+    // The pattern agrees with the one obtained by actually computing A^T * A
+    let gram = a.transpose() * &a;
+    assert_eq!(&pattern, gram.pattern());
+
+    // The pattern is symmetric and sorted within each lane
+    for (i, j) in pattern.entries() {
+        assert!(pattern.lane(j).binary_search(&i).is_ok());
+    }
+    for lane_idx in 0..pattern.major_dim() {
+        let lane = pattern.lane(lane_idx);
+        assert!(lane.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    // Empty matrices produce an empty square pattern
+    let empty = CsrMatrix::<i32>::zeros(2, 3);
+    let empty_pattern = gram_pattern(empty.pattern());
+    assert_eq!(empty_pattern.major_dim(), 3);
+    assert_eq!(empty_pattern.nnz(), 0);
+}
+
+#[test]
+fn spmm_csr_semiring_standard_ring_agrees_with_product() {
+    let a = CsrMatrix::from(&DMatrix::from_row_slice(3, 4, &[
+        1, 0, 2, 0,
+        0, 3, 0, 4,
+        5, 0, 0, 6,
+    ]));
+    let b = CsrMatrix::from(&DMatrix::from_row_slice(4, 2, &[
+        1, 2,
+        0, 3,
+        4, 0,
+        5, 6,
+    ]));
+
+    let pattern = spmm_csr_pattern(a.pattern(), b.pattern());
+    let nnz = pattern.nnz();
+    let mut c = CsrMatrix::try_from_pattern_and_values(pattern, vec![0; nnz]).unwrap();
+    spmm_csr_semiring::<StandardRing, _>(&mut c, &a, &b).unwrap();
+
+    assert_eq!(c, &a * &b);
+
+    // A pattern that cannot hold the product is reported as an error
+    let mut c_too_small =
+        CsrMatrix::try_from_csr_data(3, 2, vec![0, 1, 1, 1], vec![0], vec![0]).unwrap();
+    let result = spmm_csr_semiring::<StandardRing, _>(&mut c_too_small, &a, &b);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        OperationErrorKind::InvalidPattern
+    ));
+}
+
+#[test]
+fn spmm_csr_semiring_min_plus_computes_shortest_two_leg_paths() {
+    // Adjacency matrix of a small directed graph, where a stored entry is an edge weight.
+    // Note that the stored zero on the diagonal is a zero-weight self-loop, not an absent
+    // edge.
+    let a = CsrMatrix::try_from_csr_data(
+        3,
+        3,
+        vec![0, 3, 5, 7],
+        vec![0, 1, 2, 1, 2, 0, 2],
+        vec![0.0, 1.0, 5.0, 0.0, 1.0, 2.0, 0.0],
+    )
+    .unwrap();
+
+    let pattern = spmm_csr_pattern(a.pattern(), a.pattern());
+    let nnz = pattern.nnz();
+    let mut c = CsrMatrix::try_from_pattern_and_values(pattern, vec![0.0; nnz]).unwrap();
+    spmm_csr_semiring::<MinPlus, _>(&mut c, &a, &a).unwrap();
+
+    // Compare against a brute-force min-plus product over the stored entries
+    let n = 3;
+    let mut expected = DMatrix::from_element(n, n, f64::INFINITY);
+    for (i, k, a_ik) in a.triplet_iter() {
+        for (k2, j, a_kj) in a.triplet_iter() {
+            if k == k2 {
+                expected[(i, j)] = expected[(i, j)].min(a_ik + a_kj);
+            }
+        }
+    }
+
+    for (i, j, c_ij) in c.triplet_iter() {
+        assert_eq!(*c_ij, expected[(i, j)]);
+    }
+    // Entries outside the product pattern correspond to unreachable pairs
+    let c_dense = DMatrix::from(&c);
+    for i in 0..n {
+        for j in 0..n {
+            if c.index_entry(i, j) == nalgebra_sparse::SparseEntry::Zero {
+                assert_eq!(expected[(i, j)], f64::INFINITY);
+            } else {
+                assert_eq!(c_dense[(i, j)], expected[(i, j)]);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+proptest! {
+    #[test]
+    fn par_spadd_csr_agrees_with_serial(
+        SpaddCsrArgs { c, beta, alpha, a } in spadd_csr_prealloc_args_strategy()
+    ) {
+        use nalgebra_sparse::ops::parallel::par_spadd_csr;
+
+        // The parallel kernel only covers the non-transposed case, so realize the
+        // transpose before the call
+        let a = match a {
+            Op::NoOp(a) => a,
+            Op::Transpose(a) => a.transpose(),
+        };
+
+        let mut c_serial = c.clone();
+        spadd_csr_prealloc(beta, &mut c_serial, alpha, Op::NoOp(&a)).unwrap();
+
+        let mut c_parallel = c;
+        par_spadd_csr(beta, &mut c_parallel, alpha, &a).unwrap();
+
+        prop_assert_eq!(c_parallel, c_serial);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_spadd_csr_reports_pattern_errors() {
+    use nalgebra_sparse::ops::parallel::par_spadd_csr;
+
+    let a = CsrMatrix::from(&DMatrix::from_row_slice(2, 2, &[1, 2, 0, 3]));
+    let mut c = CsrMatrix::identity(2);
+    let result = par_spadd_csr(1, &mut c, 1, &a);
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        OperationErrorKind::InvalidPattern
+    ));
+}
+
+#[test]
+fn spmm_csr_dense_strided_output_slices() {
+    let a =
+        CsrMatrix::try_from_csr_data(3, 3, vec![0, 2, 3, 5], vec![0, 2, 1, 0, 2], vec![
+            1, -2, 3, 4, 5,
+        ])
+        .unwrap();
+    let b = DMatrix::from_row_slice(3, 2, &[1, 2, -1, 0, 3, 1]);
+    let (alpha, beta) = (3, 2);
+
+    for &trans_a in &[false, true] {
+        let op_a = if trans_a {
+            Op::Transpose(&a)
+        } else {
+            Op::NoOp(&a)
+        };
+
+        // Reference result on a plain column-major matrix
+        let mut c_ref = DMatrix::from_fn(3, 2, |i, j| (i + 2 * j) as i32);
+        spmm_csr_dense(beta, &mut c_ref, alpha, op_a, Op::NoOp(&b));
+
+        // The output is a view into a row-major buffer: the stride between rows is the
+        // number of columns, and consecutive entries of a row are adjacent in memory
+        let mut row_major = vec![0; 6];
+        for i in 0..3 {
+            for j in 0..2 {
+                row_major[i * 2 + j] = (i + 2 * j) as i32;
+            }
+        }
+        let c = DMatrixSliceMut::from_slice_with_strides_mut(&mut row_major, 3, 2, 2, 1);
+        spmm_csr_dense(beta, c, alpha, op_a, Op::NoOp(&b));
+        for i in 0..3 {
+            for j in 0..2 {
+                assert_eq!(row_major[i * 2 + j], c_ref[(i, j)], "trans_a: {}", trans_a);
+            }
+        }
+
+        // The output is a view into a column-major buffer with padded columns (a leading
+        // dimension larger than the number of rows, as in LAPACK-style interop)
+        let mut padded = vec![-1; 5 * 2];
+        for i in 0..3 {
+            for j in 0..2 {
+                padded[i + 5 * j] = (i + 2 * j) as i32;
+            }
+        }
+        let c = DMatrixSliceMut::from_slice_with_strides_mut(&mut padded, 3, 2, 1, 5);
+        // The input is a view into a row-major buffer at the same time
+        let b_row_major: Vec<_> = (0..3).flat_map(|i| (0..2).map(move |j| (i, j))).map(|(i, j)| b[(i, j)]).collect();
+        let b_slice = DMatrixSlice::from_slice_with_strides(&b_row_major, 3, 2, 2, 1);
+        spmm_csr_dense(beta, c, alpha, op_a, Op::NoOp(b_slice));
+        for i in 0..3 {
+            for j in 0..2 {
+                assert_eq!(padded[i + 5 * j], c_ref[(i, j)], "trans_a: {}", trans_a);
+            }
+        }
+        // The padding entries between the columns are untouched
+        assert_eq!(padded[3], -1);
+        assert_eq!(padded[4], -1);
+        assert_eq!(padded[8], -1);
+        assert_eq!(padded[9], -1);
+    }
+}
+
+#[test]
+fn galerkin_product_agrees_with_chained_products() {
+    #[rustfmt::skip]
+    let a = DMatrix::from_row_slice(4, 4, &[
+        2, -1, 0, 0,
+        -1, 2, -1, 0,
+        0, -1, 2, -1,
+        0, 0, -1, 2,
+    ]);
+    #[rustfmt::skip]
+    let p = DMatrix::from_row_slice(4, 2, &[
+        1, 0,
+        1, 0,
+        0, 1,
+        0, 1,
+    ]);
+    let a = CsrMatrix::from(&a);
+    let p = CsrMatrix::from(&p);
+    let r = p.transpose();
+
+    let coarse = galerkin_product(&r, &a, &p).unwrap();
+    let expected = &r * &(&a * &p);
+    assert_eq!(coarse, expected);
+    assert_eq!(coarse.nrows(), r.nrows());
+    assert_eq!(coarse.ncols(), p.ncols());
+
+    // Incompatible dimensions are reported as errors rather than panics
+    let wrong = CsrMatrix::<i32>::zeros(3, 3);
+    assert!(matches!(
+        galerkin_product(&wrong, &a, &p).unwrap_err().kind(),
+        OperationErrorKind::IncompatibleDimensions { .. }
+    ));
+    assert!(matches!(
+        galerkin_product(&r, &a, &wrong).unwrap_err().kind(),
+        OperationErrorKind::IncompatibleDimensions { .. }
+    ));
+}
+
+proptest! {
+    #[test]
+    fn galerkin_product_matches_operator_chain(
+        SpmmCsrArgs { c: _, beta: _, alpha: _, a, b }
+        in spmm_csr_prealloc_args_strategy()
+    ) {
+        // Resolve the ops so that a * p is dimensionally valid, and use a^T as restriction
+        let a = match a {
+            Op::NoOp(a) => a,
+            Op::Transpose(a) => a.transpose(),
+        };
+        let p = match b {
+            Op::NoOp(b) => b,
+            Op::Transpose(b) => b.transpose(),
+        };
+        let r = a.transpose();
+
+        let product = galerkin_product(&r, &a, &p).unwrap();
+        prop_assert_eq!(DMatrix::from(&product), DMatrix::from(&(&r * &(&a * &p))));
+    }
+}
+
+#[test]
+fn spadd_csr_prealloc_beta_zero_ignores_stale_values() {
+    let a = CsrMatrix::from(&DMatrix::from_row_slice(2, 2, &[1.0, 0.0, 2.0, 3.0]));
+
+    // C's pattern is a superset of A's; its values are poisoned with NaN
+    #[rustfmt::skip]
+    let c_pattern = SparsityPattern::try_from_offsets_and_indices(
+        2, 2,
+        vec![0, 2, 4],
+        vec![0, 1, 0, 1],
+    ).unwrap();
+    let nan = f64::NAN;
+
+    // beta == 0 must overwrite the stale values rather than scaling them
+    let mut c =
+        CsrMatrix::try_from_pattern_and_values(c_pattern.clone(), vec![nan; 4]).unwrap();
+    spadd_csr_prealloc(0.0, &mut c, 2.0, Op::NoOp(&a)).unwrap();
+    assert_eq!(DMatrix::from(&c), DMatrix::from_row_slice(2, 2, &[2.0, 0.0, 4.0, 6.0]));
+
+    let mut c =
+        CsrMatrix::try_from_pattern_and_values(c_pattern, vec![nan; 4]).unwrap();
+    spadd_csr_prealloc(0.0, &mut c, 2.0, Op::Transpose(&a)).unwrap();
+    assert_eq!(DMatrix::from(&c), DMatrix::from_row_slice(2, 2, &[2.0, 4.0, 0.0, 6.0]));
+
+    // The aligned-pattern fast path must also skip reading stale values
+    let mut c = a.clone();
+    c.values_mut().fill(nan);
+    spadd_csr_prealloc(0.0, &mut c, 1.0, Op::NoOp(&a)).unwrap();
+    assert_eq!(c, a);
+}
@@ -1,4 +1,9 @@
+use crate::assert_panics;
+use crate::common::{PROPTEST_MATRIX_DIM, PROPTEST_MAX_NNZ};
+use nalgebra_sparse::ops::serial::spadd_pattern;
 use nalgebra_sparse::pattern::{SparsityPattern, SparsityPatternFormatError};
+use nalgebra_sparse::proptest::sparsity_pattern;
+use proptest::prelude::*;
 
 #[test]
 fn sparsity_pattern_valid_data() {
@@ -152,3 +157,177 @@ fn sparsity_pattern_try_from_invalid_data() {
         assert_eq!(pattern, Err(SparsityPatternFormatError::DuplicateEntry));
     }
 }
+
+#[test]
+fn sparsity_pattern_check_invariants() {
+    // A valid pattern satisfies the invariants
+    let pattern =
+        SparsityPattern::try_from_offsets_and_indices(3, 6, vec![0, 2, 2, 5], vec![0, 5, 1, 2, 3])
+            .unwrap();
+    assert!(pattern.check_invariants().is_ok());
+
+    // The unchecked constructor asserts validity in debug builds
+    assert_panics!(unsafe {
+        SparsityPattern::from_offset_and_indices_unchecked(3, 6, vec![0, 2, 2, 5], vec![0, 5, 2, 2, 3])
+    });
+}
+
+#[test]
+fn sparsity_pattern_connected_components() {
+    // Empty pattern: every node is its own component
+    let pattern = SparsityPattern::zeros(3, 3);
+    assert_eq!(pattern.connected_components(), (3, vec![0, 1, 2]));
+
+    // Symmetric pattern with components {0, 2, 4}, {1} and {3, 5}
+    let offsets = vec![0, 1, 1, 3, 4, 5, 6];
+    let indices = vec![2, 0, 4, 5, 2, 3];
+    let pattern = SparsityPattern::try_from_offsets_and_indices(6, 6, offsets, indices).unwrap();
+    let (count, labels) = pattern.connected_components();
+    assert_eq!(count, 3);
+    assert_eq!(labels, vec![0, 1, 0, 2, 0, 2]);
+
+    // An asymmetric pattern is treated as if symmetrized: the single entry (0, 2)
+    // connects nodes 0 and 2
+    let pattern =
+        SparsityPattern::try_from_offsets_and_indices(3, 3, vec![0, 1, 1, 1], vec![2]).unwrap();
+    assert_eq!(pattern.connected_components(), (2, vec![0, 1, 0]));
+
+    // Non-square patterns are rejected
+    let pattern = SparsityPattern::zeros(2, 3);
+    assert_panics!(pattern.connected_components());
+}
+
+#[test]
+fn sparsity_pattern_group_identical_rows() {
+    // Rows 0 and 2 share {0, 1}, rows 1 and 4 share {2}, row 3 is empty
+    let offsets = vec![0, 2, 3, 5, 5, 6];
+    let indices = vec![0, 1, 2, 0, 1, 2];
+    let pattern = SparsityPattern::try_from_offsets_and_indices(5, 3, offsets, indices).unwrap();
+
+    let groups = pattern.group_identical_rows();
+    assert_eq!(groups, vec![vec![0, 2], vec![1, 4], vec![3]]);
+
+    // Concatenating the groups yields a permutation of the row indices
+    let mut concatenated: Vec<_> = groups.into_iter().flatten().collect();
+    concatenated.sort_unstable();
+    assert_eq!(concatenated, (0..5).collect::<Vec<_>>());
+
+    // All-empty rows form a single group
+    let empty = SparsityPattern::zeros(3, 3);
+    assert_eq!(empty.group_identical_rows(), vec![vec![0, 1, 2]]);
+
+    // A pattern without rows produces no groups
+    let no_rows = SparsityPattern::zeros(0, 3);
+    assert!(no_rows.group_identical_rows().is_empty());
+}
+
+proptest! {
+    #[test]
+    fn sparsity_pattern_union_nnz_agrees_with_spadd_pattern(
+        (a, b) in sparsity_pattern(PROPTEST_MATRIX_DIM, PROPTEST_MATRIX_DIM, PROPTEST_MAX_NNZ)
+            .prop_flat_map(|a| {
+                let b = sparsity_pattern(a.major_dim(), a.minor_dim(), PROPTEST_MAX_NNZ);
+                (Just(a), b)
+            }))
+    {
+        prop_assert_eq!(a.union_nnz(&b), spadd_pattern(&a, &b).nnz());
+        prop_assert_eq!(b.union_nnz(&a), a.union_nnz(&b));
+        prop_assert_eq!(a.union_nnz(&a), a.nnz());
+    }
+}
+
+#[test]
+fn sparsity_pattern_eq_and_hash_are_structural() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(pattern: &SparsityPattern) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        pattern.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // Equal content in distinct allocations compares (and hashes) equal, which enables
+    // structural fast paths and memoization keyed by pattern
+    let a = SparsityPattern::try_from_offsets_and_indices(2, 3, vec![0, 2, 3], vec![0, 2, 1])
+        .unwrap();
+    let b = SparsityPattern::try_from_offsets_and_indices(2, 3, vec![0, 2, 3], vec![0, 2, 1])
+        .unwrap();
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+
+    // Same entries but different minor dimension are distinct patterns
+    let c = SparsityPattern::try_from_offsets_and_indices(2, 4, vec![0, 2, 3], vec![0, 2, 1])
+        .unwrap();
+    assert_ne!(a, c);
+}
+
+#[test]
+fn pattern_interner_shares_equal_patterns() {
+    use nalgebra_sparse::pattern::PatternInterner;
+    use nalgebra_sparse::CsrMatrix;
+
+    // Two independently constructed matrices with equal patterns, one with a different
+    // pattern
+    let mut a =
+        CsrMatrix::try_from_csr_data(2, 3, vec![0, 2, 3], vec![0, 2, 1], vec![1, 2, 3]).unwrap();
+    let mut b =
+        CsrMatrix::try_from_csr_data(2, 3, vec![0, 2, 3], vec![0, 2, 1], vec![4, 5, 6]).unwrap();
+    let mut c =
+        CsrMatrix::try_from_csr_data(2, 3, vec![0, 1, 2], vec![0, 1], vec![7, 8]).unwrap();
+    assert!(!std::ptr::eq(a.pattern(), b.pattern()));
+
+    let mut interner = PatternInterner::new();
+    assert!(interner.is_empty());
+    interner.intern(&mut a);
+    interner.intern(&mut b);
+    interner.intern(&mut c);
+
+    // Equal patterns now share a single allocation; distinct patterns do not
+    assert!(std::ptr::eq(a.pattern(), b.pattern()));
+    assert!(!std::ptr::eq(a.pattern(), c.pattern()));
+    assert_eq!(interner.len(), 2);
+
+    // The matrices themselves are unchanged
+    assert_eq!(a.values(), &[1, 2, 3]);
+    assert_eq!(b.values(), &[4, 5, 6]);
+    assert_eq!(a.pattern().lane(0), &[0, 2]);
+
+    // Interning is idempotent
+    interner.intern(&mut a);
+    assert_eq!(interner.len(), 2);
+    assert!(std::ptr::eq(a.pattern(), b.pattern()));
+}
+
+#[test]
+fn sparsity_pattern_diff() {
+    let a = SparsityPattern::try_from_offsets_and_indices(3, 4, vec![0, 2, 3, 5], vec![0, 2, 1, 0, 3])
+        .unwrap();
+    let b = SparsityPattern::try_from_offsets_and_indices(3, 4, vec![0, 2, 3, 4], vec![0, 3, 1, 0])
+        .unwrap();
+
+    let diff = a.diff(&b);
+    assert_eq!(diff.only_in_a, vec![(0, 2), (2, 3)]);
+    assert_eq!(diff.only_in_b, vec![(0, 3)]);
+    assert_eq!(diff.in_both, vec![(0, 0), (1, 1), (2, 0)]);
+
+    // Diffing in the other direction swaps the one-sided sets
+    let reverse = b.diff(&a);
+    assert_eq!(reverse.only_in_a, diff.only_in_b);
+    assert_eq!(reverse.only_in_b, diff.only_in_a);
+    assert_eq!(reverse.in_both, diff.in_both);
+
+    // Equal patterns have an empty diff
+    let self_diff = a.diff(&a);
+    assert!(self_diff.only_in_a.is_empty());
+    assert!(self_diff.only_in_b.is_empty());
+    assert_eq!(self_diff.in_both.len(), a.nnz());
+
+    // Patterns with different major dimensions can be compared
+    let taller =
+        SparsityPattern::try_from_offsets_and_indices(4, 4, vec![0, 0, 0, 0, 1], vec![2]).unwrap();
+    let diff = a.diff(&taller);
+    assert_eq!(diff.only_in_b, vec![(3, 2)]);
+    assert_eq!(diff.only_in_a.len(), a.nnz());
+    assert!(diff.in_both.is_empty());
+}
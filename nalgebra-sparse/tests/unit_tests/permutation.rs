@@ -0,0 +1,50 @@
+use crate::assert_panics;
+use nalgebra::dvector;
+use nalgebra_sparse::permutation::{
+    apply_inverse_permutation, apply_permutation, invert_permutation,
+};
+
+#[test]
+fn invert_permutation_inverts() {
+    let p = vec![2, 0, 3, 1];
+    let ip = invert_permutation(&p);
+    assert_eq!(ip, vec![1, 3, 0, 2]);
+
+    // ip[p[i]] == i for all i
+    for (i, &old) in p.iter().enumerate() {
+        assert_eq!(ip[old], i);
+    }
+
+    // The inverse of the inverse is the original permutation
+    assert_eq!(invert_permutation(&ip), p);
+
+    // The identity is its own inverse
+    assert_eq!(invert_permutation(&[0, 1, 2]), vec![0, 1, 2]);
+    assert!(invert_permutation(&[]).is_empty());
+
+    // Invalid permutations are rejected
+    assert_panics!(invert_permutation(&[0, 2]));
+    assert_panics!(invert_permutation(&[0, 0]));
+}
+
+#[test]
+fn apply_permutation_and_inverse_roundtrip() {
+    let p = vec![2, 0, 3, 1];
+    let v = dvector![10.0, 20.0, 30.0, 40.0];
+
+    // Entry i of the result is v[p[i]]
+    let permuted = apply_permutation(&p, &v);
+    assert_eq!(permuted, dvector![30.0, 10.0, 40.0, 20.0]);
+
+    // The inverse application undoes the permutation
+    assert_eq!(apply_inverse_permutation(&p, &permuted), v);
+    // ... and agrees with applying the explicitly inverted permutation
+    assert_eq!(
+        apply_inverse_permutation(&p, &permuted),
+        apply_permutation(&invert_permutation(&p), &permuted)
+    );
+
+    // Length mismatches are rejected
+    assert_panics!(apply_permutation(&[0, 1], &dvector![1.0, 2.0, 3.0]));
+    assert_panics!(apply_inverse_permutation(&[0, 1], &dvector![1.0, 2.0, 3.0]));
+}
@@ -0,0 +1,77 @@
+use nalgebra::{DMatrix, DVector};
+use nalgebra_sparse::csr::CsrMatrix;
+use nalgebra_sparse::sym_csr::SymCsrMatrix;
+use nalgebra_sparse::SparseFormatErrorKind;
+
+#[test]
+fn sym_csr_from_csr_and_to_csr_roundtrip() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(3, 3, &[
+        2.0, 1.0, 0.0,
+        1.0, 3.0, -4.0,
+        0.0, -4.0, 5.0,
+    ]);
+    let full = CsrMatrix::from(&dense);
+
+    let sym = SymCsrMatrix::from_csr(&full, 0.0, 0.0).unwrap();
+    // Only the lower triangle is stored
+    assert_eq!(sym.nnz(), 5);
+    assert!(sym.lower().triplet_iter().all(|(i, j, _)| j <= i));
+
+    // Expansion reproduces the full matrix
+    assert_eq!(DMatrix::from(&sym.to_csr()), dense);
+
+    // An asymmetric matrix is rejected, but passes with a sufficient tolerance
+    let mut asymmetric = dense.clone();
+    asymmetric[(0, 1)] += 1e-12;
+    let asymmetric = CsrMatrix::from(&asymmetric);
+    let err = SymCsrMatrix::from_csr(&asymmetric, 0.0, 0.0).unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::InvalidStructure);
+    assert!(SymCsrMatrix::from_csr(&asymmetric, 1e-10, 0.0).is_ok());
+
+    // Non-square matrices are rejected
+    let rect = CsrMatrix::<f64>::zeros(2, 3);
+    assert!(SymCsrMatrix::from_csr(&rect, 0.0, 0.0).is_err());
+}
+
+#[test]
+fn sym_csr_try_from_lower_triangle() {
+    #[rustfmt::skip]
+    let lower = CsrMatrix::try_from_csr_data(
+        3, 3,
+        vec![0, 1, 3, 4],
+        vec![0, 0, 1, 2],
+        vec![2.0, 1.0, 3.0, 5.0],
+    ).unwrap();
+    let sym = SymCsrMatrix::try_from_lower_triangle(lower.clone()).unwrap();
+    assert_eq!(sym.into_lower(), lower);
+
+    // An entry above the diagonal is rejected
+    let with_upper =
+        CsrMatrix::try_from_csr_data(2, 2, vec![0, 2, 3], vec![0, 1, 1], vec![1.0, 2.0, 3.0])
+            .unwrap();
+    let err = SymCsrMatrix::try_from_lower_triangle(with_upper).unwrap_err();
+    assert_eq!(err.kind(), &SparseFormatErrorKind::InvalidStructure);
+
+    // As is a non-square matrix
+    assert!(SymCsrMatrix::try_from_lower_triangle(CsrMatrix::<f64>::zeros(2, 3)).is_err());
+}
+
+#[test]
+fn sym_csr_spmv_agrees_with_full_product() {
+    #[rustfmt::skip]
+    let dense = DMatrix::from_row_slice(4, 4, &[
+        2.0, 1.0, 0.0, -3.0,
+        1.0, 3.0, -4.0, 0.0,
+        0.0, -4.0, 5.0, 2.0,
+        -3.0, 0.0, 2.0, 1.0,
+    ]);
+    let sym = SymCsrMatrix::from_csr(&CsrMatrix::from(&dense), 0.0, 0.0).unwrap();
+
+    let x = DVector::from_column_slice(&[1.0, -2.0, 0.5, 3.0]);
+    let mut y = DVector::from_column_slice(&[1.0, 2.0, 3.0, 4.0]);
+    let expected = &y * 2.0 + &dense * &x * 3.0;
+
+    sym.spmv(2.0, &mut y, 3.0, &x);
+    assert_eq!(y, expected);
+}
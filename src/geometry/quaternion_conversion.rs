@@ -333,7 +333,7 @@ where
 impl<T: Scalar + Copy + PrimitiveSimdValue> From<[UnitQuaternion<T::Element>; 2]>
     for UnitQuaternion<T>
 where
-    T: From<[<T as simba::simd::SimdValue>::Element; 2]>,
+    T: From<[<T as SimdValue>::Element; 2]>,
     T::Element: Scalar + Copy,
 {
     #[inline]
@@ -345,7 +345,7 @@ where
 impl<T: Scalar + Copy + PrimitiveSimdValue> From<[UnitQuaternion<T::Element>; 4]>
     for UnitQuaternion<T>
 where
-    T: From<[<T as simba::simd::SimdValue>::Element; 4]>,
+    T: From<[<T as SimdValue>::Element; 4]>,
     T::Element: Scalar + Copy,
 {
     #[inline]
@@ -362,7 +362,7 @@ where
 impl<T: Scalar + Copy + PrimitiveSimdValue> From<[UnitQuaternion<T::Element>; 8]>
     for UnitQuaternion<T>
 where
-    T: From<[<T as simba::simd::SimdValue>::Element; 8]>,
+    T: From<[<T as SimdValue>::Element; 8]>,
     T::Element: Scalar + Copy,
 {
     #[inline]
@@ -383,7 +383,7 @@ where
 impl<T: Scalar + Copy + PrimitiveSimdValue> From<[UnitQuaternion<T::Element>; 16]>
     for UnitQuaternion<T>
 where
-    T: From<[<T as simba::simd::SimdValue>::Element; 16]>,
+    T: From<[<T as SimdValue>::Element; 16]>,
     T::Element: Scalar + Copy,
 {
     #[inline]